@@ -0,0 +1,121 @@
+//! `#[derive(Mergable)]` for [tagged_ufs](https://docs.rs/tagged_ufs).
+//!
+//! Every field is merged with its counterpart by its own `Mergable` impl,
+//! unless a strategy is chosen by `#[mergable(...)]`:
+//!
+//! * `sum`: `self.x += other.x`
+//! * `min`: keeps the less one
+//! * `max`: keeps the greater one
+//! * `extend`: `self.x.extend(other.x)`
+//! * `skip`: keeps `self.x` and drops `other.x`
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Index, Member};
+
+#[proc_macro_derive(Mergable, attributes(mergable))]
+pub fn derive_mergable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Strategy {
+    Merge,
+    Sum,
+    Min,
+    Max,
+    Extend,
+    Skip,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Mergable can only be derived for structs",
+        ));
+    };
+    let members: Vec<(Member, &syn::Field)> = match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|f| (Member::Named(f.ident.clone().unwrap()), f))
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (Member::Unnamed(Index::from(i)), f))
+            .collect(),
+        Fields::Unit => vec![],
+    };
+    let mut merges = vec![];
+    for (i, (member, field)) in members.iter().enumerate() {
+        let other = format_ident!("__other_{}", i);
+        let merge = match strategy(field)? {
+            Strategy::Merge => quote! {
+                ::tagged_ufs::Mergable::merge(&mut self.#member, #other);
+            },
+            Strategy::Sum => quote! {
+                self.#member += #other;
+            },
+            Strategy::Min => quote! {
+                if #other < self.#member {
+                    self.#member = #other;
+                }
+            },
+            Strategy::Max => quote! {
+                if #other > self.#member {
+                    self.#member = #other;
+                }
+            },
+            Strategy::Extend => quote! {
+                ::core::iter::Extend::extend(&mut self.#member, #other);
+            },
+            Strategy::Skip => quote! {
+                let _ = #other;
+            },
+        };
+        merges.push(merge);
+    }
+    let others = (0..members.len()).map(|i| format_ident!("__other_{}", i));
+    let member_names = members.iter().map(|(member, _)| member);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::tagged_ufs::Mergable for #name #ty_generics #where_clause {
+            fn merge(&mut self, other: Self) {
+                let Self { #(#member_names: #others),* } = other;
+                #(#merges)*
+            }
+        }
+    })
+}
+
+fn strategy(field: &syn::Field) -> syn::Result<Strategy> {
+    let mut res = Strategy::Merge;
+    for attr in field.attrs.iter() {
+        if !attr.path().is_ident("mergable") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            res = if meta.path.is_ident("sum") {
+                Strategy::Sum
+            } else if meta.path.is_ident("min") {
+                Strategy::Min
+            } else if meta.path.is_ident("max") {
+                Strategy::Max
+            } else if meta.path.is_ident("extend") {
+                Strategy::Extend
+            } else if meta.path.is_ident("skip") {
+                Strategy::Skip
+            } else {
+                return Err(meta.error("expected one of `sum`, `min`, `max`, `extend` or `skip`"));
+            };
+            Ok(())
+        })?;
+    }
+    Ok(res)
+}
@@ -0,0 +1,179 @@
+//! Python bindings of [tagged_ufs], where keys and tags are arbitrary Python objects.
+//!
+//! Keys must be hashable. Tags are merged by a Python callable given to the sets,
+//! which takes tags of the two sets being united and returns the merged tag.
+
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyIterator, PyList};
+use std::hash::{Hash, Hasher};
+use tagged_ufs::{Error, Mergable, Which};
+
+/// A hashable Python object, whose hash is taken once.
+struct Key {
+    obj: Py<PyAny>,
+    hash: isize,
+}
+
+impl Key {
+    fn new(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            hash: obj.hash()?,
+            obj: obj.clone().unbind(),
+        })
+    }
+}
+
+/// Raising `__eq__` is taken as unequal, as there is no way to report it.
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+            && Python::attach(|py| self.obj.bind(py).eq(other.obj.bind(py)).unwrap_or(false))
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_isize(self.hash);
+    }
+}
+
+impl Clone for Key {
+    fn clone(&self) -> Self {
+        Python::attach(|py| Self {
+            obj: self.obj.clone_ref(py),
+            hash: self.hash,
+        })
+    }
+}
+
+/// A Python object as a tag.
+///
+/// Merging keeps the absorbing tag as it is.
+/// The merged tag is computed by the Python callable before sets are united,
+/// so that exceptions it raises can be propagated, and then replaces the kept one.
+struct Tag(Py<PyAny>);
+
+impl Mergable for Tag {
+    fn merge(&mut self, _other: Self) {}
+}
+
+/// Union-find sets whose tags are merged by a Python callable.
+#[pyclass(name = "UnionFindSets", unsendable)]
+pub struct PyUnionFindSets {
+    sets: tagged_ufs::UnionFindSets<Key, Tag>,
+    merge: Py<PyAny>,
+}
+
+#[pymethods]
+impl PyUnionFindSets {
+    /// Makes new, empty sets, whose tags are merged by `merge(tag1, tag2)`.
+    #[new]
+    fn new(merge: Py<PyAny>) -> Self {
+        Self {
+            sets: tagged_ufs::UnionFindSets::new(),
+            merge,
+        }
+    }
+
+    /// Makes a singleton set of `key`, tagged by `tag`.
+    ///
+    /// Raises `ValueError` if `key` is already there.
+    fn make_set(&mut self, key: &Bound<'_, PyAny>, tag: Py<PyAny>) -> PyResult<()> {
+        self.sets
+            .make_set(Key::new(key)?, Tag(tag))
+            .map_err(to_py_err)
+    }
+
+    /// Unites sets of two keys, and tells whether they were apart.
+    ///
+    /// Raises `KeyError` if either is not there,
+    /// and whatever the merging callable raises, leaving the sets as they are.
+    fn unite(
+        &mut self,
+        py: Python<'_>,
+        key1: &Bound<'_, PyAny>,
+        key2: &Bound<'_, PyAny>,
+    ) -> PyResult<bool> {
+        let (key1, key2) = (Key::new(key1)?, Key::new(key2)?);
+        let (tag1, tag2) = match (self.sets.find(&key1), self.sets.find(&key2)) {
+            (Some(x), Some(y)) if x == y => return Ok(false),
+            (Some(x), Some(y)) => (x.tag().0.clone_ref(py), y.tag().0.clone_ref(py)),
+            (x, y) => {
+                let which = match (x, y) {
+                    (None, None) => Which::Both,
+                    (None, _) => Which::Key1,
+                    _ => Which::Key2,
+                };
+                return Err(to_py_err(Error::KeyNotFound { which }));
+            }
+        };
+        let merged = self.merge.call1(py, (tag1, tag2))?;
+        self.sets.unite(&key1, &key2).map_err(to_py_err)?;
+        if let Some(mut set) = self.sets.find_mut(&key1) {
+            *set.tag_mut() = Tag(merged);
+        }
+        Ok(true)
+    }
+
+    /// Finds the set of `key`, as a tuple of its representative, its elements and its tag,
+    /// or `None` if `key` is not there.
+    #[allow(clippy::type_complexity)]
+    fn find(
+        &self,
+        py: Python<'_>,
+        key: &Bound<'_, PyAny>,
+    ) -> PyResult<Option<(Py<PyAny>, Vec<Py<PyAny>>, Py<PyAny>)>> {
+        let Some(set) = self.sets.find(&Key::new(key)?) else {
+            return Ok(None);
+        };
+        let elements = set.iter().map(|x| x.obj.clone_ref(py)).collect();
+        Ok(Some((
+            set.key().obj.clone_ref(py),
+            elements,
+            set.tag().0.clone_ref(py),
+        )))
+    }
+
+    /// Lists all sets, each as a tuple of its elements and its tag.
+    fn sets<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let sets = self.sets.iter().map(|set| {
+            let elements: Vec<_> = set.iter().map(|x| x.obj.clone_ref(py)).collect();
+            (elements, set.tag().0.clone_ref(py))
+        });
+        PyList::new(py, sets)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyIterator::from_object(self.sets(py)?.as_any())
+    }
+
+    /// The number of sets.
+    fn __len__(&self) -> usize {
+        self.sets.len()
+    }
+
+    fn __contains__(&self, key: &Bound<'_, PyAny>) -> PyResult<bool> {
+        Ok(self.sets.find(&Key::new(key)?).is_some())
+    }
+}
+
+fn to_py_err(err: Error) -> PyErr {
+    match err {
+        Error::DuplicateKey => PyValueError::new_err(err.to_string()),
+        Error::KeyNotFound { .. } | Error::KeyNotFoundAt { .. } => {
+            PyKeyError::new_err(err.to_string())
+        }
+        _ => PyRuntimeError::new_err(err.to_string()),
+    }
+}
+
+#[pymodule(name = "tagged_ufs")]
+fn tagged_ufs_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyUnionFindSets>()
+}
+
+#[cfg(test)]
+mod test;
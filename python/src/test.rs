@@ -0,0 +1,65 @@
+use super::*;
+use pyo3::types::PyDict;
+
+fn run(script: &std::ffi::CStr) {
+    Python::attach(|py| {
+        let module = PyModule::new(py, "tagged_ufs").unwrap();
+        tagged_ufs_module(&module).unwrap();
+        let globals = PyDict::new(py);
+        globals.set_item("tagged_ufs", module).unwrap();
+        py.run(script, Some(&globals), None).unwrap();
+    });
+}
+
+#[test]
+fn unite_and_merge() {
+    run(c"
+sets = tagged_ufs.UnionFindSets(lambda x, y: x + y)
+for x in 'abcd':
+    sets.make_set(x, [x])
+assert sets.unite('a', 'b')
+assert not sets.unite('b', 'a')
+assert sets.unite('c', 'a')
+key, elements, tag = sets.find('b')
+assert key in 'abc'
+assert sorted(elements) == ['a', 'b', 'c']
+assert sorted(tag) == ['a', 'b', 'c']
+assert len(sets) == 2
+assert 'd' in sets and 'e' not in sets
+assert sorted(len(xs) for xs, _ in sets) == [1, 3]
+assert sets.find('e') is None
+");
+}
+
+#[test]
+fn errors() {
+    run(c"
+sets = tagged_ufs.UnionFindSets(lambda x, y: 1 / 0 if x == y else x + y)
+sets.make_set(1, 1)
+sets.make_set(2, 1)
+sets.make_set(3, 2)
+try:
+    sets.make_set(1, 0)
+    assert False
+except ValueError:
+    pass
+try:
+    sets.unite(1, 4)
+    assert False
+except KeyError:
+    pass
+try:
+    sets.unite(1, 2)
+    assert False
+except ZeroDivisionError:
+    pass
+assert len(sets) == 3
+assert sets.unite(1, 3)
+assert sets.find(3)[2] == 3
+try:
+    sets.make_set([], 0)
+    assert False
+except TypeError:
+    pass
+");
+}
@@ -0,0 +1,32 @@
+use crate::UnionFindSets;
+use core::hash::Hash;
+
+/// Makes sets of the equivalence closure of a relation, given as pairs of related keys,
+/// where keys are made into sets on first sight.
+///
+/// Keys related to nothing but themselves, e.g., `(x, x)`, are singleton sets.
+///
+/// ```
+/// use tagged_ufs::algorithms::equivalence_closure;
+///
+/// let sets = equivalence_closure([("a", "b"), ("c", "d"), ("b", "e")]);
+/// assert_eq!(sets.len(), 2);
+/// assert_eq!(sets.find("e").unwrap().len(), 3);
+/// ```
+pub fn equivalence_closure<K, I>(pairs: I) -> UnionFindSets<K, ()>
+where
+    K: Eq + Hash + Clone,
+    I: IntoIterator<Item = (K, K)>,
+{
+    let mut sets = UnionFindSets::new();
+    for (x, y) in pairs {
+        // membership is tested without finding, so that only the union walks paths
+        for key in [&x, &y] {
+            if !sets.contains(key) {
+                let _ = sets.make_set(key.clone(), ());
+            }
+        }
+        let _ = sets.unite(&x, &y);
+    }
+    sets
+}
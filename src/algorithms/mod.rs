@@ -0,0 +1,17 @@
+//! Ready-made algorithms built on union-find sets.
+
+mod closure;
+pub use self::closure::*;
+mod records;
+pub use self::records::*;
+#[cfg(feature = "rand")]
+mod spanning;
+#[cfg(feature = "rand")]
+pub use self::spanning::*;
+#[cfg(feature = "std")]
+mod spatial;
+#[cfg(feature = "std")]
+pub use self::spatial::*;
+
+#[cfg(test)]
+mod test;
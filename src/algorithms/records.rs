@@ -0,0 +1,82 @@
+use crate::compact::UnionFindSets;
+use crate::{Mergable, Result};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use hashbrown::hash_map::{Entry, HashMap};
+
+/// Groups records sharing any key, transitively, e.g., accounts sharing emails or phones,
+/// together with tags of records in each group merged.
+///
+/// Groups are in order of their first records,
+/// and records in a group keep their original order.
+/// Tags are merged in the same order.
+///
+/// If there are too many records,
+/// [Error::TooManyKeys](crate::Error::TooManyKeys) will be raised.
+pub fn group_records<R, K, Tag, I, Ks, F, G>(
+    records: I,
+    mut keys_of: F,
+    mut tag_of: G,
+) -> Result<Vec<(Vec<R>, Tag)>>
+where
+    I: IntoIterator<Item = R>,
+    K: Eq + Hash,
+    Tag: Mergable,
+    Ks: IntoIterator<Item = K>,
+    F: FnMut(&R) -> Ks,
+    G: FnMut(&R) -> Tag,
+{
+    let mut tagged = vec![];
+    let mut sets = UnionFindSets::new();
+    let mut owners: HashMap<K, usize, ahash::RandomState> =
+        HashMap::with_hasher(ahash::RandomState::new());
+    for (i, record) in records.into_iter().enumerate() {
+        sets.make_set(i, ())?;
+        for key in keys_of(&record) {
+            match owners.entry(key) {
+                Entry::Occupied(owner) => {
+                    sets.unite(&i, owner.get())?;
+                }
+                Entry::Vacant(owner) => {
+                    owner.insert(i);
+                }
+            }
+        }
+        let tag = tag_of(&record);
+        tagged.push((record, tag));
+    }
+    let groups = into_groups(tagged, &sets);
+    Ok(groups
+        .into_iter()
+        .filter_map(|xs| {
+            let mut xs = xs.into_iter();
+            let (first, mut tag) = xs.next()?;
+            let mut records = vec![first];
+            for (record, other) in xs {
+                records.push(record);
+                tag.merge(other);
+            }
+            Some((records, tag))
+        })
+        .collect())
+}
+
+/// Distributes records into groups by sets of their indices,
+/// in order of their first records.
+pub(crate) fn into_groups<R>(records: Vec<R>, sets: &UnionFindSets<usize, ()>) -> Vec<Vec<R>> {
+    let mut group_of: HashMap<usize, usize, ahash::RandomState> =
+        HashMap::with_hasher(ahash::RandomState::new());
+    let mut groups: Vec<Vec<R>> = vec![];
+    for (i, record) in records.into_iter().enumerate() {
+        let Some(set) = sets.find(&i) else {
+            continue;
+        };
+        let group = *group_of.entry(*set.key()).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+        groups[group].push(record);
+    }
+    groups
+}
@@ -0,0 +1,35 @@
+use crate::raw::UnionFindSets;
+use crate::Result;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Picks a random spanning forest out of candidate edges, by randomized Kruskal,
+/// i.e., candidate edges are shuffled and then taken unless they close a cycle.
+///
+/// It is the classic generator of perfect mazes.
+/// If an edge refers to an unknown node,
+/// [Error::KeyNotFound](crate::Error::KeyNotFound) will be raised;
+/// if a node is repeated, [Error::DuplicateKey](crate::Error::DuplicateKey) will be raised.
+pub fn random_spanning_tree<K, N, E, R>(nodes: N, edges: E, rng: &mut R) -> Result<Vec<(K, K)>>
+where
+    K: Eq + Hash + Clone,
+    N: IntoIterator<Item = K>,
+    E: IntoIterator<Item = (K, K)>,
+    R: Rng + ?Sized,
+{
+    let mut sets = UnionFindSets::new();
+    for node in nodes {
+        sets.make_set(node, ())?;
+    }
+    let mut edges: Vec<(K, K)> = edges.into_iter().collect();
+    edges.shuffle(rng);
+    let mut tree = Vec::with_capacity(sets.len().saturating_sub(1));
+    for (x, y) in edges {
+        if sets.unite(&x, &y)? {
+            tree.push((x, y));
+        }
+    }
+    Ok(tree)
+}
@@ -0,0 +1,108 @@
+use super::into_groups;
+use crate::compact::UnionFindSets;
+use crate::{Mergable, Result};
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+/// Sum of points, whose mean is the centroid of a cluster.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Centroid {
+    pub sum_x: f64,
+    pub sum_y: f64,
+    pub count: usize,
+}
+
+impl Centroid {
+    /// Makes the centroid of a single point.
+    pub fn point(x: f64, y: f64) -> Self {
+        Self {
+            sum_x: x,
+            sum_y: y,
+            count: 1,
+        }
+    }
+
+    /// Gets the mean point.
+    ///
+    /// If there is no point, `None` will be returned.
+    pub fn mean(&self) -> Option<(f64, f64)> {
+        (self.count > 0).then(|| {
+            let n = self.count as f64;
+            (self.sum_x / n, self.sum_y / n)
+        })
+    }
+}
+
+impl Mergable for Centroid {
+    fn merge(&mut self, other: Self) {
+        self.sum_x += other.sum_x;
+        self.sum_y += other.sum_y;
+        self.count += other.count;
+    }
+}
+
+/// Clusters points, where points within distance `r` of each other are in a same cluster.
+///
+/// Points are bucketed into a grid of cells of side `r`,
+/// so only points in nearby cells are compared.
+/// It returns indices of points of every cluster,
+/// in order of their first points.
+/// A radius which is not positive unites no points, not even equal ones.
+pub fn union_within_radius(points: &[(f64, f64)], r: f64) -> Result<Vec<Vec<usize>>> {
+    let clusters = cluster_within_radius(points, r, |_, _| ())?;
+    Ok(clusters.into_iter().map(|(xs, ())| xs).collect())
+}
+
+/// Clusters points like [union_within_radius],
+/// and summarizes every cluster by merging tags of its points, e.g., [Centroid].
+pub fn cluster_within_radius<Tag, F>(
+    points: &[(f64, f64)],
+    r: f64,
+    mut tag_of: F,
+) -> Result<Vec<(Vec<usize>, Tag)>>
+where
+    Tag: Mergable,
+    F: FnMut(usize, (f64, f64)) -> Tag,
+{
+    let mut sets = UnionFindSets::new();
+    for i in 0..points.len() {
+        sets.make_set(i, ())?;
+    }
+    if r > 0.0 {
+        let cell_of = |(x, y): (f64, f64)| ((x / r).floor() as i64, (y / r).floor() as i64);
+        let mut cells: HashMap<(i64, i64), Vec<usize>, ahash::RandomState> =
+            HashMap::with_hasher(ahash::RandomState::new());
+        for (i, point) in points.iter().enumerate() {
+            cells.entry(cell_of(*point)).or_default().push(i);
+        }
+        for (i, (x, y)) in points.iter().enumerate() {
+            let (cx, cy) = cell_of((*x, *y));
+            // points within `r` may still be two cells apart, when divisions are rounded
+            let nearby = (-2..=2).flat_map(|dx| (-2..=2).map(move |dy| (dx, dy)));
+            for (dx, dy) in nearby {
+                let Some(neighbours) = cells.get(&(cx.saturating_add(dx), cy.saturating_add(dy)))
+                else {
+                    continue;
+                };
+                for j in neighbours.iter().filter(|j| **j > i) {
+                    let (u, v) = points[*j];
+                    if (x - u).hypot(y - v) <= r {
+                        sets.unite(&i, j)?;
+                    }
+                }
+            }
+        }
+    }
+    let groups = into_groups((0..points.len()).collect(), &sets);
+    Ok(groups
+        .into_iter()
+        .filter_map(|xs| {
+            let mut tags = xs.iter().map(|i| tag_of(*i, points[*i]));
+            let mut tag = tags.next()?;
+            for other in tags {
+                tag.merge(other);
+            }
+            Some((xs, tag))
+        })
+        .collect())
+}
@@ -0,0 +1,133 @@
+use super::*;
+
+#[test]
+fn merge_accounts() {
+    let accounts = [
+        ("john", vec!["johnsmith@mail.com", "john_newyork@mail.com"]),
+        ("mary", vec!["mary@mail.com"]),
+        ("john", vec!["johnsmith@mail.com", "john00@mail.com"]),
+        ("john", vec!["johnnybravo@mail.com"]),
+        ("john", vec!["john00@mail.com", "j@mail.com"]),
+    ];
+    let groups = group_records(
+        accounts.iter(),
+        |(_, emails)| emails.iter(),
+        |(_, emails)| crate::Count(emails.len()),
+    )
+    .unwrap();
+    let counts: Vec<usize> = groups.iter().map(|(_, n)| n.0).collect();
+    assert_eq!(counts, vec![6, 1, 1]);
+    let groups: Vec<Vec<_>> = groups
+        .into_iter()
+        .map(|(xs, _)| xs.into_iter().map(|(_, emails)| emails[0]).collect())
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            vec![
+                "johnsmith@mail.com",
+                "johnsmith@mail.com",
+                "john00@mail.com"
+            ],
+            vec!["mary@mail.com"],
+            vec!["johnnybravo@mail.com"],
+        ]
+    );
+}
+
+#[test]
+fn records_without_keys_stay_alone() {
+    let groups = group_records([1, 2, 3], |_| Vec::<u8>::new(), |_| ()).unwrap();
+    assert_eq!(groups, vec![(vec![1], ()), (vec![2], ()), (vec![3], ())]);
+    let groups = group_records(Vec::<u8>::new(), |x| [*x], |_| ()).unwrap();
+    assert!(groups.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[quickcheck_macros::quickcheck]
+fn union_points_like_all_pairs(points: Vec<(i8, i8)>, r: u8) {
+    let points: Vec<(f64, f64)> = points
+        .into_iter()
+        .map(|(x, y)| (x as f64 / 4.0, y as f64 / 4.0))
+        .collect();
+    let r = (r as f64 + 1.0) / 16.0;
+    let mut oracle = crate::static_ufs::UnionFind::<256>::new();
+    for (i, (x, y)) in points.iter().enumerate().take(256) {
+        for (j, (u, v)) in points.iter().enumerate().take(256) {
+            if (x - u).hypot(y - v) <= r {
+                oracle.unite(i, j);
+            }
+        }
+    }
+    let points = &points[..points.len().min(256)];
+    let clusters = union_within_radius(points, r).unwrap();
+    assert_eq!(clusters.len(), oracle.len() - (256 - points.len()));
+    for xs in clusters.iter() {
+        assert!(xs.iter().all(|x| oracle.same_set(*x, xs[0])));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn centroids_of_clusters() {
+    let points = [
+        (0.0, 0.0),
+        (10.0, 10.0),
+        (1.0, 0.0),
+        (2.0, 0.0),
+        (10.5, 10.0),
+    ];
+    let clusters = cluster_within_radius(&points, 1.0, |_, (x, y)| Centroid::point(x, y)).unwrap();
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0].0, vec![0, 2, 3]);
+    assert_eq!(clusters[0].1.mean(), Some((1.0, 0.0)));
+    assert_eq!(clusters[1].0, vec![1, 4]);
+    assert_eq!(clusters[1].1.mean(), Some((10.25, 10.0)));
+    assert_eq!(union_within_radius(&points, 0.0).unwrap().len(), 5);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_mazes() {
+    use rand::SeedableRng;
+
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(7);
+    let cells = (0..8).flat_map(|x| (0..8).map(move |y| (x, y)));
+    let walls: Vec<_> = cells
+        .clone()
+        .flat_map(|(x, y)| [((x, y), (x + 1, y)), ((x, y), (x, y + 1))])
+        .filter(|(_, (x, y))| *x < 8 && *y < 8)
+        .collect();
+    let maze = random_spanning_tree(cells.clone(), walls.iter().copied(), &mut rng).unwrap();
+    assert_eq!(maze.len(), 63);
+    let mut sets = crate::static_ufs::UnionFind::<64>::new();
+    for ((x, y), (u, v)) in maze.iter() {
+        assert_eq!(sets.unite(x * 8 + y, u * 8 + v), Some(true));
+    }
+    assert_eq!(sets.len(), 1);
+    let another = random_spanning_tree(cells, walls, &mut rng).unwrap();
+    assert_ne!(maze, another);
+
+    let forest = random_spanning_tree([1, 2, 3], [(1, 2)], &mut rng).unwrap();
+    assert_eq!(forest, vec![(1, 2)]);
+    assert!(random_spanning_tree([1, 2], [(1, 3)], &mut rng).is_err());
+}
+
+#[quickcheck_macros::quickcheck]
+fn close_pairs(pairs: Vec<(u8, u8)>) {
+    let sets = equivalence_closure(pairs.iter().copied());
+    let mut oracle = crate::raw::test::Oracle::new();
+    for (x, y) in pairs.iter() {
+        let _ = oracle.make_set(*x);
+        let _ = oracle.make_set(*y);
+        oracle.unite(*x, *y).unwrap();
+    }
+    for (x, _) in pairs.iter() {
+        assert_eq!(sets.find(x).unwrap().len(), oracle.find(x).unwrap().len());
+    }
+    let keys: std::collections::HashSet<_> = pairs.iter().flat_map(|(x, y)| [x, y]).collect();
+    assert_eq!(sets.iter().map(|xs| xs.len()).sum::<usize>(), keys.len());
+    let stats = sets.union_stats();
+    assert_eq!(stats.missing, 0);
+    assert_eq!(stats.unions + stats.redundant, pairs.len() as u64);
+}
@@ -0,0 +1,32 @@
+//! Sets decoded from raw bytes, for fuzzers of downstream crates.
+
+use crate::{Mergable, UnionFindSets};
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+/// Decodes elements with their tags, and then unions between decoded elements.
+impl<'a, Key, Tag> Arbitrary<'a> for UnionFindSets<Key, Tag>
+where
+    Key: Arbitrary<'a> + Eq + Hash + Clone,
+    Tag: Arbitrary<'a> + Mergable,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut sets = Self::new();
+        let mut keys = Vec::new();
+        for element in u.arbitrary_iter::<(Key, Tag)>()? {
+            let (key, tag) = element?;
+            if sets.make_set(key.clone(), tag).is_ok() {
+                keys.push(key);
+            }
+        }
+        if !keys.is_empty() {
+            for _ in 0..u.arbitrary_len::<(u32, u32)>()? {
+                let x = u.choose_index(keys.len())?;
+                let y = u.choose_index(keys.len())?;
+                let _ = sets.unite(&keys[x], &keys[y]);
+            }
+        }
+        Ok(sets)
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{Mergable, UnionFindSets};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Int16Type, Int32Type, Int64Type, Int8Type};
+use arrow_array::types::{UInt16Type, UInt32Type, UInt64Type, UInt8Type};
+use arrow_array::{Array, ArrayRef, Int64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use core::hash::Hash;
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Keys which can be read from and written into Arrow arrays.
+pub trait ArrowKey: Eq + Hash + Clone + Ord + Sized {
+    /// Reads all values, with nulls as `None`.
+    ///
+    /// If the array is not of a supported type, [ArrowError::InvalidArgumentError] will be raised.
+    fn read(array: &dyn Array) -> Result<Vec<Option<Self>>, ArrowError>;
+
+    /// Writes values into an array.
+    fn write(keys: Vec<Self>) -> ArrayRef;
+}
+
+/// Reads `Utf8`, `LargeUtf8` and `Utf8View` arrays.
+impl ArrowKey for String {
+    fn read(array: &dyn Array) -> Result<Vec<Option<Self>>, ArrowError> {
+        let values = match array.data_type() {
+            DataType::Utf8 => array
+                .as_string::<i32>()
+                .iter()
+                .map(|x| x.map(String::from))
+                .collect(),
+            DataType::LargeUtf8 => array
+                .as_string::<i64>()
+                .iter()
+                .map(|x| x.map(String::from))
+                .collect(),
+            DataType::Utf8View => array
+                .as_string_view()
+                .iter()
+                .map(|x| x.map(String::from))
+                .collect(),
+            ty => return Err(unsupported(ty)),
+        };
+        Ok(values)
+    }
+
+    fn write(keys: Vec<Self>) -> ArrayRef {
+        Arc::new(StringArray::from(keys))
+    }
+}
+
+/// Reads arrays of signed integers and of unsigned integers up to 32 bits.
+impl ArrowKey for i64 {
+    fn read(array: &dyn Array) -> Result<Vec<Option<Self>>, ArrowError> {
+        let values = match array.data_type() {
+            DataType::Int8 => array
+                .as_primitive::<Int8Type>()
+                .iter()
+                .map(|x| x.map(i64::from))
+                .collect(),
+            DataType::Int16 => array
+                .as_primitive::<Int16Type>()
+                .iter()
+                .map(|x| x.map(i64::from))
+                .collect(),
+            DataType::Int32 => array
+                .as_primitive::<Int32Type>()
+                .iter()
+                .map(|x| x.map(i64::from))
+                .collect(),
+            DataType::Int64 => array.as_primitive::<Int64Type>().iter().collect(),
+            DataType::UInt8 => array
+                .as_primitive::<UInt8Type>()
+                .iter()
+                .map(|x| x.map(i64::from))
+                .collect(),
+            DataType::UInt16 => array
+                .as_primitive::<UInt16Type>()
+                .iter()
+                .map(|x| x.map(i64::from))
+                .collect(),
+            DataType::UInt32 => array
+                .as_primitive::<UInt32Type>()
+                .iter()
+                .map(|x| x.map(i64::from))
+                .collect(),
+            ty => return Err(unsupported(ty)),
+        };
+        Ok(values)
+    }
+
+    fn write(keys: Vec<Self>) -> ArrayRef {
+        Arc::new(Int64Array::from(keys))
+    }
+}
+
+/// Reads arrays of unsigned integers.
+impl ArrowKey for u64 {
+    fn read(array: &dyn Array) -> Result<Vec<Option<Self>>, ArrowError> {
+        let values = match array.data_type() {
+            DataType::UInt8 => array
+                .as_primitive::<UInt8Type>()
+                .iter()
+                .map(|x| x.map(u64::from))
+                .collect(),
+            DataType::UInt16 => array
+                .as_primitive::<UInt16Type>()
+                .iter()
+                .map(|x| x.map(u64::from))
+                .collect(),
+            DataType::UInt32 => array
+                .as_primitive::<UInt32Type>()
+                .iter()
+                .map(|x| x.map(u64::from))
+                .collect(),
+            DataType::UInt64 => array.as_primitive::<UInt64Type>().iter().collect(),
+            ty => return Err(unsupported(ty)),
+        };
+        Ok(values)
+    }
+
+    fn write(keys: Vec<Self>) -> ArrayRef {
+        Arc::new(UInt64Array::from(keys))
+    }
+}
+
+fn unsupported(ty: &DataType) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!("unsupported type of keys: {}", ty))
+}
+
+/// Builds sets from record batches, in which every row is an edge between two key columns.
+///
+/// If either key of a row is null, the other one is taken as an isolated element.
+pub struct EdgeBuilder<Key: ArrowKey> {
+    sets: UnionFindSets<Key, ()>,
+    src: String,
+    dst: String,
+}
+
+impl<Key: ArrowKey> EdgeBuilder<Key> {
+    /// Makes a builder reading edges from columns named `src` and `dst`.
+    pub fn new(src: &str, dst: &str) -> Self {
+        Self {
+            sets: UnionFindSets::new(),
+            src: src.to_string(),
+            dst: dst.to_string(),
+        }
+    }
+
+    /// Adds edges of a record batch.
+    ///
+    /// If either column is missing or not of a supported type,
+    /// [ArrowError::InvalidArgumentError] will be raised and nothing will be added.
+    pub fn push_batch(&mut self, batch: &RecordBatch) -> Result<(), ArrowError> {
+        let column = |name: &str| {
+            batch.column_by_name(name).ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!("no column named {}", name))
+            })
+        };
+        let xs = Key::read(column(&self.src)?)?;
+        let ys = Key::read(column(&self.dst)?)?;
+        for (x, y) in xs.into_iter().zip(ys) {
+            for key in [&x, &y].into_iter().flatten() {
+                let _ = self.sets.make_set(key.clone(), ());
+            }
+            if let (Some(x), Some(y)) = (x, y) {
+                self.sets
+                    .unite(&x, &y)
+                    .map_err(|err| ArrowError::ExternalError(Box::new(err)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Gives the built sets.
+    pub fn finish(self) -> UnionFindSets<Key, ()> {
+        self.sets
+    }
+}
+
+/// Writes elements into a record batch of two columns,
+/// `element` of keys and `component` of component ids as `UInt64`.
+///
+/// Components are numbered from 0 in order of their least elements,
+/// and rows are sorted by components and then by elements.
+pub fn to_record_batch<Key, Tag>(sets: &UnionFindSets<Key, Tag>) -> Result<RecordBatch, ArrowError>
+where
+    Key: ArrowKey,
+    Tag: Mergable,
+{
+    let mut components: Vec<Vec<&Key>> = sets.iter().map(|xs| xs.iter_sorted().collect()).collect();
+    components.sort_unstable_by(|xs, ys| xs.first().cmp(&ys.first()));
+    let mut elements = Vec::new();
+    let mut ids = Vec::new();
+    for (i, xs) in components.into_iter().enumerate() {
+        for x in xs {
+            elements.push(x.clone());
+            ids.push(i as u64);
+        }
+    }
+    let elements = Key::write(elements);
+    let schema = Schema::new(vec![
+        Field::new("element", elements.data_type().clone(), false),
+        Field::new("component", DataType::UInt64, false),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![elements, Arc::new(UInt64Array::from(ids))],
+    )
+}
+
+/// Writes the record batch by [to_record_batch] as a Parquet file.
+pub fn write_parquet<W, Key, Tag>(
+    writer: W,
+    sets: &UnionFindSets<Key, Tag>,
+) -> Result<(), ParquetError>
+where
+    W: Write + Send,
+    Key: ArrowKey,
+    Tag: Mergable,
+{
+    let batch = to_record_batch(sets)?;
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
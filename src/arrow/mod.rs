@@ -0,0 +1,8 @@
+//! Building sets from [Arrow](https://arrow.apache.org) record batches of edges,
+//! and writing components as Arrow record batches or Parquet files.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
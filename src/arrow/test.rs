@@ -0,0 +1,72 @@
+use super::*;
+use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray, UInt64Array};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use std::sync::Arc;
+
+fn batch(columns: Vec<(&str, ArrayRef)>) -> RecordBatch {
+    RecordBatch::try_from_iter(columns).unwrap()
+}
+
+#[test]
+fn build_from_batches() {
+    let mut builder = EdgeBuilder::<String>::new("src", "dst");
+    let src: ArrayRef = Arc::new(StringArray::from(vec![Some("a"), Some("b"), None]));
+    let dst: ArrayRef = Arc::new(StringArray::from(vec![Some("b"), None, Some("c")]));
+    builder
+        .push_batch(&batch(vec![("src", src), ("dst", dst)]))
+        .unwrap();
+    let src: ArrayRef = Arc::new(StringArray::from(vec!["c", "d"]));
+    let dst: ArrayRef = Arc::new(StringArray::from(vec!["e", "e"]));
+    builder
+        .push_batch(&batch(vec![("dst", dst), ("src", src)]))
+        .unwrap();
+    let ints: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+    assert!(builder
+        .push_batch(&batch(vec![("src", ints.clone()), ("dst", ints)]))
+        .is_err());
+    let sets = builder.finish();
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets.find("d").unwrap().len(), 3);
+
+    let mut builder = EdgeBuilder::<i64>::new("x", "y");
+    let xs: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 5]));
+    let ys: ArrayRef = Arc::new(Int32Array::from(vec![2, 3, 5]));
+    builder
+        .push_batch(&batch(vec![("x", xs.clone()), ("y", ys)]))
+        .unwrap();
+    assert!(builder.push_batch(&batch(vec![("x", xs)])).is_err());
+    let sets = builder.finish();
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets.find(&3).unwrap().len(), 3);
+}
+
+#[test]
+fn write_components() {
+    let mut sets = crate::UnionFindSets::new();
+    for x in [7u64, 3, 5, 1] {
+        sets.make_set(x, ()).unwrap();
+    }
+    sets.unite(&7, &1).unwrap();
+    let batch = to_record_batch(&sets).unwrap();
+    let elements = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap();
+    let components = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap();
+    assert_eq!(elements.values(), &[1, 7, 3, 5]);
+    assert_eq!(components.values(), &[0, 0, 1, 2]);
+
+    let mut file = tempfile::tempfile().unwrap();
+    write_parquet(&mut file, &sets).unwrap();
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches: Vec<_> = reader.map(|x| x.unwrap()).collect();
+    assert_eq!(batches, [batch]);
+}
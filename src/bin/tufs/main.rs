@@ -0,0 +1,69 @@
+//! Connected components of an edge list.
+//!
+//! The input is read by [read_edge_list].
+
+use clap::Parser;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use tagged_ufs::io::{read_edge_list, write_components, Format};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Connected components of an edge list")]
+struct Args {
+    /// The edge list; stdin if absent or `-`.
+    input: Option<PathBuf>,
+    /// Fields are separated by commas instead of tabs.
+    #[arg(long)]
+    csv: bool,
+    /// Writes components as a JSON array of arrays of elements,
+    /// instead of lines of `element<TAB>component`.
+    #[arg(long)]
+    json: bool,
+    /// Leaves out components of fewer elements.
+    #[arg(long, default_value_t = 1)]
+    min_size: usize,
+}
+
+fn main() -> io::Result<()> {
+    let args = Args::parse();
+    let reader: Box<dyn BufRead> = match &args.input {
+        Some(path) if path.as_os_str() != "-" => Box::new(BufReader::new(File::open(path)?)),
+        _ => Box::new(io::stdin().lock()),
+    };
+    let delimiter = if args.csv { ',' } else { '\t' };
+    let components = components(reader, delimiter, args.min_size)?;
+    let mut writer = BufWriter::new(io::stdout().lock());
+    if args.json {
+        write_json(&mut writer, &components)?;
+    } else {
+        write_components(&mut writer, &components, Format::Tsv)?;
+    }
+    writer.flush()
+}
+
+/// Reads edges, and groups elements into components of at least `min_size` elements.
+///
+/// Components are sorted by their least elements, and so are elements in them.
+fn components<R: BufRead>(
+    reader: R,
+    delimiter: char,
+    min_size: usize,
+) -> io::Result<Vec<Vec<String>>> {
+    let sets = read_edge_list(reader, delimiter)?;
+    let mut components: Vec<Vec<String>> = sets
+        .iter()
+        .filter(|xs| xs.len() >= min_size)
+        .map(|xs| xs.iter_sorted().cloned().collect())
+        .collect();
+    components.sort();
+    Ok(components)
+}
+
+fn write_json<W: Write>(writer: &mut W, components: &[Vec<String>]) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, components)?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,32 @@
+use super::*;
+
+const EDGES: &str = "\u{feff}# a comment\r\nb\ta\r\nc\tb\n\nd\te\nf\n";
+
+#[test]
+fn group_components() {
+    assert_eq!(
+        components(EDGES.as_bytes(), '\t', 1).unwrap(),
+        [vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]
+    );
+    assert_eq!(
+        components("x,y\ny, z\n".as_bytes(), ',', 2).unwrap(),
+        [vec!["x", "y", "z"]]
+    );
+}
+
+#[test]
+fn write_outputs() {
+    let components = components(EDGES.as_bytes(), '\t', 2).unwrap();
+    let mut tsv = Vec::new();
+    write_components(&mut tsv, &components, Format::Tsv).unwrap();
+    assert_eq!(
+        String::from_utf8(tsv).unwrap(),
+        "a\t0\nb\t0\nc\t0\nd\t1\ne\t1\n"
+    );
+    let mut json = Vec::new();
+    write_json(&mut json, &components).unwrap();
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        "[[\"a\",\"b\",\"c\"],[\"d\",\"e\"]]\n"
+    );
+}
@@ -0,0 +1,134 @@
+use crate::{Mergable, Result, Set, SetMut, SetsIter, UnionFindSets};
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+/// Sets whose representative is the least key in every set, maintained across unions.
+///
+/// Sets are no longer balanced by sizes, so long paths may be built,
+/// though they are still compressed by finding.
+///
+/// ```
+/// use tagged_ufs::canonical::CanonicalSets;
+///
+/// let mut sets = CanonicalSets::new();
+/// for x in [5, 3, 8, 1] {
+///     sets.make_set(x, ()).unwrap();
+/// }
+/// sets.unite(&5, &8).unwrap();
+/// sets.unite(&8, &3).unwrap();
+/// assert_eq!(sets.representative(&8), Some(&3));
+/// sets.unite(&1, &5).unwrap();
+/// assert_eq!(sets.representative(&8), Some(&1));
+/// ```
+#[derive(Clone)]
+pub struct CanonicalSets<Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    sets: UnionFindSets<Key, Tag>,
+}
+
+impl<Key, Tag> Default for CanonicalSets<Key, Tag>
+where
+    Key: Ord + Hash + Clone,
+    Tag: Mergable,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Key, Tag> CanonicalSets<Key, Tag>
+where
+    Key: Ord + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Makes a new, empty set of sets.
+    pub fn new() -> Self {
+        Self {
+            sets: UnionFindSets::new(),
+        }
+    }
+
+    /// Makes an individual set with a singleton element and its associated tag,
+    /// like [UnionFindSets::make_set].
+    pub fn make_set(&mut self, key: Key, tag: Tag) -> Result<()> {
+        self.sets.make_set(key, tag)
+    }
+
+    /// Unites two sets, like [UnionFindSets::unite],
+    /// where the lesser of the two representatives represents the united set.
+    pub fn unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.sets.unite_by_min_key(key1, key2)
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but simply returns `None` if either of them is not in the sets.
+    pub fn try_unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Option<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.unite(key1, key2).ok()
+    }
+
+    /// Finds an individual set, whose [key](Set::key) is its least key.
+    pub fn find<Q>(&self, key: &Q) -> Option<Set<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.sets.find(key)
+    }
+
+    /// Finds an individual set, like [find](Self::find),
+    /// but its tag can be updated through [SetMut::tag_mut].
+    pub fn find_mut<Q>(&mut self, key: &Q) -> Option<SetMut<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.sets.find_mut(key)
+    }
+
+    /// Finds the least key in the set containing `key`.
+    pub fn representative<Q>(&self, key: &Q) -> Option<&Key>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.sets.find(key).map(|xs| xs.key())
+    }
+
+    /// Iterates over all individual sets.
+    pub fn iter(&self) -> SetsIter<'_, Key, Tag> {
+        self.sets.iter()
+    }
+
+    /// Queries the number of individual sets in the set.
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Tests if this set (of sets) is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Gets the underlying sets, for reading only.
+    pub fn sets(&self) -> &UnionFindSets<Key, Tag> {
+        &self.sets
+    }
+
+    /// Gives the underlying sets back, whose representatives are no longer kept least.
+    pub fn into_sets(self) -> UnionFindSets<Key, Tag> {
+        self.sets
+    }
+}
@@ -0,0 +1,11 @@
+//! Sets whose representatives are always their least keys.
+//!
+//! Representatives then depend only on the partition,
+//! neither on the order of unions nor on sizes of sets,
+//! so they can serve as canonical ids across runs.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
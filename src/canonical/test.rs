@@ -0,0 +1,21 @@
+use super::*;
+use crate::raw::test::Oracle;
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn least_keys_represent(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let mut trial = CanonicalSets::new();
+    let mut oracle = Oracle::new();
+    for x in adds.iter() {
+        assert_eq!(trial.make_set(*x, ()).is_ok(), oracle.make_set(*x).is_ok());
+    }
+    for (x, y) in connects {
+        assert_eq!(trial.unite(&x, &y).ok(), oracle.unite(x, y).ok());
+    }
+    for x in adds.iter() {
+        let oracle_set = oracle.find(x).unwrap();
+        assert_eq!(trial.representative(x), oracle_set.iter().min());
+        assert_eq!(trial.find(x).unwrap().len(), oracle_set.len());
+    }
+    assert_eq!(trial.len(), trial.iter().count());
+}
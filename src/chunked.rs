@@ -0,0 +1,139 @@
+use crate::HeapSize;
+use alloc::collections::{linked_list, LinkedList};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::Flatten;
+use core::mem::size_of;
+
+/// Number of elements a chunk is filled up to before a new chunk is linked in.
+const CHUNK_LEN: usize = 64;
+
+/// An unordered list of elements, stored in linked chunks.
+///
+/// Appending one list to another is O(1):
+/// a small list is copied into the other's last chunk,
+/// otherwise the two chains of chunks are linked.
+/// Iteration only chases a pointer once per chunk.
+#[derive(Debug, Clone)]
+pub(crate) struct ChunkedList<T> {
+    chunks: LinkedList<Vec<T>>,
+    len: usize,
+}
+
+impl<T> ChunkedList<T> {
+    pub(crate) fn new(x: T) -> Self {
+        Self {
+            chunks: LinkedList::from_iter([vec![x]]),
+            len: 1,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn append(&mut self, mut other: Self) {
+        if !fits_in_tail(self, &other) {
+            if !fits_in_tail(&other, self) {
+                self.len += other.len;
+                self.chunks.append(&mut other.chunks);
+                return;
+            }
+            core::mem::swap(self, &mut other);
+        }
+        self.len += other.len;
+        if let Some(tail) = self.chunks.back_mut() {
+            for mut chunk in other.chunks.into_iter() {
+                tail.append(&mut chunk);
+            }
+        }
+    }
+
+    /// Gets the `i`-th element, skipping whole chunks before it.
+    #[cfg(feature = "rand")]
+    pub(crate) fn get(&self, mut i: usize) -> Option<&T> {
+        for chunk in self.chunks.iter() {
+            match chunk.get(i) {
+                Some(x) => return Some(x),
+                None => i -= chunk.len(),
+            }
+        }
+        None
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        self.chunks.iter().flatten()
+    }
+}
+
+pub(crate) type Iter<'a, T> = Flatten<linked_list::Iter<'a, Vec<T>>>;
+
+impl<T> IntoIterator for ChunkedList<T> {
+    type Item = T;
+    type IntoIter = Flatten<linked_list::IntoIter<Vec<T>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chunks.into_iter().flatten()
+    }
+}
+
+/// Tests if all elements of `ys` can be copied into the last chunk of `xs`.
+fn fits_in_tail<T>(xs: &ChunkedList<T>, ys: &ChunkedList<T>) -> bool {
+    match xs.chunks.back() {
+        Some(tail) => tail.len() + ys.len() <= CHUNK_LEN,
+        None => false,
+    }
+}
+
+/// Besides the chunks themselves, every chunk is boxed into a node with two links.
+impl<T: HeapSize> HeapSize for ChunkedList<T> {
+    fn heap_size(&self) -> usize {
+        self.chunks
+            .iter()
+            .map(|chunk| size_of::<Vec<T>>() + 2 * size_of::<usize>() + chunk.heap_size())
+            .sum()
+    }
+}
+
+impl<T> FromIterator<T> for ChunkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut chunks = LinkedList::new();
+        let mut len = 0;
+        let mut chunk = Vec::with_capacity(CHUNK_LEN);
+        for x in iter {
+            chunk.push(x);
+            len += 1;
+            if chunk.len() == CHUNK_LEN {
+                chunks.push_back(core::mem::replace(
+                    &mut chunk,
+                    Vec::with_capacity(CHUNK_LEN),
+                ));
+            }
+        }
+        if !chunk.is_empty() {
+            chunks.push_back(chunk);
+        }
+        Self { chunks, len }
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<T: borsh::BorshSerialize> borsh::BorshSerialize for ChunkedList<T> {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        let len = u32::try_from(self.len)
+            .map_err(|_| borsh::io::Error::from(borsh::io::ErrorKind::InvalidData))?;
+        len.serialize(writer)?;
+        for x in self.iter() {
+            x.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<T: borsh::BorshDeserialize> borsh::BorshDeserialize for ChunkedList<T> {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let xs: Vec<T> = borsh::BorshDeserialize::deserialize_reader(reader)?;
+        Ok(xs.into_iter().collect())
+    }
+}
@@ -0,0 +1,102 @@
+use super::UnionFindSets;
+use crate::{Error, Mergable, Result};
+use core::hash::{BuildHasher, Hash};
+
+/// Builds sets of huge numbers of keys in two passes,
+/// keeping the peak memory near the size of the built sets.
+///
+/// Keys are collected in the first pass into an id table made at once in its expected size,
+/// instead of being rehashed again and again as it grows.
+/// Duplicates are detected as they arrive, where their tags are merged, so they take no room.
+/// Edges are then fed to [UnionFindSets::unite] in the second pass, which allocates nothing.
+///
+/// ```
+/// use tagged_ufs::compact::StreamingBuilder;
+///
+/// let mut builder = StreamingBuilder::with_capacity(3);
+/// builder.extend((0..3).map(|x| (x, ())));
+/// let mut sets = builder.build().unwrap();
+/// sets.unite(&0, &2).unwrap();
+/// assert_eq!(sets.len(), 2);
+/// ```
+pub struct StreamingBuilder<Key, Tag, S = ahash::RandomState>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    sets: UnionFindSets<Key, Tag, S>,
+    too_many: bool,
+}
+
+impl<Key, Tag> StreamingBuilder<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Makes a new builder, expecting `keys` distinct keys, say, counted by a cheap scan beforehand.
+    pub fn with_capacity(keys: usize) -> Self {
+        Self::with_capacity_and_hasher(keys, ahash::RandomState::new())
+    }
+}
+
+impl<Key, Tag, S> StreamingBuilder<Key, Tag, S>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+    S: BuildHasher,
+{
+    /// Makes a new builder like [with_capacity](Self::with_capacity),
+    /// whose sets will hash keys by `hasher`.
+    pub fn with_capacity_and_hasher(keys: usize, hasher: S) -> Self {
+        Self {
+            sets: UnionFindSets::with_capacity_and_hasher(keys, hasher),
+            too_many: false,
+        }
+    }
+
+    /// Adds a key with its tag.
+    ///
+    /// If the key is already added, the tag is merged into its tag.
+    pub fn push(&mut self, key: Key, tag: Tag) {
+        if self.too_many {
+            return;
+        }
+        if let Err(Error::TooManyKeys) = self.sets.make_set_or_merge(key, tag) {
+            self.too_many = true;
+        }
+    }
+
+    /// Queries the number of distinct keys added.
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Tests if no key is added.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Makes a singleton set of every distinct key.
+    ///
+    /// If there are too many distinct keys,
+    /// [Error::TooManyKeys](crate::Error::TooManyKeys) will be raised.
+    pub fn build(self) -> Result<UnionFindSets<Key, Tag, S>> {
+        if self.too_many {
+            return Err(Error::TooManyKeys);
+        }
+        Ok(self.sets)
+    }
+}
+
+impl<Key, Tag, S> Extend<(Key, Tag)> for StreamingBuilder<Key, Tag, S>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (Key, Tag)>>(&mut self, iter: I) {
+        for (key, tag) in iter {
+            self.push(key, tag);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{Error, HeapSize, Mergable, Result};
+use alloc::vec::Vec;
+use core::mem::{size_of, MaybeUninit};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Marks a link as a root, whose remaining bits are the size of its set.
+/// Any other link is the id of the parent.
+pub(super) const ROOT_BIT: u32 = 1 << 31;
+
+/// A link of the forest.
+///
+/// It is atomic only so that finds, which compress paths through `&self`,
+/// may run in parallel while the sets are shared among threads, e.g., behind a read lock,
+/// where links are only ever redirected to ancestors.
+/// Relaxed loads and stores compile to plain ones.
+#[derive(Debug)]
+struct Link(AtomicU32);
+
+impl Link {
+    fn new(link: u32) -> Self {
+        Self(AtomicU32::new(link))
+    }
+
+    fn get(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, link: u32) {
+        self.0.store(link, Ordering::Relaxed);
+    }
+}
+
+impl Clone for Link {
+    fn clone(&self) -> Self {
+        Self::new(self.get())
+    }
+}
+
+/// Links and tags of keys, as two columns indexed by ids.
+///
+/// A tag is there exactly for a root, which is already told by its link,
+/// so tags are kept without any flag of their own,
+/// and tags of no size, e.g., `()`, take no room at all.
+/// Roots are only made or unmade through `&mut self`,
+/// while `&self` may merely redirect non-roots, so tags always match links.
+pub(super) struct Forest<Tag> {
+    links: Vec<Link>,
+    /// Initialized exactly where links are roots.
+    tags: Vec<MaybeUninit<Tag>>,
+}
+
+impl<Tag> Default for Forest<Tag> {
+    fn default() -> Self {
+        Self {
+            links: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl<Tag> Forest<Tag> {
+    /// Makes an empty forest with room for `len` ids.
+    pub(super) fn with_capacity(len: usize) -> Self {
+        Self {
+            links: Vec::with_capacity(len),
+            tags: Vec::with_capacity(len),
+        }
+    }
+
+    /// Queries the number of ids.
+    pub(super) fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Adds a singleton set.
+    pub(super) fn push(&mut self, tag: Tag) {
+        self.links.push(Link::new(ROOT_BIT | 1));
+        self.tags.push(MaybeUninit::new(tag));
+    }
+
+    /// Tells whether `id` is a root.
+    ///
+    /// `id` must be in the forest.
+    pub(super) fn is_root(&self, id: u32) -> bool {
+        self.links[id as usize].get() & ROOT_BIT != 0
+    }
+
+    /// Gets the parent of `id`, or `None` if it is a root or not in the forest.
+    pub(super) fn parent(&self, id: u32) -> Option<u32> {
+        let link = self.links.get(id as usize)?.get();
+        (link & ROOT_BIT == 0).then_some(link)
+    }
+
+    /// Gets the size of the set rooted at `top`.
+    ///
+    /// `top` must be a root in the forest.
+    pub(super) fn size(&self, top: u32) -> u32 {
+        self.links[top as usize].get() & !ROOT_BIT
+    }
+
+    /// Redirects a non-root `id` to `top`, an ancestor of it, returning its former parent.
+    ///
+    /// Roots are never redirected, and their links are returned as they are.
+    pub(super) fn redirect(&self, id: u32, top: u32) -> u32 {
+        let link = &self.links[id as usize];
+        let old = link.get();
+        // only `&mut self` makes or unmakes roots, so no root shows up meanwhile
+        if old & ROOT_BIT == 0 && top & ROOT_BIT == 0 {
+            link.set(top);
+        }
+        old
+    }
+
+    /// Gets the tag of `id`, or `None` if it is not a root.
+    pub(super) fn tag(&self, id: u32) -> Option<&Tag> {
+        let tag = self.tags.get(id as usize)?;
+        // SAFETY: tags of roots are initialized
+        self.is_root(id).then(|| unsafe { tag.assume_init_ref() })
+    }
+
+    /// Gets the tag of `id` mutably, or `None` if it is not a root.
+    pub(super) fn tag_mut(&mut self, id: u32) -> Option<&mut Tag> {
+        if !self.is_root(id) {
+            return None;
+        }
+        // SAFETY: tags of roots are initialized
+        Some(unsafe { self.tags.get_mut(id as usize)?.assume_init_mut() })
+    }
+
+    /// Calls `f` on the tag of every root.
+    pub(super) fn for_each_tag_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(u32, &mut Tag),
+    {
+        for (id, (link, tag)) in self.links.iter().zip(self.tags.iter_mut()).enumerate() {
+            if link.get() & ROOT_BIT != 0 {
+                // SAFETY: tags of roots are initialized
+                f(id as u32, unsafe { tag.assume_init_mut() });
+            }
+        }
+    }
+
+    /// Converts tags of all roots, keeping links as they are.
+    pub(super) fn map_tags<U, F>(mut self, mut f: F) -> Forest<U>
+    where
+        F: FnMut(u32, Tag) -> U,
+    {
+        // taken out, so that nothing is left for `drop` of `self`
+        let links = core::mem::take(&mut self.links);
+        let tags = core::mem::take(&mut self.tags);
+        let tags = links
+            .iter()
+            .zip(tags)
+            .enumerate()
+            .map(|(id, (link, tag))| {
+                if link.get() & ROOT_BIT == 0 {
+                    return MaybeUninit::uninit();
+                }
+                // SAFETY: tags of roots are initialized, and each is read once
+                MaybeUninit::new(f(id as u32, unsafe { tag.assume_init() }))
+            })
+            .collect();
+        Forest { links, tags }
+    }
+}
+
+impl<Tag: Mergable> Forest<Tag> {
+    /// Links the root `child` to the root `parent`, merging their sizes and tags.
+    ///
+    /// If either is not a root, or they are the same,
+    /// [Error::Inconsistent](crate::Error::Inconsistent) will be raised and nothing will change.
+    pub(super) fn link(&mut self, parent: u32, child: u32) -> Result<()> {
+        let roots = parent != child
+            && (parent as usize) < self.len()
+            && (child as usize) < self.len()
+            && self.is_root(parent)
+            && self.is_root(child);
+        if !roots {
+            return Err(Error::Inconsistent);
+        }
+        let size = self.size(parent) + self.size(child);
+        self.links[child as usize].set(parent);
+        // SAFETY: `child` was a root, and now it is not, so its tag is read exactly once
+        let child_tag = unsafe { self.tags[child as usize].assume_init_read() };
+        self.links[parent as usize].set(ROOT_BIT | size);
+        // SAFETY: `parent` is a root
+        unsafe { self.tags[parent as usize].assume_init_mut() }.merge(child_tag);
+        Ok(())
+    }
+}
+
+impl<Tag: Clone> Clone for Forest<Tag> {
+    fn clone(&self) -> Self {
+        let tags = self
+            .links
+            .iter()
+            .zip(self.tags.iter())
+            .map(|(link, tag)| {
+                if link.get() & ROOT_BIT == 0 {
+                    return MaybeUninit::uninit();
+                }
+                // SAFETY: tags of roots are initialized
+                MaybeUninit::new(unsafe { tag.assume_init_ref() }.clone())
+            })
+            .collect();
+        Self {
+            links: self.links.clone(),
+            tags,
+        }
+    }
+}
+
+impl<Tag> Drop for Forest<Tag> {
+    fn drop(&mut self) {
+        if !core::mem::needs_drop::<Tag>() {
+            return;
+        }
+        for (link, tag) in self.links.iter().zip(self.tags.iter_mut()) {
+            if link.get() & ROOT_BIT != 0 {
+                // SAFETY: tags of roots are initialized, and dropped only here
+                unsafe { tag.assume_init_drop() };
+            }
+        }
+    }
+}
+
+impl<Tag: HeapSize> HeapSize for Forest<Tag> {
+    fn heap_size(&self) -> usize {
+        let tags: usize = (0..self.len() as u32)
+            .filter_map(|id| self.tag(id))
+            .map(HeapSize::heap_size)
+            .sum();
+        self.links.capacity() * size_of::<Link>() + self.tags.capacity() * size_of::<Tag>() + tags
+    }
+}
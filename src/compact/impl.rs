@@ -0,0 +1,523 @@
+use super::forest::{Forest, ROOT_BIT};
+use crate::error::found_both;
+use crate::heap_size::hash_table_size;
+use crate::instrumentation::{count_compressed_pointer, count_find, count_union};
+use crate::raw::{Set, SetId, SetMut, UnionStats};
+use crate::Mergable;
+use crate::{Error, HeapSize, Result};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use hashbrown::HashTable;
+
+/// Compact implementation of raw union-find sets, with built-in balanced union and path compression.
+///
+/// The forest is laid out as a structure of arrays:
+/// links (parents, or sizes of roots) and tags are separate contiguous vectors indexed by ids,
+/// so walking up the forest only touches the links.
+/// Only roots have tags, as told by their links, so tags of no size, e.g., `()`, take no room at all.
+///
+/// Every key is held once, by the vector of keys, while the id table holds nothing but ids,
+/// comparing a key looked up against the key of each candidate id.
+/// Keys are hashed by `S` only when they are interned or looked up,
+/// e.g., by [BuildIntHasher](crate::int_hash::BuildIntHasher) for integer keys, see [with_hasher](Self::with_hasher).
+#[derive(Clone)]
+pub struct UnionFindSets<Key, Tag, S = ahash::RandomState>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    ids: HashTable<u32>,
+    hasher: S,
+    keys: Vec<Key>,
+    forest: Forest<Tag>,
+    sets: usize,
+    stats: UnionStats,
+}
+
+/// An iterator over all individual sets, see [UnionFindSets::iter].
+pub struct SetsIter<'a, Key, Tag> {
+    keys: core::slice::Iter<'a, Key>,
+    forest: &'a Forest<Tag>,
+    /// Id of the next set from the front.
+    front: u32,
+    remaining: usize,
+}
+
+impl<'a, Key, Tag> Clone for SetsIter<'a, Key, Tag> {
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            forest: self.forest,
+            front: self.front,
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, Key: Eq, Tag> SetsIter<'a, Key, Tag> {
+    fn root(&mut self, id: u32, key: &'a Key) -> Option<Set<'a, Key, Tag>> {
+        let tag = self.forest.tag(id)?;
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(Set {
+            key,
+            id: SetId(id as u64),
+            size: self.forest.size(id) as usize,
+            tag,
+        })
+    }
+}
+
+impl<'a, Key: Eq, Tag> Iterator for SetsIter<'a, Key, Tag> {
+    type Item = Set<'a, Key, Tag>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let key = self.keys.next()?;
+            let id = self.front;
+            self.front += 1;
+            if let Some(set) = self.root(id, key) {
+                return Some(set);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, Key: Eq, Tag> DoubleEndedIterator for SetsIter<'a, Key, Tag> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let key = self.keys.next_back()?;
+            let id = self.front + self.keys.len() as u32;
+            if let Some(set) = self.root(id, key) {
+                return Some(set);
+            }
+        }
+    }
+}
+
+impl<'a, Key: Eq, Tag> ExactSizeIterator for SetsIter<'a, Key, Tag> {}
+
+impl<'a, Key: Eq, Tag> FusedIterator for SetsIter<'a, Key, Tag> {}
+
+impl<Key, Tag, S> Default for UnionFindSets<Key, Tag, S>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<Key, Tag> UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Makes a new, empty set of sets.
+    pub fn new() -> Self {
+        Self::with_hasher(ahash::RandomState::new())
+    }
+}
+
+impl<Key, Tag, S> UnionFindSets<Key, Tag, S>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+    S: BuildHasher,
+{
+    /// Makes a new, empty set of sets, whose keys are hashed by `hasher`.
+    ///
+    /// ```
+    /// use tagged_ufs::compact::UnionFindSets;
+    /// use tagged_ufs::int_hash::BuildIntHasher;
+    ///
+    /// let mut sets = UnionFindSets::with_hasher(BuildIntHasher::default());
+    /// sets.make_set(1_000_000u64, ()).unwrap();
+    /// sets.make_set(7, ()).unwrap();
+    /// assert!(sets.unite(&7, &1_000_000).unwrap());
+    /// ```
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            ids: HashTable::new(),
+            hasher,
+            keys: vec![],
+            forest: Forest::default(),
+            sets: 0,
+            stats: UnionStats::default(),
+        }
+    }
+
+    /// Makes a new, empty set of sets, like [with_hasher](Self::with_hasher),
+    /// with room for `keys` keys.
+    pub(super) fn with_capacity_and_hasher(keys: usize, hasher: S) -> Self {
+        Self {
+            ids: HashTable::with_capacity(keys),
+            hasher,
+            keys: Vec::with_capacity(keys),
+            forest: Forest::with_capacity(keys),
+            sets: 0,
+            stats: UnionStats::default(),
+        }
+    }
+
+    /// Makes an individual set with a singleton element and its associated tag.
+    ///
+    /// If the set to make is already there, or there are too many keys,
+    /// [Error::DuplicateKey](crate::Error::DuplicateKey) or [Error::TooManyKeys](crate::Error::TooManyKeys)
+    /// will be raised respectively, and nothing will happen to the sets.
+    pub fn make_set(&mut self, key: Key, tag: Tag) -> Result<()> {
+        self.make_set_id(key, tag).map(|_| ())
+    }
+
+    /// Makes an individual set, like [make_set](Self::make_set), returning the id of its key.
+    pub(crate) fn make_set_id(&mut self, key: Key, tag: Tag) -> Result<u32> {
+        // hashes the key once for both the check and the insertion
+        let hash = self.hasher.hash_one(&key);
+        self.make_set_hashed(hash, key, tag)
+    }
+
+    /// Makes a singleton set of `key`, or merges `tag` into the set of `key` if it is already there.
+    pub(super) fn make_set_or_merge(&mut self, key: Key, tag: Tag) -> Result<()> {
+        let hash = self.hasher.hash_one(&key);
+        match self.id_of_hashed(hash, &key) {
+            Some(id) => {
+                if let Some(mut set) = self.find_mut_by_id(id) {
+                    set.tag_mut().merge(tag);
+                }
+                Ok(())
+            }
+            None => self.make_set_hashed(hash, key, tag).map(|_| ()),
+        }
+    }
+
+    fn make_set_hashed(&mut self, hash: u64, key: Key, tag: Tag) -> Result<u32> {
+        if self.id_of_hashed(hash, &key).is_some() {
+            return Err(Error::DuplicateKey);
+        }
+        let Some(id) = u32::try_from(self.keys.len())
+            .ok()
+            .filter(|x| *x < ROOT_BIT - 1)
+        else {
+            return Err(Error::TooManyKeys);
+        };
+        let (keys, hasher) = (&self.keys, &self.hasher);
+        self.ids
+            .insert_unique(hash, id, |x| hasher.hash_one(&keys[*x as usize]));
+        self.keys.push(key);
+        self.forest.push(tag);
+        self.sets += 1;
+        Ok(id)
+    }
+
+    /// Unites two sets, given by any borrowed forms of their keys.
+    ///
+    /// If either of them is not in the sets,
+    /// [Error::KeyNotFound](crate::Error::KeyNotFound) will be raised, telling which are missing;
+    /// if they are of a same set, `Ok(false)` will be returns;
+    /// otherwise, which means these two sets are really united into one in this case,
+    /// `Ok(true)` will be returned.
+    pub fn unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        let tops = found_both(self.find_top_id(key1), self.find_top_id(key2));
+        self.unite_found(tops)
+    }
+
+    /// Unites two sets, like [unite](Self::unite), given by ids of their keys.
+    pub(crate) fn unite_ids(&mut self, id1: u32, id2: u32) -> Result<bool> {
+        let tops = found_both(self.top_of(id1), self.top_of(id2));
+        self.unite_found(tops)
+    }
+
+    fn unite_found(&mut self, tops: Result<(u32, u32)>) -> Result<bool> {
+        let (key1_top, key2_top) = match tops {
+            Ok(tops) => tops,
+            Err(err) => {
+                self.stats.missing += 1;
+                return Err(err);
+            }
+        };
+        if key1_top == key2_top {
+            self.stats.redundant += 1;
+            count_union(false);
+            return Ok(false);
+        }
+        let key1_size = self.size_of(key1_top);
+        let key2_size = self.size_of(key2_top);
+        let (parent, child) = if key1_size > key2_size {
+            (key1_top, key2_top)
+        } else {
+            (key2_top, key1_top)
+        };
+        self.forest.link(parent, child)?;
+        self.sets -= 1;
+        self.stats.unions += 1;
+        count_union(true);
+        Ok(true)
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but simply returns `None` if either of them is not in the sets.
+    pub fn try_unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Option<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.unite(key1, key2).ok()
+    }
+
+    /// Finds an individual set.
+    ///
+    /// As with `HashMap::get`, `key` may be any borrowed form of `Key`, e.g., `&str` for `String`.
+    /// If the set is not inside, `None` will be returned.
+    pub fn find<Q>(&self, key: &Q) -> Option<Set<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let top = self.find_top_id(key)?;
+        self.set_of(top)
+    }
+
+    /// Tests whether `key` has been made a set of, i.e., is an element of any set.
+    ///
+    /// Unlike [find](Self::find), it neither walks nor compresses paths.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.id_of(key).is_some()
+    }
+
+    /// Tells whether `key` is the root of its tree, i.e., the representative of its set,
+    /// or `None` if it is not in the sets.
+    pub fn is_root<Q>(&self, key: &Q) -> Option<bool>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Some(self.is_root_id(self.id_of(key)?))
+    }
+
+    /// Counts links on the path from `key` up to its root, without compressing the path,
+    /// or returns `None` if it is not in the sets.
+    ///
+    /// It probes how deep trees grow, e.g., under different orders of unions.
+    pub fn depth<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut id = self.id_of(key)?;
+        let mut depth = 0;
+        while let Some(parent) = self.forest.parent(id) {
+            id = parent;
+            depth += 1;
+        }
+        Some(depth)
+    }
+
+    /// Finds an individual set, like [find](Self::find),
+    /// but its tag can be updated through [SetMut::tag_mut].
+    pub fn find_mut<Q>(&mut self, key: &Q) -> Option<SetMut<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let top = self.find_top_id(key)?;
+        self.set_mut_of(top)
+    }
+
+    /// Finds an individual set, like [find](Self::find), given the id of a key.
+    pub(crate) fn find_by_id(&self, id: u32) -> Option<Set<'_, Key, Tag>> {
+        let top = self.top_of(id)?;
+        self.set_of(top)
+    }
+
+    /// Finds an individual set, like [find_mut](Self::find_mut), given the id of a key.
+    pub(crate) fn find_mut_by_id(&mut self, id: u32) -> Option<SetMut<'_, Key, Tag>> {
+        let top = self.top_of(id)?;
+        self.set_mut_of(top)
+    }
+
+    /// Looks up the id of a key.
+    pub(crate) fn id_of<Q>(&self, key: &Q) -> Option<u32>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.id_of_hashed(self.hasher.hash_one(key), key)
+    }
+
+    fn id_of_hashed<Q>(&self, hash: u64, key: &Q) -> Option<u32>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids
+            .find(hash, |x| self.keys[*x as usize].borrow() == key)
+            .copied()
+    }
+
+    /// Queries the number of keys, i.e., the number of ids given out.
+    pub(crate) fn keys_len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Looks up the key of an id.
+    pub(crate) fn key_of(&self, id: u32) -> Option<&Key> {
+        self.keys.get(id as usize)
+    }
+
+    fn set_mut_of(&mut self, top: u32) -> Option<SetMut<'_, Key, Tag>> {
+        let size = self.size_of(top) as usize;
+        Some(SetMut {
+            key: &self.keys[top as usize],
+            id: SetId(top as u64),
+            size,
+            tag: self.forest.tag_mut(top)?,
+        })
+    }
+
+    /// Iterates over all individual sets.
+    pub fn iter(&self) -> SetsIter<'_, Key, Tag> {
+        SetsIter {
+            keys: self.keys.iter(),
+            forest: &self.forest,
+            front: 0,
+            remaining: self.sets,
+        }
+    }
+
+    /// Calls `f` on the tag of every set, along with its representative,
+    /// so that tags can be updated in place.
+    pub fn for_each_tag_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Key, &mut Tag),
+    {
+        let keys = &self.keys;
+        self.forest
+            .for_each_tag_mut(|id, tag| f(&keys[id as usize], tag));
+    }
+
+    /// Counts outcomes of all unions tried since the sets were made
+    /// or [reset_union_stats](Self::reset_union_stats) was called.
+    pub fn union_stats(&self) -> UnionStats {
+        self.stats
+    }
+
+    /// Resets counts of outcomes of unions to zero.
+    pub fn reset_union_stats(&mut self) {
+        self.stats = UnionStats::default();
+    }
+
+    /// Queries the number of individual sets in the set.
+    pub fn len(&self) -> usize {
+        self.sets
+    }
+
+    /// Tests if this set (of sets) is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sets == 0
+    }
+
+    /// Converts tags of all sets, given their representatives, keeping the sets as they are.
+    pub fn map_tags<U, F>(self, mut f: F) -> UnionFindSets<Key, U, S>
+    where
+        U: Mergable,
+        F: FnMut(&Key, Tag) -> U,
+    {
+        let keys = &self.keys;
+        let forest = self.forest.map_tags(|id, tag| f(&keys[id as usize], tag));
+        UnionFindSets {
+            ids: self.ids,
+            hasher: self.hasher,
+            keys: self.keys,
+            forest,
+            sets: self.sets,
+            stats: self.stats,
+        }
+    }
+
+    /// Tags are present exactly for roots, so it is `Some` for every root.
+    fn set_of(&self, top: u32) -> Option<Set<'_, Key, Tag>> {
+        Some(Set {
+            key: &self.keys[top as usize],
+            id: SetId(top as u64),
+            size: self.size_of(top) as usize,
+            tag: self.forest.tag(top)?,
+        })
+    }
+
+    fn is_root_id(&self, id: u32) -> bool {
+        self.forest.is_root(id)
+    }
+
+    fn size_of(&self, top: u32) -> u32 {
+        self.forest.size(top)
+    }
+
+    fn find_top_id<Q>(&self, key: &Q) -> Option<u32>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let id = self.id_of(key)?;
+        self.top_of(id)
+    }
+
+    /// Compresses the path from `id` in a second walk instead of collecting it into a buffer,
+    /// so that finding never allocates.
+    fn top_of(&self, id: u32) -> Option<u32> {
+        if id as usize >= self.forest.len() {
+            return None;
+        }
+        let mut top = id;
+        let mut path_len = 0;
+        while let Some(parent) = self.forest.parent(top) {
+            top = parent;
+            path_len += 1;
+        }
+        count_find(path_len);
+        let mut cur = id;
+        while cur != top {
+            cur = self.forest.redirect(cur, top);
+            if cur != top {
+                count_compressed_pointer();
+            }
+        }
+        Some(top)
+    }
+}
+
+impl<Key, Tag, S> UnionFindSets<Key, Tag, S>
+where
+    Key: Eq + Hash + HeapSize,
+    Tag: Mergable + HeapSize,
+    S: BuildHasher,
+{
+    /// Reports the number of bytes on heap owned by these sets,
+    /// including the id table, the flat vectors and whatever keys and tags own.
+    ///
+    /// The id table is estimated from its capacity.
+    pub fn heap_size(&self) -> usize {
+        let keys = self.keys.iter().map(HeapSize::heap_size).sum::<usize>();
+        hash_table_size::<u32>(self.ids.capacity())
+            + self.keys.capacity() * size_of::<Key>()
+            + keys
+            + self.forest.heap_size()
+    }
+}
@@ -0,0 +1,16 @@
+//! Compact implementation of raw union-find sets.
+//!
+//! Keys are interned to `u32` ids and the forest is kept in flat vectors indexed by ids,
+//! where the size of a set is packed into the parent word of its root.
+//! It is a drop-in replacement of [raw::UnionFindSets](crate::raw::UnionFindSets),
+//! which saves memory and never clones keys while finding.
+//! It holds at most `2^31 - 1` keys.
+
+mod r#impl;
+pub use self::r#impl::*;
+mod builder;
+pub use self::builder::*;
+mod forest;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,271 @@
+use super::*;
+use crate::raw::test::Oracle;
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn add_connect_query(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<(u8, u8)>) {
+    let mut trial = UnionFindSets::new();
+    let mut oracle = Oracle::new();
+
+    for x in adds.into_iter() {
+        let trial_res = trial.make_set(x, ());
+        let oracle_res = oracle.make_set(x);
+        assert_eq!(trial_res.is_ok(), oracle_res.is_ok());
+    }
+
+    for (x, y) in connects.into_iter() {
+        match (trial.unite(&x, &y), oracle.unite(x, y)) {
+            (Err(_), Err(_)) | (Ok(true), Ok(true)) | (Ok(false), Ok(false)) => (),
+            (trial_res, oracle_res) => {
+                panic!(
+                    "differences:\
+                    \n  oracle result: {:?}\
+                    \n  trial result: {:?}",
+                    trial_res, oracle_res,
+                );
+            }
+        }
+    }
+
+    for (x, y) in queries.into_iter() {
+        let trial_set_x = trial.find(&x);
+        let trial_set_y = trial.find(&y);
+        let oracle_set_x = oracle.find(&x);
+        let oracle_set_y = oracle.find(&y);
+
+        assert_eq!(trial_set_x.is_none(), oracle_set_x.is_none());
+        assert_eq!(trial_set_y.is_none(), oracle_set_y.is_none());
+        if let (Some(trial_set_x), Some(trial_set_y)) = (trial_set_x, trial_set_y) {
+            let oracle_set_x = oracle_set_x.unwrap();
+            let oracle_set_y = oracle_set_y.unwrap();
+            assert_eq!(trial_set_x.len(), oracle_set_x.len());
+            assert_eq!(trial_set_x == trial_set_y, oracle_set_x == oracle_set_y);
+        }
+    }
+    assert_eq!(trial.len(), trial.iter().count());
+}
+
+#[test]
+fn update_tags() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, crate::Count(0)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    let mut set = sets.find_mut(&1).unwrap();
+    assert_eq!(set.len(), 2);
+    set.tag_mut().0 += 5;
+    sets.find_mut(&2).unwrap().tag_mut().0 += 1;
+    assert!(sets.find_mut(&4).is_none());
+    sets.unite(&2, &0).unwrap();
+    assert_eq!(sets.find(&0).unwrap().tag(), &crate::Count(6));
+    assert_eq!(sets.find(&3).unwrap().tag(), &crate::Count(0));
+}
+
+#[test]
+fn map_tags() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, vec![x]).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &1).unwrap();
+    let sets = sets.map_tags(|_, xs| crate::Sum(xs.iter().sum::<u32>()));
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets.find(&0).unwrap().tag(), &crate::Sum(3));
+    assert_eq!(sets.find(&0).unwrap().len(), 3);
+    assert_eq!(sets.find(&3).unwrap().tag(), &crate::Sum(3));
+    let sets = sets.map_tags(|key, _| crate::Min(*key));
+    assert_eq!(
+        sets.find(&2).unwrap().tag().0,
+        *sets.find(&1).unwrap().key()
+    );
+}
+
+#[test]
+fn for_each_tag_mut() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, crate::Sum(x)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    let mut visited = 0;
+    sets.for_each_tag_mut(|_, tag| {
+        tag.0 *= 10;
+        visited += 1;
+    });
+    assert_eq!(visited, 3);
+    assert_eq!(sets.find(&1).unwrap().tag(), &crate::Sum(10));
+    assert_eq!(sets.find(&3).unwrap().tag(), &crate::Sum(30));
+}
+
+#[test]
+fn exact_size_iteration() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..10u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    for x in 1..5u32 {
+        sets.unite(&0, &x).unwrap();
+    }
+    let mut iter = sets.iter();
+    assert_eq!(iter.len(), 6);
+    let first = iter.next().unwrap();
+    let rest = iter.clone();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.by_ref().count(), 5);
+    assert!(iter.next().is_none());
+    assert_eq!(rest.map(|xs| xs.len()).sum::<usize>() + first.len(), 10);
+}
+
+#[test]
+fn iterate_from_both_ends() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..10u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    for x in 1..5u32 {
+        sets.unite(&0, &x).unwrap();
+    }
+    let forward: Vec<_> = sets.iter().map(|xs| *xs.key()).collect();
+    let mut backward: Vec<_> = sets.iter().rev().map(|xs| *xs.key()).collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    let mut iter = sets.iter();
+    assert!(iter.next_back().is_some());
+    assert!(iter.next().is_some());
+    assert_eq!(iter.len(), 4);
+}
+
+#[test]
+fn stable_set_ids() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..6u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    let ids: Vec<_> = (0..6u32).map(|x| sets.find(&x).unwrap().id()).collect();
+    let distinct: std::collections::HashSet<_> = ids.iter().collect();
+    assert_eq!(distinct.len(), 6);
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &1).unwrap();
+    let id = sets.find(&0).unwrap().id();
+    assert!(ids[..3].contains(&id));
+    assert_eq!(sets.find(&2).unwrap().id(), id);
+    assert_eq!(sets.find(&5).unwrap().id(), ids[5]);
+    let found: std::collections::HashSet<_> = (0..6u32).map(|x| sets.find(&x).unwrap()).collect();
+    assert_eq!(found.len(), 4);
+    let iterated: std::collections::HashSet<_> = sets.iter().map(|xs| xs.id()).collect();
+    assert!(iterated.contains(&id));
+    assert_eq!(iterated.len(), 4);
+}
+
+#[test]
+fn build_in_two_passes() {
+    let mut builder = StreamingBuilder::with_capacity(5);
+    assert!(builder.is_empty());
+    builder.extend([
+        (1u32, crate::Count(1)),
+        (2, crate::Count(1)),
+        (1, crate::Count(1)),
+        (3, crate::Count(1)),
+    ]);
+    builder.push(2, crate::Count(1));
+    assert_eq!(builder.len(), 3);
+    let mut sets = builder.build().unwrap();
+    assert_eq!(sets.len(), 3);
+    assert_eq!(sets.find(&1).unwrap().tag(), &crate::Count(2));
+    assert_eq!(sets.find(&3).unwrap().tag(), &crate::Count(1));
+    sets.unite(&1, &3).unwrap();
+    assert_eq!(sets.find(&3).unwrap().tag(), &crate::Count(3));
+    assert_eq!(sets.find(&2).unwrap().tag(), &crate::Count(2));
+    assert_eq!(sets.len(), 2);
+    assert!(sets.make_set(2, crate::Count(1)).is_err());
+    sets.make_set(4, crate::Count(1)).unwrap();
+    assert_eq!(sets.len(), 3);
+}
+
+#[test]
+fn build_with_many_duplicates() {
+    let mut builder =
+        StreamingBuilder::with_capacity_and_hasher(10, crate::int_hash::BuildIntHasher);
+    builder.extend((0..100_000u32).map(|x| (x % 10, crate::Count(1))));
+    assert_eq!(builder.len(), 10);
+    let sets = builder.build().unwrap();
+    assert_eq!(sets.len(), 10);
+    assert_eq!(sets.find(&7).unwrap().tag(), &crate::Count(10_000));
+}
+
+#[test]
+fn count_union_outcomes() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    assert_eq!(sets.union_stats(), crate::UnionStats::default());
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &3).unwrap();
+    assert!(!sets.unite(&1, &0).unwrap());
+    assert!(sets.unite(&0, &9).is_err());
+    assert!(sets.try_unite(&8, &9).is_none());
+    let stats = sets.union_stats();
+    assert_eq!((stats.unions, stats.redundant, stats.missing), (2, 1, 2));
+    assert!((stats.redundancy() - 1.0 / 3.0).abs() < 1e-9);
+    sets.reset_union_stats();
+    assert_eq!(sets.union_stats().redundancy(), 0.0);
+    assert!(sets.unite(&1, &3).unwrap());
+    assert_eq!(sets.union_stats().unions, 1);
+}
+
+#[quickcheck]
+fn depth_like_raw(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<u8>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, ());
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    for x in queries {
+        let Some(depth) = sets.depth(&x) else {
+            assert!(sets.is_root(&x).is_none());
+            continue;
+        };
+        assert_eq!(sets.is_root(&x), Some(depth == 0));
+        let top = *sets.find(&x).unwrap().key();
+        assert_eq!(sets.depth(&x), Some(usize::from(top != x)));
+    }
+}
+
+#[test]
+fn tags_of_roots_are_dropped_once() {
+    let token = std::rc::Rc::new(());
+    let mut sets = UnionFindSets::new();
+    for x in 0..10u32 {
+        sets.make_set(x, vec![token.clone()]).unwrap();
+    }
+    for x in 1..5u32 {
+        sets.unite(&0, &x).unwrap();
+    }
+    sets.unite(&7, &8).unwrap();
+    assert_eq!(std::rc::Rc::strong_count(&token), 11);
+    let copy = sets.clone();
+    assert_eq!(std::rc::Rc::strong_count(&token), 21);
+    assert_eq!(copy.find(&3).unwrap().tag().len(), 5);
+    drop(copy);
+    assert_eq!(std::rc::Rc::strong_count(&token), 11);
+    let sets = sets.map_tags(|_, xs| crate::Count(xs.len()));
+    assert_eq!(std::rc::Rc::strong_count(&token), 1);
+    assert_eq!(sets.find(&8).unwrap().tag(), &crate::Count(2));
+}
+
+#[test]
+fn keys_are_held_once() {
+    let key: std::rc::Rc<str> = "foo".into();
+    let mut sets = UnionFindSets::new();
+    sets.make_set(key.clone(), ()).unwrap();
+    sets.make_set("bar".into(), ()).unwrap();
+    assert_eq!(std::rc::Rc::strong_count(&key), 2);
+    assert!(sets.contains("foo"));
+    assert!(sets.unite("foo", "bar").unwrap());
+    assert_eq!(sets.find("bar").unwrap().len(), 2);
+}
@@ -0,0 +1,55 @@
+use crate::raw::SetId;
+use crate::UnionFindSets;
+use core::hash::Hash;
+use hashbrown::HashMap;
+use polars::prelude::*;
+
+/// Labels every row by the component of its source node,
+/// where every row is an edge between columns `src` and `dst`.
+///
+/// Components are numbered from 0 in order of the rows they first label,
+/// and the returned column is named `component`, as long as the frame.
+/// If the source node of a row is null, so is its label;
+/// if the destination one is null, the source one is an isolated node.
+/// String columns are compared as strings, and any others are cast into `Int64`.
+pub fn components_column(df: &DataFrame, src: &str, dst: &str) -> PolarsResult<Series> {
+    let (xs, ys) = (df.column(src)?, df.column(dst)?);
+    let labels = if xs.dtype() == &DataType::String || ys.dtype() == &DataType::String {
+        let (xs, ys) = (xs.cast(&DataType::String)?, ys.cast(&DataType::String)?);
+        label_rows(xs.str()?.iter(), ys.str()?.iter())?
+    } else {
+        let (xs, ys) = (xs.cast(&DataType::Int64)?, ys.cast(&DataType::Int64)?);
+        label_rows(xs.i64()?.iter(), ys.i64()?.iter())?
+    };
+    Ok(Series::new("component".into(), labels))
+}
+
+fn label_rows<K, I, J>(xs: I, ys: J) -> PolarsResult<Vec<Option<u64>>>
+where
+    K: Eq + Hash + Clone,
+    I: Iterator<Item = Option<K>>,
+    J: Iterator<Item = Option<K>>,
+{
+    let edges: Vec<(Option<K>, Option<K>)> = xs.zip(ys).collect();
+    let mut sets = UnionFindSets::new();
+    for (x, y) in edges.iter() {
+        for key in [x, y].into_iter().flatten() {
+            let _ = sets.make_set(key.clone(), ());
+        }
+        if let (Some(x), Some(y)) = (x, y) {
+            sets.unite(x, y)
+                .map_err(|err| PolarsError::ComputeError(err.to_string().into()))?;
+        }
+    }
+    let mut labels: HashMap<SetId, u64, ahash::RandomState> =
+        HashMap::with_hasher(ahash::RandomState::new());
+    let labeled = edges
+        .iter()
+        .map(|(x, _)| {
+            let id = sets.find(x.as_ref()?)?.id();
+            let next = labels.len() as u64;
+            Some(*labels.entry(id).or_insert(next))
+        })
+        .collect();
+    Ok(labeled)
+}
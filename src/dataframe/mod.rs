@@ -0,0 +1,7 @@
+//! Connected components of edges in [polars](https://pola.rs) data frames.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
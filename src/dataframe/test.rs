@@ -0,0 +1,28 @@
+use super::*;
+use polars::prelude::*;
+
+#[test]
+fn label_string_edges() {
+    let df = df!(
+        "src" => [Some("c"), Some("a"), None, Some("b"), Some("x")],
+        "dst" => [Some("d"), Some("b"), Some("y"), Some("c"), None],
+    )
+    .unwrap();
+    let labels = components_column(&df, "src", "dst").unwrap();
+    assert_eq!(labels.name().as_str(), "component");
+    let labels: Vec<_> = labels.u64().unwrap().iter().collect();
+    assert_eq!(labels, [Some(0), Some(0), None, Some(0), Some(1)]);
+    assert!(components_column(&df, "src", "missing").is_err());
+}
+
+#[test]
+fn label_integer_edges() {
+    let df = df!(
+        "src" => [1i32, 3, 5, 2],
+        "dst" => [2i64, 4, 5, 1],
+    )
+    .unwrap();
+    let labels = components_column(&df, "src", "dst").unwrap();
+    let labels: Vec<_> = labels.u64().unwrap().iter().collect();
+    assert_eq!(labels, [Some(0), Some(1), Some(2), Some(0)]);
+}
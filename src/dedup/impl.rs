@@ -0,0 +1,107 @@
+use crate::algorithms::into_groups;
+use crate::compact::UnionFindSets;
+use crate::Result;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use hashbrown::HashMap;
+
+/// A cluster of duplicated items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster<T> {
+    /// The item standing for the whole cluster.
+    pub canonical: T,
+    /// The other items, in their original order.
+    pub duplicates: Vec<T>,
+}
+
+/// Clusters items by pairs of indices of duplicated items.
+///
+/// Clusters are in order of their first items, and items keep their original order.
+/// If a pair refers to no item,
+/// [Error::KeyNotFound](crate::Error::KeyNotFound) will be raised;
+/// if there are too many items,
+/// [Error::TooManyKeys](crate::Error::TooManyKeys) will be raised.
+pub fn cluster_pairs<T, I, P>(items: I, pairs: P) -> Result<Vec<Vec<T>>>
+where
+    I: IntoIterator<Item = T>,
+    P: IntoIterator<Item = (usize, usize)>,
+{
+    let items: Vec<T> = items.into_iter().collect();
+    let mut sets = UnionFindSets::new();
+    for i in 0..items.len() {
+        sets.make_set(i, ())?;
+    }
+    for (i, j) in pairs {
+        sets.unite(&i, &j)?;
+    }
+    Ok(into_groups(items, &sets))
+}
+
+/// Clusters items, by testing `is_duplicate` on every two items sharing a blocking key.
+///
+/// Items without any blocking key are never compared.
+/// Clusters are ordered as by [cluster_pairs].
+pub fn cluster_blocks<T, K, I, Ks, B, D>(
+    items: I,
+    mut blocks_of: B,
+    mut is_duplicate: D,
+) -> Result<Vec<Vec<T>>>
+where
+    I: IntoIterator<Item = T>,
+    K: Eq + Hash,
+    Ks: IntoIterator<Item = K>,
+    B: FnMut(&T) -> Ks,
+    D: FnMut(&T, &T) -> bool,
+{
+    let items: Vec<T> = items.into_iter().collect();
+    let mut blocks: HashMap<K, Vec<usize>, ahash::RandomState> =
+        HashMap::with_hasher(ahash::RandomState::new());
+    for (i, item) in items.iter().enumerate() {
+        for key in blocks_of(item) {
+            blocks.entry(key).or_default().push(i);
+        }
+    }
+    let mut sets = UnionFindSets::new();
+    for i in 0..items.len() {
+        sets.make_set(i, ())?;
+    }
+    for block in blocks.values() {
+        for (k, i) in block.iter().enumerate() {
+            for j in block[k + 1..].iter() {
+                if let (Some(x), Some(y)) = (sets.find(i), sets.find(j)) {
+                    if x == y {
+                        continue;
+                    }
+                }
+                if is_duplicate(&items[*i], &items[*j]) {
+                    sets.unite(i, j)?;
+                }
+            }
+        }
+    }
+    Ok(into_groups(items, &sets))
+}
+
+/// Picks the canonical item of every cluster by `choose`,
+/// which returns the index of the canonical item in a cluster.
+///
+/// If `choose` returns an index out of the cluster, the first item will be taken.
+/// Empty clusters are dropped.
+pub fn canonicalize<T, F>(clusters: Vec<Vec<T>>, mut choose: F) -> Vec<Cluster<T>>
+where
+    F: FnMut(&[T]) -> usize,
+{
+    clusters
+        .into_iter()
+        .filter(|xs| !xs.is_empty())
+        .map(|mut xs| {
+            let i = choose(&xs);
+            let i = if i < xs.len() { i } else { 0 };
+            let canonical = xs.remove(i);
+            Cluster {
+                canonical,
+                duplicates: xs,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,11 @@
+//! Clustering of duplicated items, and picking a canonical item out of every cluster.
+//!
+//! Duplicates are given either as pairs of indices of items,
+//! or by a test on items sharing any blocking key.
+//! Either way, being duplicated is taken as transitive.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
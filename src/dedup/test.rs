@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn cluster_by_pairs() {
+    let items = ["a", "b", "c", "d", "e"];
+    let clusters = cluster_pairs(items, [(0, 3), (4, 3), (1, 1)]).unwrap();
+    assert_eq!(clusters, vec![vec!["a", "d", "e"], vec!["b"], vec!["c"]]);
+    assert!(matches!(
+        cluster_pairs(items, [(0, 5)]),
+        Err(crate::Error::KeyNotFound { .. })
+    ));
+}
+
+#[test]
+fn cluster_by_blocks() {
+    let names = ["Smith, J", "Smyth, J", "Jones, A", "smith, j", "Smith, K"];
+    let clusters = cluster_blocks(
+        names,
+        |name| name.get(..1).map(str::to_lowercase),
+        |x, y| {
+            let x = x.to_lowercase().replace('y', "i");
+            let y = y.to_lowercase().replace('y', "i");
+            x == y
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        clusters,
+        vec![
+            vec!["Smith, J", "Smyth, J", "smith, j"],
+            vec!["Jones, A"],
+            vec!["Smith, K"]
+        ]
+    );
+}
+
+#[test]
+fn pick_canonical_items() {
+    let clusters = vec![vec!["ab", "abc", "a"], vec!["x"], vec![]];
+    let clusters = canonicalize(clusters, |xs| {
+        (0..xs.len()).max_by_key(|i| xs[*i].len()).unwrap_or(0)
+    });
+    assert_eq!(
+        clusters,
+        vec![
+            Cluster {
+                canonical: "abc",
+                duplicates: vec!["ab", "a"]
+            },
+            Cluster {
+                canonical: "x",
+                duplicates: vec![]
+            },
+        ]
+    );
+    let clusters = canonicalize(vec![vec![1, 2]], |_| 7);
+    assert_eq!(clusters[0].canonical, 1);
+}
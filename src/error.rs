@@ -0,0 +1,68 @@
+use core::fmt;
+
+/// Errors raised by operations on union-find sets.
+///
+/// It implements [core::error::Error],
+/// so `?` converts it into `anyhow::Error` or `Box<dyn Error>` as usual.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// The key to make a set of is already in the sets.
+    DuplicateKey,
+    /// A key to unite is not in the sets.
+    KeyNotFound { which: Which },
+    /// A key of a batch, e.g., to `unite_many`, is not in the sets,
+    /// where `index` is its position in the batch.
+    KeyNotFoundAt { index: usize },
+    /// The sets cannot hold any more keys.
+    TooManyKeys,
+    /// The sets are found internally inconsistent,
+    /// which only happens when `Hash`, `Eq` or `Clone` of keys disagree with each other.
+    Inconsistent,
+}
+
+/// Tells which of the keys passed to a binary operation, e.g., `unite`, an error is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Which {
+    Key1,
+    Key2,
+    Both,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::DuplicateKey => write!(f, "Duplicated key!"),
+            Error::KeyNotFound { which } => write!(f, "Cannot find set: {}", which),
+            Error::KeyNotFoundAt { index } => write!(f, "Cannot find set: key at {}", index),
+            Error::TooManyKeys => write!(f, "Too many keys!"),
+            Error::Inconsistent => write!(f, "Inconsistent sets!"),
+        }
+    }
+}
+
+impl fmt::Display for Which {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Which::Key1 => write!(f, "key1"),
+            Which::Key2 => write!(f, "key2"),
+            Which::Both => write!(f, "key1 and key2"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// Pairs up what are found by two keys,
+/// or tells which of them are not found.
+#[cfg(feature = "alloc")]
+pub(crate) fn found_both<T, U>(x: Option<T>, y: Option<U>) -> Result<(T, U)> {
+    match (x, y) {
+        (Some(x), Some(y)) => Ok((x, y)),
+        (None, Some(_)) => Err(Error::KeyNotFound { which: Which::Key1 }),
+        (Some(_), None) => Err(Error::KeyNotFound { which: Which::Key2 }),
+        (None, None) => Err(Error::KeyNotFound { which: Which::Both }),
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+const WORD_LEN: u64 = 8;
+const DEFAULT_PAGE_LEN: usize = 4096;
+/// Marks a word as a root, whose remaining bits are the size of its set.
+/// A zero word is a root of a singleton, so a sparse fresh file is already a valid forest.
+/// Any other word is the parent plus one.
+const ROOT_BIT: u64 = 1 << 63;
+
+struct Page {
+    words: Vec<u64>,
+    dirty: bool,
+    last_used: u64,
+}
+
+/// Union-find sets over keys `0..capacity`, backed by a file with an LRU page cache.
+///
+/// Changes are written back to the file when their pages are evicted or on [flush](Self::flush).
+/// Dropping the sets flushes them too, but ignores errors,
+/// so [flush](Self::flush) should be called to know the file is complete.
+pub struct ExternalUnionFind {
+    file: File,
+    capacity: u64,
+    sets: u64,
+    page_len: usize,
+    cache_pages: usize,
+    pages: HashMap<u64, Page, ahash::RandomState>,
+    lru: BTreeMap<u64, u64>,
+    clock: u64,
+}
+
+impl ExternalUnionFind {
+    /// Creates `capacity` singleton sets, keyed by `0..capacity`, in a new file at `path`,
+    /// which replaces any existing file.
+    ///
+    /// At most `cache_pages` pages of the file are held in memory at the same time,
+    /// which must be at least 2.
+    pub fn create<P: AsRef<Path>>(path: P, capacity: u64, cache_pages: usize) -> io::Result<Self> {
+        Self::create_with_page_len(path, capacity, cache_pages, DEFAULT_PAGE_LEN)
+    }
+
+    /// Opens sets previously saved at `path`, by [create](Self::create) and [flush](Self::flush).
+    ///
+    /// The file is scanned once to count sets.
+    pub fn open<P: AsRef<Path>>(path: P, cache_pages: usize) -> io::Result<Self> {
+        Self::open_with_page_len(path, cache_pages, DEFAULT_PAGE_LEN)
+    }
+
+    pub(crate) fn create_with_page_len<P: AsRef<Path>>(
+        path: P,
+        capacity: u64,
+        cache_pages: usize,
+        page_len: usize,
+    ) -> io::Result<Self> {
+        check_cache(cache_pages, page_len)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let file_len = capacity
+            .checked_mul(WORD_LEN)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "too many keys"))?;
+        file.set_len(file_len)?;
+        Ok(Self::with_file(
+            file,
+            capacity,
+            capacity,
+            cache_pages,
+            page_len,
+        ))
+    }
+
+    pub(crate) fn open_with_page_len<P: AsRef<Path>>(
+        path: P,
+        cache_pages: usize,
+        page_len: usize,
+    ) -> io::Result<Self> {
+        check_cache(cache_pages, page_len)?;
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+        let file_len = file.metadata()?.len();
+        if file_len % WORD_LEN != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a file of union-find sets",
+            ));
+        }
+        let capacity = file_len / WORD_LEN;
+        let mut sets = 0;
+        for start in (0..capacity).step_by(page_len) {
+            let len = (capacity - start).min(page_len as u64) as usize;
+            for word in load(&mut file, start, len)? {
+                if is_root(word) {
+                    sets += 1;
+                } else if word > capacity {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "parent out of the file",
+                    ));
+                }
+            }
+        }
+        Ok(Self::with_file(file, capacity, sets, cache_pages, page_len))
+    }
+
+    fn with_file(
+        file: File,
+        capacity: u64,
+        sets: u64,
+        cache_pages: usize,
+        page_len: usize,
+    ) -> Self {
+        Self {
+            file,
+            capacity,
+            sets,
+            page_len,
+            cache_pages,
+            pages: HashMap::with_hasher(ahash::RandomState::new()),
+            lru: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// Queries the number of keys, i.e., keys are in `0..capacity()`.
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Queries the number of individual sets.
+    pub fn len(&self) -> u64 {
+        self.sets
+    }
+
+    /// Tests if there is no set at all.
+    pub fn is_empty(&self) -> bool {
+        self.sets == 0
+    }
+
+    /// Finds the representative of the set containing `key`.
+    ///
+    /// If `key` is out of `0..capacity()`, `None` will be returned.
+    pub fn find(&mut self, key: u64) -> io::Result<Option<u64>> {
+        if key >= self.capacity {
+            return Ok(None);
+        }
+        self.find_top(key).map(Some)
+    }
+
+    /// Queries the number of elements in the set containing `key`.
+    ///
+    /// If `key` is out of `0..capacity()`, `None` will be returned.
+    pub fn set_len(&mut self, key: u64) -> io::Result<Option<u64>> {
+        let Some(top) = self.find(key)? else {
+            return Ok(None);
+        };
+        Ok(Some(root_size(self.read(top)?)))
+    }
+
+    /// Unites two sets.
+    ///
+    /// If either key is out of `0..capacity()`, an error will be raised;
+    /// if they are of a same set, `Ok(false)` will be returned;
+    /// otherwise `Ok(true)` will be returned.
+    pub fn unite(&mut self, key1: u64, key2: u64) -> io::Result<bool> {
+        for key in [key1, key2] {
+            if key >= self.capacity {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Cannot find set: {}", key),
+                ));
+            }
+        }
+        let top1 = self.find_top(key1)?;
+        let top2 = self.find_top(key2)?;
+        if top1 == top2 {
+            return Ok(false);
+        }
+        let size1 = root_size(self.read(top1)?);
+        let size2 = root_size(self.read(top2)?);
+        let (parent, child) = if size1 > size2 {
+            (top1, top2)
+        } else {
+            (top2, top1)
+        };
+        self.write_together([(child, parent + 1), (parent, ROOT_BIT | (size1 + size2))])?;
+        self.sets -= 1;
+        Ok(true)
+    }
+
+    /// Writes all dirty cached pages back to the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        let dirty: Vec<u64> = self
+            .pages
+            .iter()
+            .filter(|(_, page)| page.dirty)
+            .map(|(page_no, _)| *page_no)
+            .collect();
+        for page_no in dirty {
+            self.write_back(page_no)?;
+        }
+        self.file.flush()
+    }
+
+    fn find_top(&mut self, key: u64) -> io::Result<u64> {
+        let mut top = key;
+        loop {
+            let word = self.read(top)?;
+            if is_root(word) {
+                break;
+            }
+            top = word - 1;
+        }
+        let mut cur = key;
+        while cur != top {
+            let nxt = self.read(cur)? - 1;
+            if nxt != top {
+                self.write(cur, top + 1)?;
+            }
+            cur = nxt;
+        }
+        Ok(top)
+    }
+
+    fn read(&mut self, key: u64) -> io::Result<u64> {
+        let (page_no, offset) = self.locate(key);
+        let page = self.page(page_no)?;
+        Ok(page.words[offset])
+    }
+
+    fn write(&mut self, key: u64, word: u64) -> io::Result<()> {
+        let (page_no, offset) = self.locate(key);
+        let page = self.page(page_no)?;
+        page.words[offset] = word;
+        page.dirty = true;
+        Ok(())
+    }
+
+    /// Writes words all at once:
+    /// their pages, which are at most as many as cached pages, are all loaded before any word changes,
+    /// so an I/O error leaves every word as it was.
+    fn write_together<const N: usize>(&mut self, writes: [(u64, u64); N]) -> io::Result<()> {
+        debug_assert!(N <= self.cache_pages);
+        for (key, _) in writes {
+            self.page(self.locate(key).0)?;
+        }
+        for (key, word) in writes {
+            let (page_no, offset) = self.locate(key);
+            // pages loaded above are the latest used, which are not evicted by each other
+            let page = self.pages.get_mut(&page_no).expect("page is cached");
+            page.words[offset] = word;
+            page.dirty = true;
+        }
+        Ok(())
+    }
+
+    fn locate(&self, key: u64) -> (u64, usize) {
+        let page_len = self.page_len as u64;
+        (key / page_len, (key % page_len) as usize)
+    }
+
+    fn page(&mut self, page_no: u64) -> io::Result<&mut Page> {
+        self.clock += 1;
+        if !self.pages.contains_key(&page_no) && self.pages.len() >= self.cache_pages {
+            if let Some((last_used, victim)) = self.lru.pop_first() {
+                if let Err(err) = self.write_back(victim) {
+                    // the victim stays cached, so it must stay evictable
+                    self.lru.insert(last_used, victim);
+                    return Err(err);
+                }
+                self.pages.remove(&victim);
+            }
+        }
+        let page = match self.pages.entry(page_no) {
+            Entry::Occupied(entry) => {
+                let page = entry.into_mut();
+                self.lru.remove(&page.last_used);
+                page
+            }
+            Entry::Vacant(entry) => {
+                let start = page_no * self.page_len as u64;
+                let len = (self.capacity - start).min(self.page_len as u64) as usize;
+                let words = load(&mut self.file, start, len)?;
+                entry.insert(Page {
+                    words,
+                    dirty: false,
+                    last_used: 0,
+                })
+            }
+        };
+        page.last_used = self.clock;
+        self.lru.insert(self.clock, page_no);
+        Ok(page)
+    }
+
+    fn write_back(&mut self, page_no: u64) -> io::Result<()> {
+        let Some(page) = self.pages.get_mut(&page_no) else {
+            return Ok(());
+        };
+        if !page.dirty {
+            return Ok(());
+        }
+        let bytes: Vec<u8> = page.words.iter().flat_map(|x| x.to_le_bytes()).collect();
+        let start = page_no * self.page_len as u64 * WORD_LEN;
+        self.file.seek(SeekFrom::Start(start))?;
+        self.file.write_all(&bytes)?;
+        if let Some(page) = self.pages.get_mut(&page_no) {
+            page.dirty = false;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ExternalUnionFind {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+fn check_cache(cache_pages: usize, page_len: usize) -> io::Result<()> {
+    if cache_pages < 2 || page_len == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "at least two pages of at least one key must be cached",
+        ));
+    }
+    Ok(())
+}
+
+/// Reads `len` words from the `start`-th word of `file`.
+fn load(file: &mut File, start: u64, len: usize) -> io::Result<Vec<u64>> {
+    let mut bytes = vec![0u8; len * WORD_LEN as usize];
+    file.seek(SeekFrom::Start(start * WORD_LEN))?;
+    file.read_exact(&mut bytes)?;
+    let (words, _) = bytes.as_chunks::<{ WORD_LEN as usize }>();
+    Ok(words.iter().map(|x| u64::from_le_bytes(*x)).collect())
+}
+
+fn is_root(word: u64) -> bool {
+    word == 0 || word & ROOT_BIT != 0
+}
+
+fn root_size(word: u64) -> u64 {
+    if word == 0 {
+        1
+    } else {
+        word & !ROOT_BIT
+    }
+}
@@ -0,0 +1,11 @@
+//! Out-of-core union-find sets over dense `u64` keys.
+//!
+//! The forest is kept in a file and only a bounded number of its pages are cached in memory,
+//! so key spaces larger than RAM can still be processed, at the price of slower operations.
+//! Customized tags are not spilled; each set only knows its number of elements.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
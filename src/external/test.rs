@@ -0,0 +1,74 @@
+use super::*;
+use crate::raw::UnionFindSets;
+use quickcheck_macros::*;
+use std::io::Write;
+
+#[quickcheck]
+fn connect_query(connects: Vec<(u8, u8)>, queries: Vec<(u8, u8)>) {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    // tiny pages and cache so that pages are evicted and reloaded all the time
+    let mut trial = ExternalUnionFind::create_with_page_len(file.path(), 200, 3, 8).unwrap();
+    let mut oracle = UnionFindSets::new();
+    for x in 0..200u8 {
+        oracle.make_set(x, ()).unwrap();
+    }
+
+    for (x, y) in connects.into_iter() {
+        let trial_res = trial.unite(x as u64, y as u64);
+        let oracle_res = oracle.unite(&x, &y);
+        assert_eq!(trial_res.ok(), oracle_res.ok());
+    }
+    assert_eq!(trial.len(), oracle.len() as u64);
+
+    for (x, y) in queries.into_iter() {
+        let trial_x = trial.find(x as u64).unwrap();
+        let trial_y = trial.find(y as u64).unwrap();
+        let oracle_x = oracle.find(&x);
+        let oracle_y = oracle.find(&y);
+        assert_eq!(trial_x.is_some(), oracle_x.is_some());
+        assert_eq!(trial_y.is_some(), oracle_y.is_some());
+        if let (Some(oracle_x), Some(oracle_y)) = (oracle_x, oracle_y) {
+            assert_eq!(trial_x == trial_y, oracle_x == oracle_y);
+            assert_eq!(
+                trial.set_len(x as u64).unwrap(),
+                Some(oracle_x.len() as u64)
+            );
+        }
+    }
+}
+
+#[test]
+fn reopen() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let mut sets = ExternalUnionFind::create_with_page_len(file.path(), 100, 2, 8).unwrap();
+    for x in 1..50 {
+        sets.unite(x - 1, x).unwrap();
+    }
+    sets.flush().unwrap();
+    sets.unite(60, 99).unwrap();
+    // the last union is only written back on drop
+    drop(sets);
+
+    let mut sets = ExternalUnionFind::open_with_page_len(file.path(), 2, 8).unwrap();
+    assert_eq!(sets.capacity(), 100);
+    assert_eq!(sets.len(), 50);
+    assert_eq!(sets.set_len(7).unwrap(), Some(50));
+    assert_eq!(sets.find(60).unwrap(), sets.find(99).unwrap());
+    assert!(!sets.unite(0, 49).unwrap());
+}
+
+#[test]
+fn reject_tiny_cache() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    assert!(ExternalUnionFind::create(file.path(), 10, 1).is_err());
+    assert!(ExternalUnionFind::create(file.path(), 10, 2).is_ok());
+}
+
+#[test]
+fn reject_garbage() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"not sets").unwrap();
+    file.write_all(&u64::MAX.to_le_bytes()[..7]).unwrap();
+    file.flush().unwrap();
+    assert!(ExternalUnionFind::open(file.path(), 2).is_err());
+}
@@ -0,0 +1,73 @@
+use alloc::format;
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// The first bytes of a versioned file.
+pub const MAGIC: [u8; 4] = *b"TUFS";
+
+/// The version of files written by [save].
+pub const VERSION: u16 = 1;
+
+/// Writes a value, e.g., sets, with the header of the current [VERSION].
+pub fn save<T: BorshSerialize, W: Write>(value: &T, writer: &mut W) -> Result<()> {
+    writer.write_all(&MAGIC)?;
+    VERSION.serialize(writer)?;
+    value.serialize(writer)
+}
+
+/// Reads a value written by [save] of this or any older version, or a plain borsh encoding.
+///
+/// If the file is of a newer version, [ErrorKind::InvalidData] will be raised, telling versions.
+pub fn load<T: BorshDeserialize, R: Read>(reader: &mut R) -> Result<T> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        let mut reader = Prefixed {
+            prefix: &magic,
+            reader,
+        };
+        return decode_v0(&mut reader);
+    }
+    match u16::deserialize_reader(reader)? {
+        0 => decode_v0(reader),
+        1 => decode_v1(reader),
+        version => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "file format version {} is newer than the supported version {}",
+                version, VERSION
+            ),
+        )),
+    }
+}
+
+/// Decodes the body of version 0, the plain borsh encoding written before the header existed.
+fn decode_v0<T: BorshDeserialize, R: Read>(reader: &mut R) -> Result<T> {
+    T::deserialize_reader(reader)
+}
+
+/// Decodes the body of version 1, which only put the header before the body of version 0.
+///
+/// A later version changing the body gets a decoder of its own,
+/// while older ones keep decoding and migrating what they always have.
+fn decode_v1<T: BorshDeserialize, R: Read>(reader: &mut R) -> Result<T> {
+    decode_v0(reader)
+}
+
+/// Reads bytes already consumed before the rest of a reader.
+struct Prefixed<'a, R> {
+    prefix: &'a [u8],
+    reader: &'a mut R,
+}
+
+impl<R: Read> Read for Prefixed<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.prefix.is_empty() {
+            return self.reader.read(buf);
+        }
+        let n = self.prefix.len().min(buf.len());
+        buf[..n].copy_from_slice(&self.prefix[..n]);
+        self.prefix = &self.prefix[n..];
+        Ok(n)
+    }
+}
@@ -0,0 +1,14 @@
+//! Versioned files of sets, by a header before the [borsh](https://borsh.io) encoding.
+//!
+//! A file starts with [MAGIC] and a little-endian `u16` version.
+//! Files of older versions are still read, migrating them as needed,
+//! while those of versions newer than [VERSION] are rejected.
+//! Files without the header are plain borsh encodings, i.e., version 0.
+//! Every version is decoded by a path of its own,
+//! though the body of version 1 is still the same as that of version 0.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,82 @@
+use super::*;
+use crate::UnionFindSets;
+
+fn sample() -> UnionFindSets<u32, Vec<u32>> {
+    let mut sets = UnionFindSets::new();
+    for x in 0..6u32 {
+        sets.make_set(x, vec![x]).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &1).unwrap();
+    sets
+}
+
+fn sizes(sets: &UnionFindSets<u32, Vec<u32>>) -> Vec<(usize, usize)> {
+    let mut sizes: Vec<_> = sets.iter().map(|xs| (xs.len(), xs.tag().len())).collect();
+    sizes.sort();
+    sizes
+}
+
+#[test]
+fn save_and_load() {
+    let sets = sample();
+    let mut file = Vec::new();
+    save(&sets, &mut file).unwrap();
+    assert_eq!(file[..6], [b'T', b'U', b'F', b'S', 1, 0]);
+    let loaded: UnionFindSets<u32, Vec<u32>> = load(&mut file.as_slice()).unwrap();
+    assert_eq!(sizes(&loaded), sizes(&sets));
+    let raw: crate::raw::UnionFindSets<u32, ()> = {
+        let mut raw = crate::raw::UnionFindSets::new();
+        raw.make_set(7, ()).unwrap();
+        raw
+    };
+    let mut file = Vec::new();
+    save(&raw, &mut file).unwrap();
+    let loaded: crate::raw::UnionFindSets<u32, ()> = load(&mut file.as_slice()).unwrap();
+    assert_eq!(loaded.len(), 1);
+}
+
+#[test]
+fn load_older_versions() {
+    let sets = sample();
+    let legacy = borsh::to_vec(&sets).unwrap();
+    let loaded: UnionFindSets<u32, Vec<u32>> = load(&mut legacy.as_slice()).unwrap();
+    assert_eq!(sizes(&loaded), sizes(&sets));
+    let mut v0 = b"TUFS\0\0".to_vec();
+    v0.extend_from_slice(&legacy);
+    let loaded: UnionFindSets<u32, Vec<u32>> = load(&mut v0.as_slice()).unwrap();
+    assert_eq!(sizes(&loaded), sizes(&sets));
+    let empty = borsh::to_vec(&UnionFindSets::<u32, ()>::new()).unwrap();
+    let loaded: UnionFindSets<u32, ()> = load(&mut empty.as_slice()).unwrap();
+    assert!(loaded.is_empty());
+}
+
+/// Raw sets of `7` and `8` under the root `7`, as written by version 0, i.e., before the header.
+const V0_FIXTURE: [u8; 26] = [
+    2, 0, 0, 0, // two nodes in key order
+    7, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, // `7`, a root of size 2 and a unit tag
+    8, 0, 0, 0, 0, 7, 0, 0, 0, // `8`, a parent link to `7`
+];
+
+#[test]
+fn load_v0_fixture() {
+    let check = |sets: crate::raw::UnionFindSets<u32, ()>| {
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets.find(&8).unwrap().key(), &7);
+        assert_eq!(sets.find(&7).unwrap().len(), 2);
+    };
+    check(load(&mut V0_FIXTURE.as_slice()).unwrap());
+    let mut v0 = b"TUFS\0\0".to_vec();
+    v0.extend_from_slice(&V0_FIXTURE);
+    check(load(&mut v0.as_slice()).unwrap());
+}
+
+#[test]
+fn reject_newer_versions() {
+    let mut file = Vec::new();
+    save(&sample(), &mut file).unwrap();
+    file[4] = 2;
+    let err = load::<UnionFindSets<u32, Vec<u32>>, _>(&mut file.as_slice()).unwrap_err();
+    assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("version 2"));
+}
@@ -0,0 +1,96 @@
+use crate::{static_ufs, Error, Mergable, UnionFindSets};
+use petgraph::graph::{Graph, IndexType, NodeIndex};
+use petgraph::unionfind::UnionFind;
+use petgraph::EdgeType;
+
+/// Finds connected components of a graph, regardless of directions of edges.
+///
+/// Every node makes a set tagged with its weight,
+/// so the tag of a component is the merge of weights of its nodes.
+pub fn from_graph<N, E, Ty, Ix>(graph: &Graph<N, E, Ty, Ix>) -> UnionFindSets<NodeIndex<Ix>, N>
+where
+    N: Clone + Mergable,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    let mut sets = UnionFindSets::new();
+    for node in graph.node_indices() {
+        // node indices are distinct
+        let _ = sets.make_set(node, graph[node].clone());
+    }
+    for edge in graph.raw_edges() {
+        // ends of edges are always nodes of the graph
+        let _ = sets.unite(&edge.source(), &edge.target());
+    }
+    sets
+}
+
+/// Writes indices of components back into weights of nodes by `f`.
+///
+/// Components are numbered from 0, in ascending order of their representatives,
+/// so the numbering is stable for the same graph.
+/// Nodes not in `sets` are left untouched.
+pub fn write_components<N, E, Ty, Ix, Tag, F>(
+    sets: &UnionFindSets<NodeIndex<Ix>, Tag>,
+    graph: &mut Graph<N, E, Ty, Ix>,
+    mut f: F,
+) where
+    Ty: EdgeType,
+    Ix: IndexType,
+    Tag: Mergable,
+    F: FnMut(&mut N, usize),
+{
+    for (i, set) in sets.iter_sets_sorted_by_key().enumerate() {
+        for node in set.iter() {
+            if let Some(weight) = graph.node_weight_mut(*node) {
+                f(weight, i);
+            }
+        }
+    }
+}
+
+/// Copies the partition of `0..N` into petgraph's union-find sets.
+///
+/// If `Ix` cannot index `N` elements, [Error::TooManyKeys] will be raised.
+impl<const N: usize, Tag, Ix> TryFrom<&static_ufs::UnionFind<N, Tag>> for UnionFind<Ix>
+where
+    Ix: IndexType,
+{
+    type Error = Error;
+
+    fn try_from(sets: &static_ufs::UnionFind<N, Tag>) -> Result<Self, Error> {
+        if N > 0 && N - 1 > <Ix as IndexType>::max().index() {
+            return Err(Error::TooManyKeys);
+        }
+        let mut res = UnionFind::new(N);
+        for key in 0..N {
+            if let Some(top) = sets.find(key) {
+                res.union(Ix::new(key), Ix::new(top));
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Copies the partition of petgraph's union-find sets into `0..N`,
+/// where indices beyond petgraph's sets are left as singletons.
+///
+/// If there are more than `N` elements, [Error::TooManyKeys] will be raised.
+impl<const N: usize, Ix> TryFrom<&UnionFind<Ix>> for static_ufs::UnionFind<N>
+where
+    Ix: IndexType,
+{
+    type Error = Error;
+
+    fn try_from(sets: &UnionFind<Ix>) -> Result<Self, Error> {
+        if sets.len() > N {
+            return Err(Error::TooManyKeys);
+        }
+        let mut res = static_ufs::UnionFind::new();
+        for key in 0..sets.len() {
+            let top = sets.find(Ix::new(key));
+            res.unite(key, top.index());
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,10 @@
+//! Integration with [petgraph](https://docs.rs/petgraph).
+//!
+//! Connected components of a graph are found by uniting the ends of every edge,
+//! where weights of nodes in a component are merged into its tag.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,61 @@
+use super::*;
+use crate::Sum;
+use petgraph::graph::{DiGraph, UnGraph};
+
+#[test]
+fn components_of_graph() {
+    let mut graph = UnGraph::<Sum<u32>, ()>::new_undirected();
+    let nodes: Vec<_> = (0..6).map(|x| graph.add_node(Sum(x))).collect();
+    graph.add_edge(nodes[0], nodes[1], ());
+    graph.add_edge(nodes[1], nodes[2], ());
+    graph.add_edge(nodes[4], nodes[5], ());
+    let sets = from_graph(&graph);
+    assert_eq!(sets.len(), 3);
+    assert_eq!(sets.find(&nodes[2]).unwrap().tag(), &Sum(3));
+    assert_eq!(sets.find(&nodes[3]).unwrap().tag(), &Sum(3));
+    assert_eq!(sets.find(&nodes[5]).unwrap().tag(), &Sum(9));
+}
+
+#[test]
+fn directions_are_ignored() {
+    let mut graph = DiGraph::<(), ()>::new();
+    let nodes: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+    graph.add_edge(nodes[0], nodes[2], ());
+    graph.add_edge(nodes[1], nodes[2], ());
+    assert_eq!(from_graph(&graph).len(), 1);
+}
+
+#[test]
+fn write_back_components() {
+    let mut graph = UnGraph::<(u32, usize), ()>::new_undirected();
+    let nodes: Vec<_> = (0..4).map(|_| graph.add_node((0, usize::MAX))).collect();
+    graph.add_edge(nodes[3], nodes[1], ());
+    let sets = from_graph(&graph.map(|_, _| (), |_, _| ()));
+    write_components(&sets, &mut graph, |weight, i| weight.1 = i);
+    let labels: Vec<_> = nodes.iter().map(|x| graph[*x].1).collect();
+    assert_eq!(labels, vec![0, 1, 2, 1]);
+}
+
+#[test]
+fn convert_from_and_to_petgraph() {
+    let mut sets = crate::static_ufs::UnionFind::<6>::new();
+    sets.unite(0, 3);
+    sets.unite(4, 3);
+    sets.unite(1, 5);
+    let theirs = petgraph::unionfind::UnionFind::<u32>::try_from(&sets).unwrap();
+    assert!(theirs.equiv(0, 4));
+    assert!(theirs.equiv(1, 5));
+    assert!(!theirs.equiv(0, 1));
+    assert!(!theirs.equiv(2, 3));
+
+    let ours = crate::static_ufs::UnionFind::<8>::try_from(&theirs).unwrap();
+    assert_eq!(ours.len(), 5);
+    for x in 0..6 {
+        for y in 0..6 {
+            assert_eq!(ours.same_set(x, y), sets.same_set(x, y));
+        }
+    }
+    assert!(crate::static_ufs::UnionFind::<4>::try_from(&theirs).is_err());
+    let big = crate::static_ufs::UnionFind::<300>::new();
+    assert!(petgraph::unionfind::UnionFind::<u8>::try_from(&big).is_err());
+}
@@ -0,0 +1,170 @@
+use crate::error::found_both;
+use crate::{Mergable, Result, Set, UnionFindSets};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::Hash;
+use hashbrown::HashMap;
+
+/// A handle of an element of [HandleSets].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: u32,
+    generation: u32,
+}
+
+struct Slot<Key> {
+    generation: u32,
+    key: Option<Key>,
+}
+
+/// Sets whose elements are addressed by [Handle]s.
+///
+/// ```
+/// use tagged_ufs::handles::HandleSets;
+///
+/// let mut sets = HandleSets::new();
+/// let a = sets.make_set("a", ()).unwrap();
+/// let b = sets.make_set("b", ()).unwrap();
+/// let root = sets.representative(a).unwrap();
+/// sets.unite(a, b).unwrap();
+/// // `root` may have been merged away, but it still reaches the united set
+/// assert_eq!(sets.find(root).unwrap().len(), 2);
+/// sets.remove_set(b).unwrap();
+/// assert!(sets.find(a).is_none());
+/// let c = sets.make_set("c", ()).unwrap();
+/// assert!(sets.find(a).is_none());
+/// assert!(sets.find(c).is_some());
+/// ```
+pub struct HandleSets<Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    sets: UnionFindSets<Key, Tag>,
+    slots: Vec<Slot<Key>>,
+    indices: HashMap<Key, u32, ahash::RandomState>,
+    free: Vec<u32>,
+}
+
+impl<Key, Tag> Default for HandleSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Key, Tag> HandleSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Makes new, empty sets.
+    pub fn new() -> Self {
+        Self {
+            sets: UnionFindSets::new(),
+            slots: Vec::new(),
+            indices: HashMap::with_hasher(ahash::RandomState::new()),
+            free: Vec::new(),
+        }
+    }
+
+    /// Makes an individual set with a singleton element and its associated tag,
+    /// like [UnionFindSets::make_set], and returns the handle of the element.
+    pub fn make_set(&mut self, key: Key, tag: Tag) -> Result<Handle> {
+        let index = match self.free.last() {
+            Some(index) => *index,
+            None => u32::try_from(self.slots.len()).map_err(|_| crate::Error::TooManyKeys)?,
+        };
+        self.sets.make_set(key.clone(), tag)?;
+        self.indices.insert(key.clone(), index);
+        if self.free.pop().is_none() {
+            self.slots.push(Slot {
+                generation: 0,
+                key: None,
+            });
+        }
+        let slot = &mut self.slots[index as usize];
+        slot.key = Some(key);
+        Ok(Handle {
+            index,
+            generation: slot.generation,
+        })
+    }
+
+    /// Returns the handle of an element, if it is in the sets.
+    pub fn handle<Q>(&self, key: &Q) -> Option<Handle>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let index = *self.indices.get(key)?;
+        let slot = self.slots.get(index as usize)?;
+        Some(Handle {
+            index,
+            generation: slot.generation,
+        })
+    }
+
+    /// Returns the key of an element, or `None` if the handle is stale.
+    pub fn key(&self, handle: Handle) -> Option<&Key> {
+        let slot = self.slots.get(handle.index as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.key.as_ref()
+    }
+
+    /// Finds the current set of an element, or `None` if the handle is stale.
+    pub fn find(&self, handle: Handle) -> Option<Set<'_, Key, Tag>> {
+        self.sets.find(self.key(handle)?)
+    }
+
+    /// Returns the handle of the representative of the current set of an element.
+    pub fn representative(&self, handle: Handle) -> Option<Handle> {
+        self.handle(self.find(handle)?.key())
+    }
+
+    /// Unites the sets of two elements, like [UnionFindSets::unite],
+    /// where stale handles are reported as [Error::KeyNotFound](crate::Error::KeyNotFound).
+    pub fn unite(&mut self, handle1: Handle, handle2: Handle) -> Result<bool> {
+        let (key1, key2) = found_both(self.key(handle1), self.key(handle2))?;
+        let (key1, key2) = (key1.clone(), key2.clone());
+        self.sets.unite(&key1, &key2)
+    }
+
+    /// Removes the whole set containing an element, returning its tag and elements.
+    /// Handles of all these elements become stale.
+    pub fn remove_set(&mut self, handle: Handle) -> Option<(Tag, Vec<Key>)> {
+        let key = self.key(handle)?.clone();
+        let (keys, tag) = self.sets.take_set(&key)?;
+        for x in keys.iter() {
+            let Some(index) = self.indices.remove(x) else {
+                continue;
+            };
+            if let Some(slot) = self.slots.get_mut(index as usize) {
+                slot.generation = slot.generation.wrapping_add(1);
+                slot.key = None;
+                self.free.push(index);
+            }
+        }
+        Some((tag, keys))
+    }
+
+    /// Returns the underlying sets.
+    pub fn sets(&self) -> &UnionFindSets<Key, Tag> {
+        &self.sets
+    }
+
+    /// Returns the number of sets.
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Tests whether there is no set.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+}
@@ -0,0 +1,12 @@
+//! Sets addressed by copyable handles of elements instead of keys.
+//!
+//! A handle names an element, not a set, so it never goes stale by unions:
+//! finding by it always reaches the current set of the element.
+//! Removing a set bumps the generations of its elements,
+//! so that their handles fail cleanly even after their slots are reused.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,47 @@
+use super::*;
+use crate::raw::test::Oracle;
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn handles_follow_elements(adds: Vec<u8>, connects: Vec<(u8, u8)>, removes: Vec<u8>) {
+    let mut trial = HandleSets::new();
+    let mut oracle = Oracle::new();
+    let mut handles = Vec::new();
+    for x in adds {
+        if let Ok(h) = trial.make_set(x, ()) {
+            handles.push((x, h));
+        }
+        let _ = oracle.make_set(x);
+    }
+    for (x, y) in connects {
+        if let (Some(hx), Some(hy)) = (trial.handle(&x), trial.handle(&y)) {
+            trial.unite(hx, hy).unwrap();
+        }
+        let _ = oracle.unite(x, y);
+    }
+    let mut removed = Vec::new();
+    for x in removes {
+        if let Some(h) = trial.handle(&x) {
+            let (_, keys) = trial.remove_set(h).unwrap();
+            removed.extend(keys);
+        }
+    }
+    // reuses slots of removed elements, which must not revive their handles
+    for x in removed.iter() {
+        trial.make_set(*x, ()).unwrap();
+    }
+    for (x, h) in handles {
+        if removed.contains(&x) {
+            assert!(trial.find(h).is_none());
+            assert!(trial.key(h).is_none());
+        } else {
+            let mut trial_set: Vec<_> = trial.find(h).unwrap().iter().copied().collect();
+            trial_set.sort();
+            let mut oracle_set = oracle.find(&x).unwrap();
+            oracle_set.sort();
+            assert_eq!(trial_set, oracle_set);
+            let rep = trial.representative(h).unwrap();
+            assert_eq!(trial.key(rep), Some(trial.find(h).unwrap().key()));
+        }
+    }
+}
@@ -0,0 +1,199 @@
+#[cfg(feature = "alloc")]
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+#[cfg(feature = "alloc")]
+use core::mem::size_of;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+/// Reports the number of bytes on heap owned by a value,
+/// which excludes the value itself (i.e., `size_of_val(self)`).
+///
+/// Keys and tags must implement it to have the footprint of whole sets reported,
+/// see [UnionFindSets::heap_size](crate::UnionFindSets::heap_size).
+/// Sizes of std collections are estimated from their capacities.
+pub trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+macro_rules! no_heap {
+    ($($t:ty),*) => {
+        $(
+            impl HeapSize for $t {
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+no_heap!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    &'static str
+);
+
+#[cfg(feature = "alloc")]
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: HeapSize> HeapSize for Box<T> {
+    fn heap_size(&self) -> usize {
+        size_of::<T>() + self.as_ref().heap_size()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl HeapSize for Box<str> {
+    fn heap_size(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Shared values are not counted, as they are not owned exclusively.
+impl<T: ?Sized> HeapSize for Rc<T> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Shared values are not counted, as they are not owned exclusively.
+impl<T: ?Sized> HeapSize for Arc<T> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+macro_rules! wrapper_heap {
+    ($($t:ident),*) => {
+        $(
+            impl<T: HeapSize> HeapSize for crate::$t<T> {
+                fn heap_size(&self) -> usize {
+                    self.0.heap_size()
+                }
+            }
+        )*
+    };
+}
+
+wrapper_heap!(Sum, ByAdd, Min, Max);
+no_heap!(crate::Count);
+
+/// Whatever captured by the closure is not counted.
+impl<T: HeapSize, F> HeapSize for crate::MergeWith<T, F> {
+    fn heap_size(&self) -> usize {
+        self.value().heap_size()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: HeapSize, V: HeapSize, S> HeapSize for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        hash_table_size::<(K, V)>(self.capacity())
+            + self
+                .iter()
+                .map(|(k, v)| k.heap_size() + v.heap_size())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: HeapSize, S> HeapSize for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        hash_table_size::<T>(self.capacity()) + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: HeapSize, V: HeapSize, S> HeapSize for crate::MergeMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: HeapSize, V: HeapSize> HeapSize for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        self.len() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(k, v)| k.heap_size() + v.heap_size())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: HeapSize> HeapSize for BTreeSet<T> {
+    fn heap_size(&self) -> usize {
+        self.len() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+macro_rules! tuple_heap {
+    ($($t:ident),*) => {
+        impl<$($t: HeapSize),*> HeapSize for ($($t,)*) {
+            #[allow(non_snake_case)]
+            fn heap_size(&self) -> usize {
+                let ($($t,)*) = self;
+                0 $(+ $t.heap_size())*
+            }
+        }
+    };
+}
+
+tuple_heap!(A);
+tuple_heap!(A, B);
+tuple_heap!(A, B, C);
+tuple_heap!(A, B, C, D);
+
+#[cfg(feature = "alloc")]
+/// Estimates the allocation of a swiss table with `capacity`,
+/// i.e., a slot plus a control byte per bucket.
+pub(crate) fn hash_table_size<T>(capacity: usize) -> usize {
+    if capacity == 0 {
+        return 0;
+    }
+    let buckets = (capacity * 8 / 7).next_power_of_two();
+    buckets * (size_of::<T>() + 1)
+}
@@ -0,0 +1,110 @@
+//! Counters of operations, shared by all sets in the process.
+//!
+//! Hooks are always called, but they are empty and inlined away
+//! unless the feature `instrumentation` or `metrics` is on.
+//!
+//! With `metrics`, operations are also reported through the [metrics](https://docs.rs/metrics) facade:
+//! - `tagged_ufs_unions_total` and `tagged_ufs_redundant_unions_total`, counters of unions;
+//! - `tagged_ufs_compressed_pointers_total`, a counter of parent pointers rewritten by finds;
+//! - `tagged_ufs_find_path_length`, a histogram of links walked by every find;
+//! - `tagged_ufs_sets`, a gauge of the number of sets, labelled by `name`,
+//!   which is only reported by [record_set_count](crate::record_set_count) for the sets the caller names.
+
+#[cfg(feature = "instrumentation")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Numbers of operations counted since start-up or the last [reset_metrics].
+#[cfg(feature = "instrumentation")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Metrics {
+    /// Walks from a key up to its root, including those made on behalf of unions.
+    pub finds: u64,
+    /// Unions which really merged two sets.
+    pub unions: u64,
+    /// Unions of two keys which were already in a same set.
+    pub redundant_unions: u64,
+    /// Parent pointers rewritten by path compression.
+    pub compressed_pointers: u64,
+}
+
+#[cfg(feature = "instrumentation")]
+static FINDS: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "instrumentation")]
+static UNIONS: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "instrumentation")]
+static REDUNDANT_UNIONS: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "instrumentation")]
+static COMPRESSED_POINTERS: AtomicU64 = AtomicU64::new(0);
+
+/// Reads all counters.
+///
+/// Counters are read one by one, so they may be slightly out of step with each other
+/// while other threads are busy.
+#[cfg(feature = "instrumentation")]
+pub fn metrics_snapshot() -> Metrics {
+    Metrics {
+        finds: FINDS.load(Ordering::Relaxed),
+        unions: UNIONS.load(Ordering::Relaxed),
+        redundant_unions: REDUNDANT_UNIONS.load(Ordering::Relaxed),
+        compressed_pointers: COMPRESSED_POINTERS.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets all counters to zero.
+#[cfg(feature = "instrumentation")]
+pub fn reset_metrics() {
+    FINDS.store(0, Ordering::Relaxed);
+    UNIONS.store(0, Ordering::Relaxed);
+    REDUNDANT_UNIONS.store(0, Ordering::Relaxed);
+    COMPRESSED_POINTERS.store(0, Ordering::Relaxed);
+}
+
+/// Counts a find which walked `path_len` links up to the root.
+#[inline(always)]
+pub(crate) fn count_find(path_len: usize) {
+    #[cfg(feature = "instrumentation")]
+    FINDS.fetch_add(1, Ordering::Relaxed);
+    #[cfg(feature = "metrics")]
+    ::metrics::histogram!("tagged_ufs_find_path_length").record(path_len as f64);
+    #[cfg(not(feature = "metrics"))]
+    let _ = path_len;
+}
+
+#[inline(always)]
+pub(crate) fn count_union(merged: bool) {
+    #[cfg(feature = "instrumentation")]
+    if merged {
+        UNIONS.fetch_add(1, Ordering::Relaxed);
+    } else {
+        REDUNDANT_UNIONS.fetch_add(1, Ordering::Relaxed);
+    }
+    #[cfg(feature = "metrics")]
+    if merged {
+        ::metrics::counter!("tagged_ufs_unions_total").increment(1);
+    } else {
+        ::metrics::counter!("tagged_ufs_redundant_unions_total").increment(1);
+    }
+    #[cfg(not(any(feature = "instrumentation", feature = "metrics")))]
+    let _ = merged;
+}
+
+#[inline(always)]
+pub(crate) fn count_compressed_pointer() {
+    #[cfg(feature = "instrumentation")]
+    COMPRESSED_POINTERS.fetch_add(1, Ordering::Relaxed);
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!("tagged_ufs_compressed_pointers_total").increment(1);
+}
+
+/// Reports `sets`, the number of sets in some union-find sets, as the gauge `tagged_ufs_sets`
+/// labelled by `name`.
+///
+/// Every instance should be given its own name, e.g., `record_set_count("users", users.len())`,
+/// as sets of a same name share one gauge.
+#[cfg(feature = "metrics")]
+pub fn record_set_count<N>(name: N, sets: usize)
+where
+    N: Into<::metrics::SharedString>,
+{
+    ::metrics::gauge!("tagged_ufs_sets", "name" => name.into()).set(sets as f64);
+}
@@ -0,0 +1,85 @@
+use core::hash::{BuildHasher, Hasher};
+
+/// `2^64` divided by the golden ratio, an odd constant with well mixed bits.
+///
+/// Multiplying by it spreads low bits of integers into high bits,
+/// but never high bits into low ones, so the high half is folded back by `finish`.
+const SPREAD: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// A hasher which takes integers almost as they are.
+///
+/// It is meant for keys of primitive integers, which are written as a single word.
+/// Anything else is still hashed, only poorly, word by word.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntHasher {
+    state: u64,
+}
+
+impl Hasher for IntHasher {
+    fn finish(&self) -> u64 {
+        // hash tables pick buckets by low bits, which would otherwise only depend on
+        // low bits of the integer, piling up strided ids such as `i << 32`
+        let h = self.state.wrapping_mul(SPREAD);
+        h ^ (h >> 32)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, x: u8) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_u16(&mut self, x: u16) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_u32(&mut self, x: u32) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_u64(&mut self, x: u64) {
+        self.state = self.state.rotate_left(5) ^ x;
+    }
+
+    fn write_usize(&mut self, x: usize) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_i8(&mut self, x: i8) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_i16(&mut self, x: i16) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_i32(&mut self, x: i32) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_i64(&mut self, x: i64) {
+        self.write_u64(x as u64);
+    }
+
+    fn write_isize(&mut self, x: isize) {
+        self.write_u64(x as u64);
+    }
+}
+
+/// Builds [IntHasher]s, to be passed to `with_hasher` of sets.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuildIntHasher;
+
+impl BuildHasher for BuildIntHasher {
+    type Hasher = IntHasher;
+
+    fn build_hasher(&self) -> IntHasher {
+        IntHasher::default()
+    }
+}
@@ -0,0 +1,13 @@
+//! A hasher for integer keys, which are usually well distributed already.
+//!
+//! Ids from a database or a counter need no real hashing,
+//! yet they may be too sparse for indices into a flat array.
+//! An integer is only multiplied by an odd constant, with the high half folded into the low one,
+//! so that both the low bits, which hash tables pick buckets by,
+//! and the high bits, which they tell keys apart by, depend on the whole integer.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
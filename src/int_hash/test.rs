@@ -0,0 +1,47 @@
+use super::*;
+use core::hash::BuildHasher;
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn distinct_words_hash_distinctly(x: u64, y: u64) -> bool {
+    let hasher = BuildIntHasher;
+    (x == y) == (hasher.hash_one(x) == hasher.hash_one(y))
+}
+
+#[test]
+fn spread_sequential_ids() {
+    let hasher = BuildIntHasher;
+    // hash tables tell keys apart by the top 7 bits first
+    let tops: std::collections::HashSet<_> =
+        (0..1000u32).map(|x| hasher.hash_one(x) >> 57).collect();
+    assert!(tops.len() > 100);
+    assert_eq!(hasher.hash_one(7u32), hasher.hash_one(7u64));
+}
+
+#[test]
+fn other_keys() {
+    let mut sets = crate::compact::UnionFindSets::with_hasher(BuildIntHasher);
+    for x in ["apple", "banana", "cherry"] {
+        sets.make_set(x.to_string(), ()).unwrap();
+    }
+    assert!(sets.unite("apple", "cherry").unwrap());
+    assert_eq!(sets.len(), 2);
+    assert!(sets.find("banana").is_some());
+}
+
+#[test]
+fn spread_strided_ids() {
+    let hasher = BuildIntHasher;
+    // hash tables pick buckets by low bits
+    let buckets: std::collections::HashSet<_> = (0..1024u64)
+        .map(|x| hasher.hash_one(x << 32) & 1023)
+        .collect();
+    assert!(buckets.len() > 512);
+
+    let mut sets = crate::compact::UnionFindSets::with_hasher(BuildIntHasher);
+    for x in 0..20_000u64 {
+        sets.make_set(x << 32, ()).unwrap();
+    }
+    assert!(sets.unite(&(1 << 32), &(2 << 32)).unwrap());
+    assert_eq!(sets.len(), 19_999);
+}
@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+use core::cell::Cell;
+
+/// Slots `0..n`, each of which is either free or used.
+///
+/// Finding the next free slot at or after any slot takes near-constant amortized time.
+#[derive(Debug, Clone)]
+pub struct NextFree {
+    /// `next[i] == i` iff slot `i` is free.
+    /// The extra slot `n` is always free, standing for "none".
+    next: Vec<Cell<usize>>,
+    used: usize,
+}
+
+impl NextFree {
+    /// Makes `n` free slots.
+    pub fn new(n: usize) -> Self {
+        Self {
+            next: (0..=n).map(Cell::new).collect(),
+            used: 0,
+        }
+    }
+
+    /// Queries the number of slots.
+    pub fn capacity(&self) -> usize {
+        self.next.len() - 1
+    }
+
+    /// Queries the number of used slots.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Tests if a slot is free.
+    ///
+    /// Slots out of `0..n` are never free.
+    pub fn is_free(&self, i: usize) -> bool {
+        i < self.capacity() && self.next[i].get() == i
+    }
+
+    /// Finds the first free slot at or after `i`.
+    ///
+    /// If there is none, `None` will be returned.
+    pub fn next_free(&self, i: usize) -> Option<usize> {
+        if i >= self.capacity() {
+            return None;
+        }
+        let mut top = i;
+        while self.next[top].get() != top {
+            top = self.next[top].get();
+        }
+        let mut cur = i;
+        while cur != top {
+            cur = self.next[cur].replace(top);
+        }
+        Some(top).filter(|x| *x < self.capacity())
+    }
+
+    /// Marks a slot as used.
+    ///
+    /// If it was free, `true` will be returned;
+    /// if it was already used or out of `0..n`, `false` will be returned.
+    pub fn mark_used(&mut self, i: usize) -> bool {
+        if !self.is_free(i) {
+            return false;
+        }
+        self.next[i].set(i + 1);
+        self.used += 1;
+        true
+    }
+
+    /// Takes the first free slot at or after `i`, and marks it as used.
+    ///
+    /// If there is none, `None` will be returned.
+    pub fn allocate_at_or_after(&mut self, i: usize) -> Option<usize> {
+        let slot = self.next_free(i)?;
+        self.mark_used(slot);
+        Some(slot)
+    }
+}
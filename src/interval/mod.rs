@@ -0,0 +1,10 @@
+//! Allocation of free slots over `0..n`, by union-find sets of used runs.
+//!
+//! Every used slot points at the one after it, so the root of a slot is the next free one,
+//! and paths are compressed as with any union-find sets.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
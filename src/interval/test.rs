@@ -0,0 +1,44 @@
+use super::*;
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn allocate_like_linear_scan(n: u8, ops: Vec<(bool, u8)>) {
+    let n = n as usize;
+    let mut trial = NextFree::new(n);
+    let mut oracle = vec![false; n];
+    for (allocate, i) in ops {
+        let i = i as usize;
+        if allocate {
+            let expected = (i..n).find(|x| !oracle[*x]);
+            if let Some(x) = expected {
+                oracle[x] = true;
+            }
+            assert_eq!(trial.allocate_at_or_after(i), expected);
+        } else {
+            let expected = i < n && !oracle[i];
+            if expected {
+                oracle[i] = true;
+            }
+            assert_eq!(trial.mark_used(i), expected);
+        }
+        assert_eq!(trial.used(), oracle.iter().filter(|x| **x).count());
+    }
+    for (i, used) in oracle.iter().enumerate() {
+        assert_eq!(trial.is_free(i), !used);
+    }
+    assert!(!trial.is_free(n));
+}
+
+#[test]
+fn schedule_jobs_by_deadline() {
+    let mut slots = NextFree::new(4);
+    assert_eq!(slots.capacity(), 4);
+    assert_eq!(slots.allocate_at_or_after(1), Some(1));
+    assert_eq!(slots.allocate_at_or_after(1), Some(2));
+    assert!(slots.mark_used(3));
+    assert_eq!(slots.allocate_at_or_after(1), None);
+    assert_eq!(slots.next_free(0), Some(0));
+    assert_eq!(slots.allocate_at_or_after(0), Some(0));
+    assert_eq!(slots.next_free(0), None);
+    assert_eq!(slots.allocate_at_or_after(7), None);
+}
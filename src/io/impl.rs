@@ -0,0 +1,170 @@
+use crate::UnionFindSets;
+use core::fmt::Display;
+use std::io::{self, BufRead, ErrorKind, Write};
+
+/// Formats of written components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Lines of `element<TAB>component`.
+    Tsv,
+    /// Lines of `element,component`, quoting elements as needed.
+    Csv,
+    /// A line of a JSON array of elements for every component.
+    JsonLines,
+}
+
+/// Reads an edge list into sets of its elements.
+///
+/// Every line is an edge of two fields separated by `delimiter`, or a single isolated element;
+/// further fields are ignored.
+/// Fields may be quoted by `"`, doubling quotes in them, and unquoted ones are trimmed.
+/// A leading BOM, `\r` before line breaks, blank lines and lines starting with `#` are skipped.
+/// If a quote is not closed, [ErrorKind::InvalidData] will be raised.
+pub fn read_edge_list<R: BufRead>(
+    reader: R,
+    delimiter: char,
+) -> io::Result<UnionFindSets<String, ()>> {
+    let mut sets = UnionFindSets::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = if i == 0 {
+            line.trim_start_matches('\u{feff}')
+        } else {
+            &line
+        };
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = split_fields(line, delimiter)?.into_iter();
+        let Some(x) = fields.next() else {
+            continue;
+        };
+        let _ = sets.make_set(x.clone(), ());
+        if let Some(y) = fields.next() {
+            let _ = sets.make_set(y.clone(), ());
+            sets.unite(&x, &y)
+                .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+        }
+    }
+    Ok(sets)
+}
+
+/// Writes components, numbered from 0 in the given order.
+///
+/// As TSV cannot quote, if an element contains a tab or a line break in it,
+/// [ErrorKind::InvalidInput] will be raised.
+pub fn write_components<W, I, C, K>(mut writer: W, components: I, format: Format) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = C>,
+    C: IntoIterator<Item = K>,
+    K: Display,
+{
+    for (i, xs) in components.into_iter().enumerate() {
+        match format {
+            Format::Tsv => {
+                for x in xs {
+                    let x = x.to_string();
+                    if x.contains(['\t', '\n', '\r']) {
+                        return Err(io::Error::new(
+                            ErrorKind::InvalidInput,
+                            "TSV cannot hold tabs or line breaks in elements",
+                        ));
+                    }
+                    writeln!(writer, "{}\t{}", x, i)?;
+                }
+            }
+            Format::Csv => {
+                for x in xs {
+                    write_csv_field(&mut writer, &x.to_string())?;
+                    writeln!(writer, ",{}", i)?;
+                }
+            }
+            Format::JsonLines => {
+                writer.write_all(b"[")?;
+                for (j, x) in xs.into_iter().enumerate() {
+                    if j > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    write_json_string(&mut writer, &x.to_string())?;
+                }
+                writer.write_all(b"]\n")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn split_fields(line: &str, delimiter: char) -> io::Result<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut rest = line;
+    loop {
+        let trimmed = if delimiter == ' ' {
+            rest
+        } else {
+            rest.trim_start_matches(' ')
+        };
+        if let Some(quoted) = trimmed.strip_prefix('"') {
+            let mut field = String::new();
+            let mut chars = quoted.char_indices();
+            let after = loop {
+                match chars.next() {
+                    Some((j, '"')) => {
+                        if quoted[j + 1..].starts_with('"') {
+                            field.push('"');
+                            chars.next();
+                        } else {
+                            break &quoted[j + 1..];
+                        }
+                    }
+                    Some((_, c)) => field.push(c),
+                    None => {
+                        return Err(io::Error::new(ErrorKind::InvalidData, "unclosed quote"));
+                    }
+                }
+            };
+            fields.push(field);
+            match after.find(delimiter) {
+                Some(j) => rest = &after[j + delimiter.len_utf8()..],
+                None => return Ok(fields),
+            }
+        } else {
+            match rest.find(delimiter) {
+                Some(j) => {
+                    fields.push(rest[..j].trim().to_string());
+                    rest = &rest[j + delimiter.len_utf8()..];
+                }
+                None => {
+                    fields.push(rest.trim().to_string());
+                    return Ok(fields);
+                }
+            }
+        }
+    }
+}
+
+fn write_csv_field<W: Write>(writer: &mut W, x: &str) -> io::Result<()> {
+    let needs_quotes = x.contains([',', '"', '\n', '\r']) || x.trim() != x || x.starts_with('#');
+    if needs_quotes {
+        write!(writer, "\"{}\"", x.replace('"', "\"\""))
+    } else {
+        writer.write_all(x.as_bytes())
+    }
+}
+
+fn write_json_string<W: Write>(writer: &mut W, x: &str) -> io::Result<()> {
+    writer.write_all(b"\"")?;
+    for c in x.chars() {
+        match c {
+            '"' => writer.write_all(b"\\\"")?,
+            '\\' => writer.write_all(b"\\\\")?,
+            '\n' => writer.write_all(b"\\n")?,
+            '\r' => writer.write_all(b"\\r")?,
+            '\t' => writer.write_all(b"\\t")?,
+            c if (c as u32) < 0x20 => write!(writer, "\\u{:04x}", c as u32)?,
+            c => write!(writer, "{}", c)?,
+        }
+    }
+    writer.write_all(b"\"")
+}
@@ -0,0 +1,10 @@
+//! Reading edge lists and writing components, in TSV, CSV and JSON lines,
+//! or as GraphML and GEXF for graph viewers like Gephi and yEd.
+
+mod r#impl;
+pub use self::r#impl::*;
+mod xml;
+pub use self::xml::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,81 @@
+use super::*;
+
+fn sorted(sets: &crate::UnionFindSets<String, ()>) -> Vec<Vec<&str>> {
+    let mut components: Vec<Vec<&str>> = sets
+        .iter()
+        .map(|xs| xs.iter_sorted().map(String::as_str).collect())
+        .collect();
+    components.sort();
+    components
+}
+
+#[test]
+fn read_tsv() {
+    let tsv = "\u{feff}# a comment\r\nb\ta\r\nc\tb\tignored\n\n  \nd\te\nf\n";
+    let sets = read_edge_list(tsv.as_bytes(), '\t').unwrap();
+    assert_eq!(
+        sorted(&sets),
+        [vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]
+    );
+}
+
+#[test]
+fn read_quoted_csv() {
+    let csv = "\"x, y\",z\n z , \"say \"\"hi\"\"\"\n\"\",w\n";
+    let sets = read_edge_list(csv.as_bytes(), ',').unwrap();
+    assert_eq!(
+        sorted(&sets),
+        [vec!["", "w"], vec!["say \"hi\"", "x, y", "z"]]
+    );
+    assert!(read_edge_list("\"x,y\n".as_bytes(), ',').is_err());
+}
+
+#[test]
+fn write_formats() {
+    let components = [vec!["a", "b,\"c\""], vec!["#d"]];
+    let mut tsv = Vec::new();
+    write_components(&mut tsv, &components, Format::Tsv).unwrap();
+    assert_eq!(String::from_utf8(tsv).unwrap(), "a\t0\nb,\"c\"\t0\n#d\t1\n");
+    let mut csv = Vec::new();
+    write_components(&mut csv, &components, Format::Csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(csv, "a,0\n\"b,\"\"c\"\"\",0\n\"#d\",1\n");
+    let sets = read_edge_list(csv.as_bytes(), ',').unwrap();
+    assert_eq!(sorted(&sets), [vec!["#d", "1"], vec!["0", "a", "b,\"c\""]]);
+    let mut jsonl = Vec::new();
+    write_components(
+        &mut jsonl,
+        [vec!["a\tb", "\u{1}"], vec![]],
+        Format::JsonLines,
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(jsonl).unwrap(),
+        "[\"a\\tb\",\"\\u0001\"]\n[]\n"
+    );
+    assert!(write_components(Vec::new(), [["a\tb"]], Format::Tsv).is_err());
+}
+
+#[test]
+fn write_graph_files() {
+    let mut sets = crate::UnionFindSets::new();
+    for x in ["a&b", "c", "d"] {
+        sets.make_set(x.to_string(), crate::Count(1)).unwrap();
+    }
+    sets.unite("a&b", "d").unwrap();
+    let mut graphml = Vec::new();
+    write_graphml(&mut graphml, &sets, |tag| format!("<{}>", tag.0)).unwrap();
+    let graphml = String::from_utf8(graphml).unwrap();
+    assert!(graphml.contains(
+        r#"<node id="a&amp;b"><data key="component">0</data><data key="size">2</data><data key="tag">&lt;2&gt;</data></node>"#
+    ));
+    assert!(graphml.contains(r#"<node id="c"><data key="component">1</data>"#));
+    assert_eq!(graphml.matches("<node ").count(), 3);
+    let mut gexf = Vec::new();
+    write_gexf(&mut gexf, &sets, |tag| tag.0.to_string()).unwrap();
+    let gexf = String::from_utf8(gexf).unwrap();
+    assert!(gexf.contains(
+        r#"<node id="d" label="d"><attvalues><attvalue for="0" value="0"/><attvalue for="1" value="2"/><attvalue for="2" value="2"/></attvalues></node>"#
+    ));
+    assert!(gexf.ends_with("</gexf>\n"));
+}
@@ -0,0 +1,132 @@
+use crate::{Mergable, UnionFindSets};
+use core::fmt::Display;
+use core::hash::Hash;
+use std::io::{self, Write};
+
+/// Writes elements as nodes of a [GraphML](http://graphml.graphdrawing.org) graph without edges,
+/// with their component ids, component sizes and tags stringified by `tag_of` as node data.
+///
+/// Components are numbered from 0 in order of their least elements.
+pub fn write_graphml<W, Key, Tag, F>(
+    mut writer: W,
+    sets: &UnionFindSets<Key, Tag>,
+    tag_of: F,
+) -> io::Result<()>
+where
+    W: Write,
+    Key: Eq + Hash + Clone + Ord + Display,
+    Tag: Mergable,
+    F: FnMut(&Tag) -> String,
+{
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    for (id, ty) in [("component", "long"), ("size", "long"), ("tag", "string")] {
+        writeln!(
+            writer,
+            r#"  <key id="{id}" for="node" attr.name="{id}" attr.type="{ty}"/>"#
+        )?;
+    }
+    writeln!(
+        writer,
+        r#"  <graph id="components" edgedefault="undirected">"#
+    )?;
+    for_each_node(sets, tag_of, |x, component, size, tag| {
+        writeln!(
+            writer,
+            r#"    <node id="{}"><data key="component">{}</data><data key="size">{}</data><data key="tag">{}</data></node>"#,
+            x, component, size, tag
+        )
+    })?;
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")
+}
+
+/// Writes elements as nodes of a [GEXF](https://gexf.net) graph without edges,
+/// with their component ids, component sizes and tags stringified by `tag_of` as node attributes.
+///
+/// Components are numbered from 0 in order of their least elements.
+pub fn write_gexf<W, Key, Tag, F>(
+    mut writer: W,
+    sets: &UnionFindSets<Key, Tag>,
+    tag_of: F,
+) -> io::Result<()>
+where
+    W: Write,
+    Key: Eq + Hash + Clone + Ord + Display,
+    Tag: Mergable,
+    F: FnMut(&Tag) -> String,
+{
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#
+    )?;
+    writeln!(writer, r#"  <graph defaultedgetype="undirected">"#)?;
+    writeln!(writer, r#"    <attributes class="node">"#)?;
+    for (id, (title, ty)) in [("component", "long"), ("size", "long"), ("tag", "string")]
+        .into_iter()
+        .enumerate()
+    {
+        writeln!(
+            writer,
+            r#"      <attribute id="{id}" title="{title}" type="{ty}"/>"#
+        )?;
+    }
+    writeln!(writer, "    </attributes>")?;
+    writeln!(writer, "    <nodes>")?;
+    for_each_node(sets, tag_of, |x, component, size, tag| {
+        writeln!(
+            writer,
+            r#"      <node id="{x}" label="{x}"><attvalues><attvalue for="0" value="{}"/><attvalue for="1" value="{}"/><attvalue for="2" value="{}"/></attvalues></node>"#,
+            component, size, tag
+        )
+    })?;
+    writeln!(writer, "    </nodes>")?;
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</gexf>")
+}
+
+/// Calls `f` with every escaped element, its component id, its component size and its escaped tag.
+fn for_each_node<Key, Tag, T, F>(
+    sets: &UnionFindSets<Key, Tag>,
+    mut tag_of: T,
+    mut f: F,
+) -> io::Result<()>
+where
+    Key: Eq + Hash + Clone + Ord + Display,
+    Tag: Mergable,
+    T: FnMut(&Tag) -> String,
+    F: FnMut(&str, usize, usize, &str) -> io::Result<()>,
+{
+    let mut components: Vec<_> = sets
+        .iter()
+        .map(|xs| (xs.iter_sorted().collect::<Vec<_>>(), xs.tag()))
+        .collect();
+    components.sort_unstable_by(|(xs, _), (ys, _)| xs.first().cmp(&ys.first()));
+    for (component, (xs, tag)) in components.into_iter().enumerate() {
+        let tag = escape(&tag_of(tag));
+        for x in xs.iter() {
+            f(&escape(&x.to_string()), component, xs.len(), &tag)?;
+        }
+    }
+    Ok(())
+}
+
+/// Escapes text for both contents and attributes of XML.
+fn escape(x: &str) -> String {
+    let mut escaped = String::with_capacity(x.len());
+    for c in x.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
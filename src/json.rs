@@ -0,0 +1,72 @@
+//! Human-readable JSON of sets, as groups of members.
+
+use crate::{Mergable, UnionFindSets};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use serde::{Deserialize, Serialize};
+
+/// A set as written in JSON.
+#[derive(Serialize, Deserialize)]
+struct Group<K, T> {
+    representative: K,
+    tag: T,
+    members: Vec<K>,
+}
+
+impl<Key, Tag> UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Writes sets as a pretty JSON array of `{"representative": .., "tag": .., "members": [..]}`.
+    ///
+    /// Groups are sorted by their representatives, and members are sorted, too,
+    /// so that the output is deterministic and fit for diffing.
+    pub fn to_json_groups(&self) -> serde_json::Result<String>
+    where
+        Key: Ord + Serialize,
+        Tag: Serialize,
+    {
+        let groups: Vec<Group<&Key, &Tag>> = self
+            .iter_sets_sorted_by_key()
+            .map(|xs| Group {
+                representative: xs.key(),
+                tag: xs.tag(),
+                members: xs.iter_sorted().collect(),
+            })
+            .collect();
+        serde_json::to_string_pretty(&groups)
+    }
+
+    /// Reads sets written by [to_json_groups](Self::to_json_groups), maybe edited by hand.
+    ///
+    /// The representative is taken as a member even if it is not listed,
+    /// but it does not necessarily represent the set after reading.
+    /// If a key is in more than one group, an error will be raised.
+    pub fn from_json_groups(json: &str) -> serde_json::Result<Self>
+    where
+        Key: for<'de> Deserialize<'de>,
+        Tag: Clone + for<'de> Deserialize<'de>,
+    {
+        let groups: Vec<Group<Key, Tag>> = serde_json::from_str(json)?;
+        let mut sets = Self::new();
+        for group in groups {
+            let first = group.representative;
+            let duplicate = |_| <serde_json::Error as serde::de::Error>::custom("duplicate member");
+            sets.make_set(first.clone(), group.tag.clone())
+                .map_err(duplicate)?;
+            for x in group.members {
+                if x == first {
+                    continue;
+                }
+                sets.make_set(x.clone(), group.tag.clone())
+                    .map_err(duplicate)?;
+                sets.unite(&first, &x).map_err(duplicate)?;
+            }
+            if let Some(mut set) = sets.find_mut(&first) {
+                *set.tag_mut() = group.tag;
+            }
+        }
+        Ok(sets)
+    }
+}
@@ -0,0 +1,22 @@
+use super::{label_grid, Connectivity, Region};
+use crate::{Error, Result};
+use ::image::{GrayImage, ImageBuffer, Luma};
+use alloc::vec::Vec;
+
+/// An image of labels of components, where 0 is for the background.
+pub type LabelImage = ImageBuffer<Luma<u32>, Vec<u32>>;
+
+/// Labels components of non-zero pixels of a mask, see [label_grid].
+///
+/// Every component is summarized by its area and bounding box.
+pub fn label_image(
+    mask: &GrayImage,
+    connectivity: Connectivity,
+) -> Result<(LabelImage, Vec<Region>)> {
+    let (width, height) = mask.dimensions();
+    let (labels, regions) = label_grid(width, height, connectivity, |x, y| {
+        (mask.get_pixel(x, y).0[0] != 0).then(|| Region::cell(x, y))
+    })?;
+    let labels = ImageBuffer::from_raw(width, height, labels).ok_or(Error::Inconsistent)?;
+    Ok((labels, regions))
+}
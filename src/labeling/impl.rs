@@ -0,0 +1,112 @@
+use crate::compact::UnionFindSets;
+use crate::{Mergable, Result};
+use alloc::vec;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+/// Which neighbours of a cell are connected to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Left, right, up and down.
+    Four,
+    /// Diagonal ones as well.
+    Eight,
+}
+
+/// Area and bounding box of a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub area: usize,
+    pub min_x: u32,
+    pub min_y: u32,
+    pub max_x: u32,
+    pub max_y: u32,
+}
+
+impl Region {
+    /// Makes the region of a single cell.
+    pub fn cell(x: u32, y: u32) -> Self {
+        Self {
+            area: 1,
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        }
+    }
+}
+
+impl Mergable for Region {
+    fn merge(&mut self, other: Self) {
+        self.area += other.area;
+        self.min_x = self.min_x.min(other.min_x);
+        self.min_y = self.min_y.min(other.min_y);
+        self.max_x = self.max_x.max(other.max_x);
+        self.max_y = self.max_y.max(other.max_y);
+    }
+}
+
+/// Labels components of a `width` by `height` grid in row-major order.
+///
+/// `tag_of(x, y)` tells the tag of a foreground cell, or `None` for a background one.
+/// Labels of background cells are 0, and components are labeled from 1
+/// in the order their first cells are met,
+/// so the tag of component `i` is the `i - 1`-th of the returned tags.
+///
+/// If there are too many foreground cells,
+/// [Error::TooManyKeys](crate::Error::TooManyKeys) will be raised.
+pub fn label_grid<Tag, F>(
+    width: u32,
+    height: u32,
+    connectivity: Connectivity,
+    mut tag_of: F,
+) -> Result<(Vec<u32>, Vec<Tag>)>
+where
+    Tag: Mergable + Clone,
+    F: FnMut(u32, u32) -> Option<Tag>,
+{
+    let index = |x: u32, y: u32| y as u64 * width as u64 + x as u64;
+    let mut sets = UnionFindSets::new();
+    for y in 0..height {
+        for x in 0..width {
+            let Some(tag) = tag_of(x, y) else {
+                continue;
+            };
+            sets.make_set(index(x, y), tag)?;
+            let mut neighbours = [None; 4];
+            if x > 0 {
+                neighbours[0] = Some(index(x - 1, y));
+            }
+            if y > 0 {
+                neighbours[1] = Some(index(x, y - 1));
+                if connectivity == Connectivity::Eight {
+                    if x > 0 {
+                        neighbours[2] = Some(index(x - 1, y - 1));
+                    }
+                    if x + 1 < width {
+                        neighbours[3] = Some(index(x + 1, y - 1));
+                    }
+                }
+            }
+            for neighbour in neighbours.into_iter().flatten() {
+                // background neighbours are not in the sets
+                let _ = sets.unite(&index(x, y), &neighbour);
+            }
+        }
+    }
+    let cells = width as usize * height as usize;
+    let mut labels = vec![0; cells];
+    let mut tags = vec![];
+    let mut label_of: HashMap<u64, u32, ahash::RandomState> =
+        HashMap::with_hasher(ahash::RandomState::new());
+    for (i, label) in labels.iter_mut().enumerate() {
+        let Some(set) = sets.find(&(i as u64)) else {
+            continue;
+        };
+        *label = *label_of.entry(*set.key()).or_insert_with(|| {
+            tags.push(set.tag().clone());
+            tags.len() as u32
+        });
+    }
+    Ok((labels, tags))
+}
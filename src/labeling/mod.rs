@@ -0,0 +1,18 @@
+//! Connected-component labeling of 2D grids, e.g., masks of images.
+//!
+//! Foreground cells are united with their foreground neighbours,
+//! and every component is summarized by a mergable tag, e.g., its [Region].
+
+mod r#impl;
+pub use self::r#impl::*;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "image")]
+pub use self::image::*;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+#[cfg(feature = "ndarray")]
+pub use self::ndarray::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,35 @@
+use super::{label_grid, Connectivity};
+use crate::{Error, Mergable, Result};
+use ::ndarray::{Array2, ArrayView2};
+use alloc::vec::Vec;
+
+/// Labels components of `true` cells of an array, see [label_grid].
+///
+/// Cells are indexed by `(row, column)`, i.e., `(y, x)`.
+pub fn label_array(mask: ArrayView2<'_, bool>, connectivity: Connectivity) -> Result<Array2<u32>> {
+    let (labels, _) = label_array_by(mask, connectivity, |x| x.then_some(()))?;
+    Ok(labels)
+}
+
+/// Labels components of an array, see [label_grid],
+/// where `tag_of` tells the tag of a foreground value, or `None` for a background one.
+pub fn label_array_by<T, Tag, F>(
+    values: ArrayView2<'_, T>,
+    connectivity: Connectivity,
+    mut tag_of: F,
+) -> Result<(Array2<u32>, Vec<Tag>)>
+where
+    Tag: Mergable + Clone,
+    F: FnMut(&T) -> Option<Tag>,
+{
+    let (rows, columns) = values.dim();
+    let (Ok(height), Ok(width)) = (u32::try_from(rows), u32::try_from(columns)) else {
+        return Err(Error::TooManyKeys);
+    };
+    let (labels, tags) = label_grid(width, height, connectivity, |x, y| {
+        tag_of(&values[(y as usize, x as usize)])
+    })?;
+    let labels =
+        Array2::from_shape_vec((rows, columns), labels).map_err(|_| Error::Inconsistent)?;
+    Ok((labels, tags))
+}
@@ -0,0 +1,95 @@
+use super::*;
+use crate::Count;
+
+#[test]
+fn label_with_either_connectivity() {
+    let grid = ["#..#", ".#.#", "...#", "##.."];
+    let foreground = |x: u32, y: u32| grid[y as usize].as_bytes()[x as usize] == b'#';
+
+    let (labels, tags) = label_grid(4, 4, Connectivity::Four, |x, y| {
+        foreground(x, y).then_some(Count(1))
+    })
+    .unwrap();
+    assert_eq!(labels, vec![1, 0, 0, 2, 0, 3, 0, 2, 0, 0, 0, 2, 4, 4, 0, 0]);
+    assert_eq!(tags, vec![Count(1), Count(3), Count(1), Count(2)]);
+
+    let (labels, tags) = label_grid(4, 4, Connectivity::Eight, |x, y| {
+        foreground(x, y).then(|| Region::cell(x, y))
+    })
+    .unwrap();
+    assert_eq!(labels, vec![1, 0, 0, 2, 0, 1, 0, 2, 0, 0, 0, 2, 3, 3, 0, 0]);
+    assert_eq!(tags[0].area, 2);
+    assert_eq!((tags[0].min_x, tags[0].max_x), (0, 1));
+    assert_eq!((tags[1].min_y, tags[1].max_y), (0, 2));
+    assert_eq!(
+        tags[2],
+        Region {
+            area: 2,
+            min_x: 0,
+            min_y: 3,
+            max_x: 1,
+            max_y: 3
+        }
+    );
+}
+
+#[test]
+fn empty_grid() {
+    let (labels, tags) =
+        label_grid::<Count, _>(0, 3, Connectivity::Four, |_, _| Some(Count(1))).unwrap();
+    assert!(labels.is_empty());
+    assert!(tags.is_empty());
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn label_mask_image() {
+    let mut mask = ::image::GrayImage::new(5, 3);
+    for (x, y) in [(0, 0), (1, 0), (4, 0), (4, 1), (4, 2), (2, 2)] {
+        mask.put_pixel(x, y, ::image::Luma([255]));
+    }
+    let (labels, regions) = label_image(&mask, Connectivity::Four).unwrap();
+    assert_eq!(labels.dimensions(), (5, 3));
+    assert_eq!(labels.get_pixel(1, 0).0, [1]);
+    assert_eq!(labels.get_pixel(4, 2).0, [2]);
+    assert_eq!(labels.get_pixel(2, 2).0, [3]);
+    assert_eq!(labels.get_pixel(3, 1).0, [0]);
+    assert_eq!(regions.len(), 3);
+    assert_eq!(
+        regions[1],
+        Region {
+            area: 3,
+            min_x: 4,
+            min_y: 0,
+            max_x: 4,
+            max_y: 2
+        }
+    );
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn label_arrays() {
+    use ::ndarray::array;
+
+    let mask = array![
+        [true, false, true],
+        [true, false, false],
+        [false, true, true]
+    ];
+    let labels = label_array(mask.view(), Connectivity::Four).unwrap();
+    assert_eq!(labels, array![[1, 0, 2], [1, 0, 0], [0, 3, 3]]);
+    let labels = label_array(mask.view(), Connectivity::Eight).unwrap();
+    assert_eq!(labels, array![[1, 0, 2], [1, 0, 0], [0, 1, 1]]);
+
+    let depths = array![[0.5, 0.0, 2.0], [1.5, 0.0, 0.0], [0.0, 3.0, 1.0]];
+    let (labels, volumes) = label_array_by(depths.view(), Connectivity::Four, |x: &f64| {
+        (*x > 0.0).then_some(crate::Sum(*x))
+    })
+    .unwrap();
+    assert_eq!(labels, array![[1, 0, 2], [1, 0, 0], [0, 3, 3]]);
+    assert_eq!(
+        volumes,
+        vec![crate::Sum(2.0), crate::Sum(2.0), crate::Sum(4.0)]
+    );
+}
@@ -1,9 +1,96 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub mod raw;
-pub use self::raw::Mergable;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// lets `#[derive(Mergable)]` refer to `::tagged_ufs` inside this crate, too
+extern crate self as tagged_ufs;
+
+mod error;
+pub use self::error::{Error, Result, Which};
+mod mergable;
+#[cfg(feature = "roaring")]
+pub use self::mergable::BitmapTag;
+#[cfg(feature = "std")]
+pub use self::mergable::MergeMap;
+pub use self::mergable::{ByAdd, Count, Max, Mergable, MergeWith, Min, Semilattice, Sum};
+#[cfg(feature = "derive")]
+pub use tagged_ufs_derive::Mergable;
+mod heap_size;
+#[cfg(feature = "alloc")]
+mod instrumentation;
+pub use self::heap_size::HeapSize;
+#[cfg(feature = "metrics")]
+pub use self::instrumentation::record_set_count;
+#[cfg(feature = "instrumentation")]
+pub use self::instrumentation::{metrics_snapshot, reset_metrics, Metrics};
+pub mod static_ufs;
+
+#[cfg(feature = "alloc")]
+pub mod algorithms;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "alloc")]
+pub mod canonical;
+#[cfg(feature = "alloc")]
+mod chunked;
+#[cfg(feature = "alloc")]
+pub mod compact;
+#[cfg(feature = "alloc")]
 mod prelude;
+#[cfg(feature = "alloc")]
+pub mod raw;
+#[cfg(feature = "alloc")]
 pub use self::prelude::*;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+#[cfg(feature = "alloc")]
+pub mod dedup;
+#[cfg(feature = "std")]
+pub mod external;
+#[cfg(feature = "borsh")]
+pub mod format;
+#[cfg(feature = "petgraph")]
+pub mod graph;
+#[cfg(feature = "alloc")]
+pub mod handles;
+pub mod int_hash;
+#[cfg(feature = "alloc")]
+pub mod interval;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "alloc")]
+pub mod labeling;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "alloc")]
+pub mod ops;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "alloc")]
+pub mod replica;
+#[cfg(feature = "alloc")]
+pub mod simple;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "alloc")]
+pub mod strings;
+#[cfg(feature = "std")]
+pub mod sync;
+#[cfg(feature = "alloc")]
+pub mod valued;
+#[cfg(all(feature = "borsh", feature = "std"))]
+pub mod wal;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "alloc")]
+pub mod watch;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,418 @@
+#[cfg(feature = "alloc")]
+use alloc::{
+    collections::{BTreeSet, LinkedList, VecDeque},
+    string::String,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::ops::{Add, AddAssign};
+#[cfg(feature = "std")]
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+pub trait Mergable {
+    fn merge(&mut self, other: Self);
+}
+
+/// Mergable tags whose merging is a join of a semilattice,
+/// i.e., commutative, associative and idempotent, like taking unions, minima or maxima.
+///
+/// Merging a clone of a tag into itself must change nothing,
+/// so that sets can be joined as replicas of a CRDT, by [UnionFindSets::join](crate::UnionFindSets::join).
+pub trait Semilattice: Mergable + Clone {}
+
+impl Mergable for () {
+    fn merge(&mut self, _other: Self) {}
+}
+
+impl Semilattice for () {}
+
+/// Merges `Some`s, or keeps whichever is `Some`.
+impl<T: Mergable> Mergable for Option<T> {
+    fn merge(&mut self, other: Self) {
+        match (self.as_mut(), other) {
+            (Some(x), Some(y)) => x.merge(y),
+            (None, y) => *self = y,
+            (Some(_), None) => {}
+        }
+    }
+}
+
+impl<T: Semilattice> Semilattice for Option<T> {}
+
+macro_rules! tuple_mergable {
+    ($($t:ident $i:tt),*) => {
+        /// Merges component-wise.
+        impl<$($t: Mergable),*> Mergable for ($($t,)*) {
+            fn merge(&mut self, other: Self) {
+                $(self.$i.merge(other.$i);)*
+            }
+        }
+
+        impl<$($t: Semilattice),*> Semilattice for ($($t,)*) {}
+    };
+}
+
+tuple_mergable!(A 0);
+tuple_mergable!(A 0, B 1);
+tuple_mergable!(A 0, B 1, C 2);
+tuple_mergable!(A 0, B 1, C 2, D 3);
+tuple_mergable!(A 0, B 1, C 2, D 3, E 4);
+tuple_mergable!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_mergable!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_mergable!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple_mergable!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple_mergable!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple_mergable!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple_mergable!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Appends elements of `other`.
+#[cfg(feature = "alloc")]
+impl<T> Mergable for Vec<T> {
+    fn merge(&mut self, mut other: Self) {
+        self.append(&mut other);
+    }
+}
+
+/// Appends elements of `other`.
+#[cfg(feature = "alloc")]
+impl<T> Mergable for VecDeque<T> {
+    fn merge(&mut self, mut other: Self) {
+        self.append(&mut other);
+    }
+}
+
+/// Appends elements of `other`.
+#[cfg(feature = "alloc")]
+impl<T> Mergable for LinkedList<T> {
+    fn merge(&mut self, mut other: Self) {
+        self.append(&mut other);
+    }
+}
+
+/// Appends `other`.
+#[cfg(feature = "alloc")]
+impl Mergable for String {
+    fn merge(&mut self, other: Self) {
+        self.push_str(&other);
+    }
+}
+
+/// Takes the union, by inserting elements of the smaller one into the larger one.
+#[cfg(feature = "alloc")]
+impl<T: Ord> Mergable for BTreeSet<T> {
+    fn merge(&mut self, mut other: Self) {
+        if other.len() > self.len() {
+            core::mem::swap(self, &mut other);
+        }
+        self.extend(other);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord + Clone> Semilattice for BTreeSet<T> {}
+
+/// Takes the union, by inserting elements of the smaller one into the larger one.
+#[cfg(feature = "std")]
+impl<T: Eq + Hash, S: BuildHasher> Mergable for HashSet<T, S> {
+    fn merge(&mut self, mut other: Self) {
+        if other.len() > self.len() {
+            core::mem::swap(self, &mut other);
+        }
+        self.extend(other);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash + Clone, S: BuildHasher + Clone> Semilattice for HashSet<T, S> {}
+
+/// A map merging values of common keys, and keeping all others.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct MergeMap<K, V, S = std::hash::RandomState>(pub HashMap<K, V, S>);
+
+#[cfg(feature = "std")]
+impl<K, V, S: Default> Default for MergeMap<K, V, S> {
+    fn default() -> Self {
+        Self(HashMap::default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V: PartialEq, S: BuildHasher> PartialEq for MergeMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V: Eq, S: BuildHasher> Eq for MergeMap<K, V, S> {}
+
+#[cfg(feature = "std")]
+impl<K, V, S> core::ops::Deref for MergeMap<K, V, S> {
+    type Target = HashMap<K, V, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> core::ops::DerefMut for MergeMap<K, V, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> From<HashMap<K, V, S>> for MergeMap<K, V, S> {
+    fn from(map: HashMap<K, V, S>) -> Self {
+        Self(map)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V, S: BuildHasher + Default> FromIterator<(K, V)> for MergeMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(HashMap::from_iter(iter))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V: Mergable, S: BuildHasher> Mergable for MergeMap<K, V, S> {
+    fn merge(&mut self, other: Self) {
+        for (k, v) in other.0 {
+            match self.0.entry(k) {
+                Entry::Occupied(mut entry) => entry.get_mut().merge(v),
+                Entry::Vacant(entry) => {
+                    entry.insert(v);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash + Clone, V: Semilattice, S: BuildHasher + Clone> Semilattice
+    for MergeMap<K, V, S>
+{
+}
+
+/// A value merged by a closure, for one-off policies without a dedicated type.
+///
+/// The closure of the absorbing tag is called, and that of the absorbed one is dropped.
+///
+/// ```
+/// use tagged_ufs::*;
+///
+/// let mut sets = raw::UnionFindSets::new();
+/// for x in 0..3 {
+///     let tag = MergeWith::new(x, |x: &mut i32, y| *x = (*x).max(y) * 10);
+///     sets.make_set(x, tag).unwrap();
+/// }
+/// sets.unite(&0, &1).unwrap();
+/// assert_eq!(*sets.find(&0).unwrap().tag().value(), 10);
+/// ```
+#[derive(Clone, Copy)]
+pub struct MergeWith<T, F> {
+    value: T,
+    f: F,
+}
+
+impl<T, F> MergeWith<T, F>
+where
+    F: FnMut(&mut T, T),
+{
+    /// Makes a value, which will be merged by `f`.
+    pub fn new(value: T, f: F) -> Self {
+        Self { value, f }
+    }
+}
+
+impl<T, F> MergeWith<T, F> {
+    /// Gets the value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Gets the value mutably.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Takes the value, dropping the closure.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: core::fmt::Debug, F> core::fmt::Debug for MergeWith<T, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("MergeWith").field(&self.value).finish()
+    }
+}
+
+impl<T, F> Mergable for MergeWith<T, F>
+where
+    F: FnMut(&mut T, T),
+{
+    fn merge(&mut self, other: Self) {
+        (self.f)(&mut self.value, other.value);
+    }
+}
+
+/// Sums up values of merged tags.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Sum<T>(pub T);
+
+impl<T: AddAssign> Mergable for Sum<T> {
+    fn merge(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+/// Merges values by their own `Add`, e.g., of a semigroup or a monoid,
+/// so that no more `Mergable` impls are needed.
+///
+/// Unlike [Sum], which adds in place by `AddAssign`,
+/// the value is moved out by `Default` and then added.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct ByAdd<T>(pub T);
+
+impl<T: Add<Output = T> + Default> Mergable for ByAdd<T> {
+    fn merge(&mut self, other: Self) {
+        let x = core::mem::take(&mut self.0);
+        self.0 = x + other.0;
+    }
+}
+
+/// Keeps the least value of merged tags.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Min<T>(pub T);
+
+impl<T: PartialOrd> Mergable for Min<T> {
+    fn merge(&mut self, other: Self) {
+        if other.0 < self.0 {
+            *self = other;
+        }
+    }
+}
+
+impl<T: Ord + Clone> Semilattice for Min<T> {}
+
+/// Keeps the greatest value of merged tags.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Max<T>(pub T);
+
+impl<T: PartialOrd> Mergable for Max<T> {
+    fn merge(&mut self, other: Self) {
+        if other.0 > self.0 {
+            *self = other;
+        }
+    }
+}
+
+impl<T: Ord + Clone> Semilattice for Max<T> {}
+
+/// Counts whatever is counted by tags of singletons, e.g., marked elements.
+///
+/// Unlike the size of a set, not every element has to count as one.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Count(pub usize);
+
+impl Mergable for Count {
+    fn merge(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+/// Collects ids of elements into a [RoaringBitmap](roaring::RoaringBitmap), merged by union,
+/// so that sets can be compared with external lists of ids fast.
+///
+/// ```
+/// use roaring::RoaringBitmap;
+/// use tagged_ufs::{BitmapTag, UnionFindSets};
+///
+/// let mut sets = UnionFindSets::new();
+/// for x in 0..4u32 {
+///     sets.make_set(x, BitmapTag::new(x)).unwrap();
+/// }
+/// sets.unite(&0, &1).unwrap();
+/// sets.unite(&1, &2).unwrap();
+/// let external: RoaringBitmap = [1, 2, 3].into_iter().collect();
+/// assert_eq!(sets.tag_of(&0).unwrap().intersection_size(&external), 2);
+/// assert_eq!(sets.tag_of(&3).unwrap().intersection_size(&external), 1);
+/// ```
+#[cfg(feature = "roaring")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BitmapTag(pub roaring::RoaringBitmap);
+
+#[cfg(feature = "roaring")]
+impl BitmapTag {
+    /// Makes the tag of a singleton of the element with `id`.
+    pub fn new(id: u32) -> Self {
+        let mut ids = roaring::RoaringBitmap::new();
+        ids.insert(id);
+        Self(ids)
+    }
+
+    /// Counts ids both in the set and in `external`, without materializing their intersection.
+    pub fn intersection_size(&self, external: &roaring::RoaringBitmap) -> u64 {
+        self.0.intersection_len(external)
+    }
+
+    /// Tests whether the set has an element with `id`.
+    pub fn contains(&self, id: u32) -> bool {
+        self.0.contains(id)
+    }
+
+    /// Counts ids in the set.
+    pub fn len(&self) -> u64 {
+        self.0.len()
+    }
+
+    /// Tests whether there is no id in the set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(feature = "roaring")]
+impl Mergable for BitmapTag {
+    fn merge(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+#[cfg(feature = "roaring")]
+impl Semilattice for BitmapTag {}
@@ -0,0 +1,133 @@
+use crate::raw::{Allocator, UnionFindSets};
+use crate::{Error, Mergable};
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"TUFSMMAP";
+const HEADER_LEN: usize = 16;
+const ENTRY_LEN: usize = 8;
+
+/// Union-find sets frozen into a file and mapped into memory.
+///
+/// The file holds a `(key, representative)` pair for every key, sorted by keys,
+/// so both [find](Self::find) and [same_set](Self::same_set) are binary searches over the mapping.
+/// Its size is proportional to the number of keys, however sparse the keys are.
+pub struct MmapUnionFind {
+    map: Mmap,
+}
+
+impl MmapUnionFind {
+    /// Freezes `sets` and writes it to `writer`.
+    pub fn save<Tag, A, W>(sets: &UnionFindSets<u32, Tag, A>, writer: &mut W) -> io::Result<()>
+    where
+        Tag: Mergable,
+        A: Allocator,
+        W: Write,
+    {
+        let mut entries = Vec::with_capacity(sets.nodes.len());
+        for key in sets.nodes.keys() {
+            let Some(root) = sets.find(key) else {
+                return Err(io::Error::other(Error::Inconsistent));
+            };
+            entries.push((*key, *root.key()));
+        }
+        entries.sort_unstable();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(entries.len() as u64).to_le_bytes())?;
+        for (key, root) in entries {
+            writer.write_all(&key.to_le_bytes())?;
+            writer.write_all(&root.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Maps a file previously written by [save](Self::save).
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only.
+        // Like every memory-mapped file, it must not be truncated or rewritten while mapped.
+        let map = unsafe { Mmap::map(&file)? };
+        Self::from_mmap(map)
+    }
+
+    /// Checks and wraps an existing mapping of a frozen file.
+    ///
+    /// Keys are checked to be strictly increasing,
+    /// which costs a single pass over the mapping.
+    pub fn from_mmap(map: Mmap) -> io::Result<Self> {
+        if map.len() < HEADER_LEN || &map[..MAGIC.len()] != MAGIC {
+            return Err(invalid_data("not a frozen union-find file"));
+        }
+        let Some(n) = map[MAGIC.len()..].first_chunk() else {
+            return Err(invalid_data("not a frozen union-find file"));
+        };
+        let n = u64::from_le_bytes(*n);
+        let body_len = n
+            .checked_mul(ENTRY_LEN as u64)
+            .and_then(|x| usize::try_from(x).ok())
+            .ok_or_else(|| invalid_data("too many keys"))?;
+        if map.len() - HEADER_LEN != body_len {
+            return Err(invalid_data(
+                "truncated or oversized frozen union-find file",
+            ));
+        }
+        let res = Self { map };
+        let sorted = (1..res.len()).all(|i| res.entry(i - 1).0 < res.entry(i).0);
+        if !sorted {
+            return Err(invalid_data("keys are not sorted"));
+        }
+        Ok(res)
+    }
+
+    /// Queries the number of keys.
+    pub fn len(&self) -> usize {
+        (self.map.len() - HEADER_LEN) / ENTRY_LEN
+    }
+
+    /// Tests if there are no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Finds the representative of the set containing `key`.
+    ///
+    /// If `key` is not in any set, `None` will be returned.
+    pub fn find(&self, key: u32) -> Option<u32> {
+        let (mut lo, mut hi) = (0, self.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let (x, root) = self.entry(mid);
+            match x.cmp(&key) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return Some(root),
+            }
+        }
+        None
+    }
+
+    /// Tests if two keys are in a same set.
+    ///
+    /// Keys out of any set are never in a same set.
+    pub fn same_set(&self, key1: u32, key2: u32) -> bool {
+        match (self.find(key1), self.find(key2)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    }
+
+    fn entry(&self, i: usize) -> (u32, u32) {
+        let start = HEADER_LEN + i * ENTRY_LEN;
+        let word = |at: usize| {
+            let bytes = self.map[at..at + 4].try_into().unwrap();
+            u32::from_le_bytes(bytes)
+        };
+        (word(start), word(start + 4))
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,12 @@
+//! Read-only union-find sets over `u32` keys, answered directly from a memory-mapped file.
+//!
+//! The file is a frozen snapshot of some [raw::UnionFindSets](crate::raw::UnionFindSets),
+//! which is written by [MmapUnionFind::save] as `(key, representative)` pairs sorted by keys.
+//! Keys may be anywhere in `u32`; the file grows only with the number of keys.
+//! Many processes may map the same file and share one physical copy of it.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,69 @@
+use super::*;
+use crate::raw::UnionFindSets;
+use quickcheck_macros::*;
+use std::io::Write;
+
+#[quickcheck]
+fn frozen_same_set(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<(u8, u8)>) {
+    let mut origin = UnionFindSets::new();
+    for x in adds.into_iter() {
+        let _ = origin.make_set(x as u32, ());
+    }
+    for (x, y) in connects.into_iter() {
+        let _ = origin.unite(&(x as u32), &(y as u32));
+    }
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    MmapUnionFind::save(&origin, &mut file).unwrap();
+    file.flush().unwrap();
+    let trial = MmapUnionFind::open(file.path()).unwrap();
+
+    for (x, y) in queries.into_iter() {
+        let (x, y) = (x as u32, y as u32);
+        assert_eq!(trial.find(x).is_some(), origin.find(&x).is_some());
+        let oracle = match (origin.find(&x), origin.find(&y)) {
+            (Some(xs), Some(ys)) => xs == ys,
+            _ => false,
+        };
+        assert_eq!(trial.same_set(x, y), oracle);
+    }
+}
+
+#[test]
+fn reject_garbage() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"definitely not frozen").unwrap();
+    file.flush().unwrap();
+    assert!(MmapUnionFind::open(file.path()).is_err());
+}
+
+#[test]
+fn sparse_keys() {
+    let mut origin = UnionFindSets::new();
+    for x in [0, u32::MAX - 1, u32::MAX] {
+        origin.make_set(x, ()).unwrap();
+    }
+    origin.unite(&0, &u32::MAX).unwrap();
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    MmapUnionFind::save(&origin, &mut file).unwrap();
+    file.flush().unwrap();
+    assert_eq!(file.as_file().metadata().unwrap().len(), 16 + 3 * 8);
+    let trial = MmapUnionFind::open(file.path()).unwrap();
+
+    assert_eq!(trial.len(), 3);
+    assert!(trial.same_set(0, u32::MAX));
+    assert!(!trial.same_set(0, u32::MAX - 1));
+    assert_eq!(trial.find(u32::MAX - 1), Some(u32::MAX - 1));
+    assert_eq!(trial.find(1), None);
+}
+
+#[test]
+fn reject_unsorted_keys() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"TUFSMMAP").unwrap();
+    file.write_all(&2u64.to_le_bytes()).unwrap();
+    for x in [2u32, 2, 1, 1] {
+        file.write_all(&x.to_le_bytes()).unwrap();
+    }
+    file.flush().unwrap();
+    assert!(MmapUnionFind::open(file.path()).is_err());
+}
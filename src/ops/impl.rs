@@ -0,0 +1,56 @@
+use crate::raw::SetId;
+use crate::{Mergable, Result, UnionFindSets};
+use core::hash::Hash;
+
+/// An operation on [UnionFindSets].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(::arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub enum Op<Key, Tag> {
+    /// Makes a singleton set, like [make_set](UnionFindSets::make_set).
+    MakeSet(Key, Tag),
+    /// Unites two sets, like [unite](UnionFindSets::unite).
+    Unite(Key, Key),
+    /// Finds the set of an element, like [find](UnionFindSets::find).
+    Find(Key),
+    /// Removes the whole set of an element, like [take_set](UnionFindSets::take_set).
+    Remove(Key),
+}
+
+/// The result of an [Op], by the operation it comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OpResult {
+    MakeSet(Result<()>),
+    Unite(Result<bool>),
+    /// The id and the size of the set found, if any.
+    Find(Option<(SetId, usize)>),
+    /// The size of the set removed, if any.
+    Remove(Option<usize>),
+}
+
+impl<Key, Tag> UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Applies an operation, as if the corresponding method is called.
+    pub fn apply(&mut self, op: Op<Key, Tag>) -> OpResult {
+        match op {
+            Op::MakeSet(key, tag) => OpResult::MakeSet(self.make_set(key, tag)),
+            Op::Unite(x, y) => OpResult::Unite(self.unite(&x, &y)),
+            Op::Find(key) => OpResult::Find(self.find(&key).map(|xs| (xs.id(), xs.len()))),
+            Op::Remove(key) => OpResult::Remove(self.take_set(&key).map(|(keys, _)| keys.len())),
+        }
+    }
+
+    /// Applies operations in order, and collects their results.
+    pub fn apply_all<I>(&mut self, ops: I) -> alloc::vec::Vec<OpResult>
+    where
+        I: IntoIterator<Item = Op<Key, Tag>>,
+    {
+        ops.into_iter().map(|op| self.apply(op)).collect()
+    }
+}
@@ -0,0 +1,9 @@
+//! Operations on sets as plain values,
+//! so that they can be generated, recorded and replayed
+//! by [UnionFindSets::apply](crate::UnionFindSets::apply).
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
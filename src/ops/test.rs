@@ -0,0 +1,71 @@
+use super::*;
+use crate::raw::test::Oracle;
+use crate::UnionFindSets;
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn apply_like_oracle(ops: Vec<(u8, u8, u8)>) {
+    let mut trial = UnionFindSets::new();
+    let mut oracle = Oracle::new();
+    for (kind, x, y) in ops {
+        let (x, y) = (x % 16, y % 16);
+        match (kind % 4, trial.apply(op(kind, x, y))) {
+            (0, OpResult::MakeSet(res)) => {
+                assert_eq!(res.is_ok(), oracle.make_set(x).is_ok());
+            }
+            (1, OpResult::Unite(res)) => {
+                assert_eq!(res.ok(), oracle.unite(x, y).ok());
+            }
+            (2, OpResult::Find(res)) => {
+                assert_eq!(res.map(|(_, len)| len), oracle.find(&x).map(|xs| xs.len()));
+            }
+            (3, OpResult::Remove(res)) => {
+                assert_eq!(res, oracle.remove(x).map(|xs| xs.len()));
+            }
+            (_, res) => panic!("mismatched result: {:?}", res),
+        }
+    }
+}
+
+fn op(kind: u8, x: u8, y: u8) -> Op<u8, ()> {
+    match kind % 4 {
+        0 => Op::MakeSet(x, ()),
+        1 => Op::Unite(x, y),
+        2 => Op::Find(x),
+        _ => Op::Remove(x),
+    }
+}
+
+#[test]
+fn apply_all() {
+    let mut sets = UnionFindSets::new();
+    let results = sets.apply_all([
+        Op::MakeSet(0, ()),
+        Op::MakeSet(1, ()),
+        Op::MakeSet(1, ()),
+        Op::Unite(0, 1),
+        Op::Unite(1, 2),
+        Op::Find(1),
+        Op::Find(2),
+        Op::Remove(0),
+        Op::Remove(1),
+        Op::Find(1),
+    ]);
+    assert_eq!(
+        results[..2],
+        [OpResult::MakeSet(Ok(())), OpResult::MakeSet(Ok(()))]
+    );
+    assert!(matches!(results[2], OpResult::MakeSet(Err(_))));
+    assert_eq!(results[3], OpResult::Unite(Ok(true)));
+    assert!(matches!(results[4], OpResult::Unite(Err(_))));
+    assert!(matches!(results[5], OpResult::Find(Some((_, 2)))));
+    assert_eq!(results[6], OpResult::Find(None));
+    assert_eq!(
+        results[7..],
+        [
+            OpResult::Remove(Some(2)),
+            OpResult::Remove(None),
+            OpResult::Find(None)
+        ]
+    );
+}
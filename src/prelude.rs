@@ -1,7 +1,12 @@
-use crate::Mergable;
-use std::borrow::Borrow;
-use std::collections::LinkedList;
-use std::hash::Hash;
+use crate::chunked::ChunkedList;
+use crate::raw::{Node, SizedTag};
+pub use crate::raw::{SetId, UnionStats, UniteOutcome};
+use crate::{Error, HeapSize, Mergable, Result, Semilattice};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use hashbrown::{HashMap, HashSet};
 
 /// A set of union-find sets, each of which can be associated with a mergable tag.
 #[derive(Clone)]
@@ -13,6 +18,22 @@ where
     raw: crate::raw::UnionFindSets<Key, IterableTag<Key, Tag>>,
 }
 
+/// Lists sets, each by its key, elements and tag.
+impl<Key, Tag> core::fmt::Debug for UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone + core::fmt::Debug,
+    Tag: Mergable + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.raw.iter().map(|xs| {
+                let tag = xs.tag();
+                (xs.key(), tag.sets.iter().collect::<Vec<_>>(), &tag.tag)
+            }))
+            .finish()
+    }
+}
+
 impl<Key, Tag> UnionFindSets<Key, Tag>
 where
     Key: Eq + Hash + Clone,
@@ -28,38 +49,624 @@ where
     /// Makes an individual set with a singleton element and its associated tag.
     ///
     /// If the set to make is already there,
-    /// an error will be raised and nothing will happen to the sets.
-    pub fn make_set(&mut self, key: Key, tag: Tag) -> anyhow::Result<()> {
+    /// [Error::DuplicateKey](crate::Error::DuplicateKey) will be raised
+    /// and nothing will happen to the sets.
+    pub fn make_set(&mut self, key: Key, tag: Tag) -> Result<()> {
         self.raw.make_set(key.clone(), IterableTag::new(key, tag))
     }
 
-    /// Unites two sets.
+    /// Makes an individual set with a singleton element and the default tag,
+    /// like [make_set](Self::make_set).
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
     ///
-    /// If either of them is not in the sets, an error will be raised;
+    /// let mut sets: UnionFindSets<&str, ()> = UnionFindSets::new();
+    /// sets.make_set_default("a").unwrap();
+    /// sets.make_sets_default(["b", "c"]).unwrap();
+    /// assert!(sets.make_sets_default(["d", "a", "e"]).is_err());
+    /// assert_eq!(sets.len(), 4);
+    /// ```
+    pub fn make_set_default(&mut self, key: Key) -> Result<()>
+    where
+        Tag: Default,
+    {
+        self.make_set(key, Tag::default())
+    }
+
+    /// Makes individual sets of all the keys with default tags,
+    /// stopping at the first error, e.g., [Error::DuplicateKey](crate::Error::DuplicateKey),
+    /// where sets made before it are kept.
+    pub fn make_sets_default<I>(&mut self, keys: I) -> Result<()>
+    where
+        Tag: Default,
+        I: IntoIterator<Item = Key>,
+    {
+        keys.into_iter()
+            .try_for_each(|key| self.make_set_default(key))
+    }
+
+    /// Unites two sets, given by any borrowed forms of their keys.
+    ///
+    /// If either of them is not in the sets,
+    /// [Error::KeyNotFound](crate::Error::KeyNotFound) will be raised, telling which are missing;
     /// if they are of a same set, `Ok(false)` will be returns;
     /// otherwise, which means these two sets are really united into one in this case,
     /// `Ok(true)` will be returned.
-    pub fn unite<K1, K2>(&mut self, key1: &K1, key2: &K2) -> anyhow::Result<bool>
+    pub fn unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Result<bool>
     where
-        K1: Hash + Eq + Borrow<Key> + std::fmt::Debug,
-        K2: Hash + Eq + Borrow<Key> + std::fmt::Debug,
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
     {
         self.raw.unite(key1, key2)
     }
 
+    /// Unites two sets, like [unite](Self::unite),
+    /// but tells which representative survives and which is merged away,
+    /// e.g., to update indexes keyed by representatives.
+    pub fn unite_with_outcome<Q1, Q2>(
+        &mut self,
+        key1: &Q1,
+        key2: &Q2,
+    ) -> Result<UniteOutcome<'_, Key>>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.raw.unite_with_outcome(key1, key2)
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but the representative of the set with the higher `priority` of tags survives,
+    /// whichever set is larger; on ties, the larger set wins as usual.
+    ///
+    /// E.g., in entity resolution, the set of the golden record keeps it as the representative.
+    /// Sets are no longer balanced by sizes, so long paths may be built,
+    /// though they are still compressed by finding.
+    pub fn unite_by_priority<Q1, Q2, P, F>(
+        &mut self,
+        key1: &Q1,
+        key2: &Q2,
+        mut priority: F,
+    ) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+        P: Ord,
+        F: FnMut(&Tag) -> P,
+    {
+        self.raw
+            .unite_by_priority(key1, key2, |tag| priority(&tag.tag))
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but the lesser of the two representatives survives.
+    pub(crate) fn unite_by_min_key<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Result<bool>
+    where
+        Key: Ord + Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.raw
+            .unite_choosing(key1, key2, |(top1, _), (top2, _)| top1 < top2)
+    }
+
+    /// Unites sets of all the keys into one, returning how many unions really happened,
+    /// like [raw::UnionFindSets::unite_many](crate::raw::UnionFindSets::unite_many).
+    ///
+    /// E.g., all records of a batch which are known to belong together are grouped at once.
+    pub fn unite_many<Q>(&mut self, keys: &[Q]) -> Result<usize>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        self.raw.unite_many(keys)
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but simply returns `None` if either of them is not in the sets.
+    pub fn try_unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Option<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.unite(key1, key2).ok()
+    }
+
     /// Finds an individual set.
     ///
+    /// As with `HashMap::get`, `key` may be any borrowed form of `Key`, e.g., `&str` for `String`.
     /// If the set is not inside, `None` will be returned.
-    pub fn find<K>(&self, key: &K) -> Option<Set<Key, Tag>>
+    pub fn find<Q>(&self, key: &Q) -> Option<Set<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.raw.find(key).map(|raw| Set {
+            raw,
+            sets: &self.raw,
+        })
+    }
+
+    /// Tests whether `key` has been made a set of, i.e., is an element of any set.
+    ///
+    /// Unlike [find](Self::find), it neither walks nor compresses paths.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.raw.contains(key)
+    }
+
+    /// Tells whether `key` is the root of its tree, i.e., the representative of its set,
+    /// or `None` if it is not in the sets.
+    pub fn is_root<Q>(&self, key: &Q) -> Option<bool>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.raw.is_root(key)
+    }
+
+    /// Counts links on the path from `key` up to its root, without compressing the path,
+    /// or returns `None` if it is not in the sets.
+    ///
+    /// It probes how deep trees grow, e.g., under different orders of unions.
+    pub fn depth<Q>(&self, key: &Q) -> Option<usize>
     where
-        K: Eq + Hash + Borrow<Key>,
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
     {
-        self.raw.find(key).map(|x| Set { raw: x })
+        self.raw.depth(key)
+    }
+
+    /// Gets the tag of the set containing `key`, without making a view of the set.
+    ///
+    /// If the key is not in the sets, `None` will be returned.
+    pub fn tag_of<Q>(&self, key: &Q) -> Option<&Tag>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.raw.tag_of(key).map(|tag| &tag.tag)
+    }
+
+    /// Iterates over all individual sets in ascending order of their representatives.
+    pub fn iter_sets_sorted_by_key(&self) -> alloc::vec::IntoIter<Set<'_, Key, Tag>>
+    where
+        Key: Ord,
+    {
+        let mut sets: Vec<_> = self.iter().collect();
+        sets.sort_unstable_by_key(|xs| xs.key());
+        sets.into_iter()
+    }
+
+    /// Picks a set at random, with probability proportional to its size.
+    ///
+    /// If there is no set, `None` will be returned.
+    #[cfg(feature = "rand")]
+    pub fn sample_set_weighted<R: rand::Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Option<Set<'_, Key, Tag>> {
+        let elements = self.raw.nodes.len();
+        if elements == 0 {
+            return None;
+        }
+        let mut i = rng.random_range(0..elements);
+        for set in self.iter() {
+            match i.checked_sub(set.len()) {
+                Some(rest) => i = rest,
+                None => return Some(set),
+            }
+        }
+        None
+    }
+
+    /// Groups elements by representatives of their sets.
+    #[cfg(feature = "std")]
+    pub fn group_map(&self) -> std::collections::HashMap<&Key, Vec<&Key>> {
+        self.iter()
+            .map(|xs| (xs.key(), xs.iter().collect()))
+            .collect()
+    }
+
+    /// Finds an individual set, like [find](Self::find),
+    /// but its tag can be updated through [SetMut::tag_mut].
+    pub fn find_mut<Q>(&mut self, key: &Q) -> Option<SetMut<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.raw.find_mut(key).map(|raw| SetMut { raw })
     }
 
     /// Iterates over all individual sets.
-    pub fn iter(&self) -> impl Iterator<Item = Set<Key, Tag>> {
-        self.raw.iter().map(|raw| Set { raw })
+    pub fn iter(&self) -> SetsIter<'_, Key, Tag> {
+        SetsIter {
+            raw: self.raw.iter(),
+            sets: &self.raw,
+        }
+    }
+
+    /// Iterates over every element along with the representative of its set,
+    /// compressing paths as elements are visited.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..3u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &2).unwrap();
+    /// let mut pairs: Vec<_> = sets.iter_pairs().collect();
+    /// pairs.sort();
+    /// let rep = sets.find(&0).unwrap().key();
+    /// assert_eq!(pairs, vec![(&0, rep), (&1, &1), (&2, rep)]);
+    /// ```
+    pub fn iter_pairs(&self) -> impl Iterator<Item = (&Key, &Key)> + '_ {
+        self.raw.iter_pairs()
+    }
+
+    /// Lazily iterates over all unordered pairs of distinct elements within each set,
+    /// e.g., candidate pairs for pairwise scoring after blocking.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..4u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &1).unwrap();
+    /// sets.unite(&1, &2).unwrap();
+    /// let mut pairs: Vec<_> = sets
+    ///     .iter_same_set_pairs()
+    ///     .map(|(x, y)| (*x.min(y), *x.max(y)))
+    ///     .collect();
+    /// pairs.sort();
+    /// assert_eq!(pairs, vec![(0, 1), (0, 2), (1, 2)]);
+    /// ```
+    pub fn iter_same_set_pairs(&self) -> impl Iterator<Item = (&Key, &Key)> + '_ {
+        self.iter_same_set_pairs_capped(usize::MAX)
+    }
+
+    /// Like [UnionFindSets::iter_same_set_pairs],
+    /// but yields at most `max_per_set` pairs from each set,
+    /// so that a few huge sets do not swamp the output.
+    pub fn iter_same_set_pairs_capped(
+        &self,
+        max_per_set: usize,
+    ) -> impl Iterator<Item = (&Key, &Key)> + '_ {
+        self.iter().flat_map(move |xs| {
+            let mut rest = xs.iter();
+            core::iter::from_fn(move || {
+                let x = rest.next()?;
+                Some(rest.clone().map(move |y| (x, y)))
+            })
+            .flatten()
+            .take(max_per_set)
+        })
+    }
+
+    /// Builds the dense same-set matrix of `order`,
+    /// where the cell at row `i` and column `j` tells whether `order[i]` and `order[j]` are in a same set.
+    ///
+    /// Keys absent from the sets are connected to nothing, not even to themselves.
+    /// It takes `n^2` cells, so it is meant for small `n`.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..3u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &2).unwrap();
+    /// let matrix = sets.to_connectivity_matrix(&[0, 1, 2]);
+    /// assert_eq!(matrix, vec![
+    ///     vec![true, false, true],
+    ///     vec![false, true, false],
+    ///     vec![true, false, true],
+    /// ]);
+    /// ```
+    pub fn to_connectivity_matrix(&self, order: &[Key]) -> Vec<Vec<bool>> {
+        let ids: Vec<_> = order
+            .iter()
+            .map(|key| self.find(key).map(|xs| xs.id()))
+            .collect();
+        ids.iter()
+            .map(|x| ids.iter().map(|y| x.is_some() && x == y).collect())
+            .collect()
+    }
+
+    /// Calls `f` on the tag of every set, along with its representative,
+    /// so that tags can be updated in place.
+    pub fn for_each_tag_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Key, &mut Tag),
+    {
+        self.raw.for_each_tag_mut(|key, tag| f(key, &mut tag.tag));
+    }
+
+    /// Converts tags of all sets, given their representatives, keeping the sets as they are.
+    pub fn map_tags<U, F>(self, mut f: F) -> UnionFindSets<Key, U>
+    where
+        U: Mergable,
+        F: FnMut(&Key, Tag) -> U,
+    {
+        let raw = self
+            .raw
+            .map_tags(|key, IterableTag { sets, tag }| IterableTag {
+                sets,
+                tag: f(key, tag),
+            });
+        UnionFindSets { raw }
+    }
+
+    /// Counts outcomes of all unions tried since the sets were made
+    /// or [reset_union_stats](Self::reset_union_stats) was called.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// sets.make_set(0, ()).unwrap();
+    /// sets.make_set(1, ()).unwrap();
+    /// sets.unite(&0, &1).unwrap();
+    /// sets.unite(&1, &0).unwrap();
+    /// assert!(sets.unite(&0, &2).is_err());
+    /// let stats = sets.union_stats();
+    /// assert_eq!((stats.unions, stats.redundant, stats.missing), (1, 1, 1));
+    /// assert_eq!(stats.redundancy(), 0.5);
+    /// ```
+    pub fn union_stats(&self) -> UnionStats {
+        self.raw.union_stats()
+    }
+
+    /// Resets counts of outcomes of unions to zero.
+    pub fn reset_union_stats(&mut self) {
+        self.raw.reset_union_stats()
+    }
+
+    /// Consumes the sets, yielding the tag and elements of every set,
+    /// whose keys are moved out of the lists of elements rather than cloned.
+    ///
+    /// ```
+    /// use tagged_ufs::{Count, UnionFindSets};
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..3u32 {
+    ///     sets.make_set(x, Count(1)).unwrap();
+    /// }
+    /// sets.unite(&0, &2).unwrap();
+    /// let mut groups: Vec<_> = sets.into_sets().collect();
+    /// groups.sort_by_key(|(tag, _)| tag.0);
+    /// assert_eq!(groups[0], (Count(1), vec![1]));
+    /// assert_eq!(groups[1].1.len(), 2);
+    /// ```
+    pub fn into_sets(self) -> impl Iterator<Item = (Tag, Vec<Key>)> {
+        self.raw
+            .into_tags()
+            .map(|IterableTag { sets, tag }| (tag, sets.into_iter().collect()))
+    }
+
+    /// Relabels every key by `f`, keeping the sets and their tags as they are.
+    ///
+    /// `f` is called exactly once per key.
+    /// If two keys are relabeled the same,
+    /// [Error::DuplicateKey](crate::Error::DuplicateKey) will be raised.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..3u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &2).unwrap();
+    /// let sets = sets.map_keys(|x| format!("id-{}", x)).unwrap();
+    /// assert_eq!(sets.find("id-2").unwrap().len(), 2);
+    /// ```
+    pub fn map_keys<K2, F>(self, mut f: F) -> Result<UnionFindSets<K2, Tag>>
+    where
+        K2: Eq + Hash + Clone,
+        F: FnMut(Key) -> K2,
+    {
+        let mut raw = crate::raw::UnionFindSets::new();
+        for (top, IterableTag { sets, tag }) in self.raw.into_roots() {
+            // the representative goes first, so that it stays the representative
+            let mut keys = Vec::with_capacity(sets.len());
+            for x in sets {
+                let is_top = x == top;
+                keys.push(f(x));
+                if is_top {
+                    let last = keys.len() - 1;
+                    keys.swap(0, last);
+                }
+            }
+            let sets = keys.iter().cloned().collect();
+            raw.insert_set(keys, IterableTag { sets, tag })?;
+        }
+        Ok(UnionFindSets { raw })
+    }
+
+    /// Extracts new sets of only the given keys, each in the set it is in here,
+    /// whose tags are cloned from the sets they come from.
+    ///
+    /// Keys which are not in the sets are skipped, and so are repeated ones.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..6u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &3).unwrap();
+    /// sets.unite(&3, &4).unwrap();
+    /// let part = sets.restrict(&[0, 3, 5, 9]);
+    /// assert_eq!(part.len(), 2);
+    /// assert_eq!(part.find(&3).unwrap().len(), 2);
+    /// assert!(part.find(&4).is_none());
+    /// ```
+    pub fn restrict<'a, I>(&self, keys: I) -> UnionFindSets<Key, Tag>
+    where
+        Key: 'a,
+        Tag: Clone,
+        I: IntoIterator<Item = &'a Key>,
+    {
+        self.restrict_with(keys, |xs| xs.tag().clone())
+    }
+
+    /// Extracts new sets of only the given keys, like [restrict](Self::restrict),
+    /// but tags are made by `f` from the sets they come from.
+    pub fn restrict_with<'a, I, F>(&self, keys: I, mut f: F) -> UnionFindSets<Key, Tag>
+    where
+        Key: 'a,
+        I: IntoIterator<Item = &'a Key>,
+        F: FnMut(&Set<'_, Key, Tag>) -> Tag,
+    {
+        let mut seen = HashSet::with_hasher(ahash::RandomState::new());
+        let mut groups: HashMap<SetId, Vec<Key>, _> =
+            HashMap::with_hasher(ahash::RandomState::new());
+        for key in keys {
+            if !seen.insert(key) {
+                continue;
+            }
+            if let Some(xs) = self.find(key) {
+                groups.entry(xs.id()).or_default().push(key.clone());
+            }
+        }
+        let mut raw = crate::raw::UnionFindSets::new();
+        for keys in groups.into_values() {
+            let Some(tag) = keys.first().and_then(|x| self.find(x)).map(|xs| f(&xs)) else {
+                continue;
+            };
+            let sets = keys.iter().cloned().collect();
+            // `raw` starts empty, duplicates of `keys` are skipped by `seen`,
+            // and every key joins only the group of its own set,
+            // so no key is inserted twice
+            raw.insert_set(keys, IterableTag { sets, tag })
+                .expect("keys of groups are distinct");
+        }
+        UnionFindSets { raw }
+    }
+
+    /// Moves whole sets on which `pred` holds out into new sets, leaving the others here.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..5u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &1).unwrap();
+    /// sets.unite(&1, &2).unwrap();
+    /// let finished = sets.split_off(|xs| xs.len() >= 3);
+    /// assert_eq!(finished.len(), 1);
+    /// assert_eq!(finished.find(&2).unwrap().len(), 3);
+    /// assert_eq!(sets.len(), 2);
+    /// assert!(sets.find(&0).is_none());
+    /// ```
+    pub fn split_off<F>(&mut self, pred: F) -> Self
+    where
+        F: FnMut(&Set<'_, Key, Tag>) -> bool,
+    {
+        let mut raw = crate::raw::UnionFindSets::new();
+        for (keys, tag) in self.take_sets_where(pred) {
+            // `raw` starts empty, and keys are taken out of disjoint sets of `self`,
+            // so no key is inserted twice
+            raw.insert_set(keys, tag)
+                .expect("keys of taken sets are distinct");
+        }
+        Self { raw }
+    }
+
+    /// Removes whole sets on which `f` holds, returning their tags and elements,
+    /// where the representative of every set comes first.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..5u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &1).unwrap();
+    /// let singletons = sets.extract_sets_where(|xs| xs.len() == 1);
+    /// assert_eq!(singletons.len(), 3);
+    /// assert_eq!(sets.len(), 1);
+    /// ```
+    pub fn extract_sets_where<F>(&mut self, f: F) -> Vec<(Tag, Vec<Key>)>
+    where
+        F: FnMut(&Set<'_, Key, Tag>) -> bool,
+    {
+        self.take_sets_where(f)
+            .into_iter()
+            .map(|(keys, tag)| (tag.tag, keys))
+            .collect()
+    }
+
+    fn take_sets_where<F>(&mut self, mut pred: F) -> Vec<(Vec<Key>, IterableTag<Key, Tag>)>
+    where
+        F: FnMut(&Set<'_, Key, Tag>) -> bool,
+    {
+        let tops: Vec<Key> = self
+            .iter()
+            .filter(|xs| pred(xs))
+            .map(|xs| xs.key().clone())
+            .collect();
+        tops.iter().filter_map(|top| self.take_top(top)).collect()
+    }
+
+    /// Removes the whole set containing `key`, returning its elements and tag,
+    /// where the representative comes first.
+    ///
+    /// If `key` is not in the sets, `None` will be returned.
+    ///
+    /// ```
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..3u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &1).unwrap();
+    /// let (keys, _) = sets.take_set(&0).unwrap();
+    /// assert_eq!(keys.len(), 2);
+    /// assert_eq!(sets.len(), 1);
+    /// assert!(sets.take_set(&1).is_none());
+    /// ```
+    pub fn take_set<Q>(&mut self, key: &Q) -> Option<(Vec<Key>, Tag)>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let top = self.find(key)?.key().clone();
+        self.take_top(&top).map(|(keys, tag)| (keys, tag.tag))
+    }
+
+    fn take_top(&mut self, top: &Key) -> Option<(Vec<Key>, IterableTag<Key, Tag>)> {
+        let Some((top, Node::Root(SizedTag { tag, .. }))) = self.raw.nodes.remove_entry(top) else {
+            return None;
+        };
+        // the top is among the elements, but it is already taken out
+        let mut keys = alloc::vec![top];
+        for x in tag.sets.iter() {
+            if let Some((x, _)) = self.raw.nodes.remove_entry(x) {
+                keys.push(x);
+            }
+        }
+        self.raw.sets -= 1;
+        Some((keys, tag))
     }
 
     /// Queries the number of individual sets in the set.
@@ -73,6 +680,110 @@ where
     }
 }
 
+impl<Key, Tag> UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone + Borrow<str> + for<'a> From<&'a str>,
+    Tag: Mergable,
+{
+    /// Makes an individual set of a string key, like [make_set](Self::make_set),
+    /// but converts the string into `Key` only if it is not in the sets yet.
+    ///
+    /// With shared strings, e.g., `Arc<str>` or `Rc<str>`, as keys,
+    /// every copy of a key inside the sets points at a single allocation of the string.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tagged_ufs::UnionFindSets;
+    ///
+    /// let mut sets = UnionFindSets::<Arc<str>, ()>::new();
+    /// sets.make_set_str("https://example.com/a", ()).unwrap();
+    /// sets.make_set_str("https://example.com/b", ()).unwrap();
+    /// assert!(sets.make_set_str("https://example.com/a", ()).is_err());
+    /// sets.unite("https://example.com/a", "https://example.com/b").unwrap();
+    /// let a = sets.shared_key("https://example.com/a").unwrap();
+    /// let elements: Vec<_> = sets.find("https://example.com/b").unwrap().iter().collect();
+    /// assert!(elements.iter().any(|x| Arc::ptr_eq(x, a)));
+    /// ```
+    pub fn make_set_str(&mut self, key: &str, tag: Tag) -> Result<()> {
+        if self.raw.nodes.contains_key(key) {
+            return Err(Error::DuplicateKey);
+        }
+        self.make_set(Key::from(key), tag)
+    }
+
+    /// Gets the key stored in the sets for a string,
+    /// so that other structures can share it instead of holding another copy.
+    ///
+    /// If the key is not in the sets, `None` will be returned.
+    pub fn shared_key(&self, key: &str) -> Option<&Key> {
+        self.raw.nodes.get_key_value(key).map(|(key, _)| key)
+    }
+}
+
+impl<Key, Tag> UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Semilattice,
+{
+    /// Joins another replica of sets into this one, as a state-based CRDT.
+    ///
+    /// Elements of either are kept, and two elements are in a same set
+    /// if they are so in either, transitively.
+    /// Tags of sets being united are joined by their [Semilattice] merging,
+    /// so joining is commutative, associative and idempotent,
+    /// up to which elements represent sets.
+    pub fn join(&mut self, other: &Self) -> Result<()> {
+        for xs in other.iter() {
+            let top = xs.key();
+            // elements new here are made as one set, carrying a single clone of the tag,
+            // with the representative first if it is new, too
+            let (old_keys, new_keys): (Vec<&Key>, Vec<&Key>) =
+                xs.iter().partition(|x| self.contains(*x));
+            let mut new_keys: Vec<Key> = new_keys.into_iter().cloned().collect();
+            if let Some(i) = new_keys.iter().position(|x| x == top) {
+                new_keys.swap(0, i);
+            }
+            match new_keys.first().cloned() {
+                Some(new_top) => {
+                    let sets = new_keys.iter().cloned().collect();
+                    self.raw.insert_set(
+                        new_keys,
+                        IterableTag {
+                            sets,
+                            tag: xs.tag().clone(),
+                        },
+                    )?;
+                    // a new representative already heads the elements it would be united with
+                    if new_top != *top {
+                        self.unite(&new_top, top)?;
+                    }
+                }
+                None => {
+                    if let Some(mut set) = self.find_mut(top) {
+                        set.tag_mut().merge(xs.tag().clone());
+                    }
+                }
+            }
+            for x in old_keys.into_iter().filter(|x| *x != top) {
+                self.unite(top, x)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Key, Tag> UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone + HeapSize,
+    Tag: Mergable + HeapSize,
+{
+    /// Reports the number of bytes on heap owned by these sets,
+    /// including the forest, the lists of elements and whatever keys and tags own.
+    pub fn heap_size(&self) -> usize {
+        self.raw.heap_size()
+    }
+}
+
 impl<Key, Tag> Default for UnionFindSets<Key, Tag>
 where
     Key: Eq + Hash + Clone,
@@ -85,11 +796,16 @@ where
 
 /// A wrapper to customized tag, which provides iterability over elements.
 ///
-/// The iterability is implemented by linked list.
-/// So, merging two IterableTag's has O(1) overhead.
+/// The iterability is implemented by a linked list of chunks of elements.
+/// So, merging two IterableTag's has O(1) overhead,
+/// while iterating over elements stays cache-friendly.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct IterableTag<Key, Tag> {
-    sets: LinkedList<Key>,
+    sets: ChunkedList<Key>,
     tag: Tag,
 }
 
@@ -97,37 +813,60 @@ impl<Key, Tag> Mergable for IterableTag<Key, Tag>
 where
     Tag: Mergable,
 {
-    fn merge(&mut self, mut other: Self) {
-        self.sets.append(&mut other.sets);
+    fn merge(&mut self, other: Self) {
+        self.sets.append(other.sets);
         self.tag.merge(other.tag);
     }
 }
 
+impl<Key: HeapSize, Tag: HeapSize> HeapSize for IterableTag<Key, Tag> {
+    fn heap_size(&self) -> usize {
+        self.sets.heap_size() + self.tag.heap_size()
+    }
+}
+
 impl<Key, Tag> IterableTag<Key, Tag> {
     pub fn new(key: Key, tag: Tag) -> Self {
         Self {
-            sets: LinkedList::from_iter([key]),
+            sets: ChunkedList::new(key),
             tag,
         }
     }
 }
 
 /// An individual set
-#[derive(Debug)]
 pub struct Set<'a, Key, Tag>
 where
-    Key: Eq,
+    Key: Eq + Hash,
+    Tag: Mergable,
 {
     raw: crate::raw::Set<'a, Key, IterableTag<Key, Tag>>,
+    sets: &'a crate::raw::UnionFindSets<Key, IterableTag<Key, Tag>>,
 }
 
-impl<'a, Key: Eq + Hash, Tag> PartialEq for Set<'a, Key, Tag> {
+impl<'a, Key, Tag> core::fmt::Debug for Set<'a, Key, Tag>
+where
+    Key: Eq + Hash + core::fmt::Debug,
+    Tag: Mergable + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Set").field("raw", &self.raw).finish()
+    }
+}
+
+impl<'a, Key: Eq + Hash, Tag: Mergable> PartialEq for Set<'a, Key, Tag> {
     fn eq(&self, other: &Self) -> bool {
         self.raw.eq(&other.raw)
     }
 }
 
-impl<'a, Key: Eq + Hash, Tag> Eq for Set<'a, Key, Tag> {}
+impl<'a, Key: Eq + Hash, Tag: Mergable> Eq for Set<'a, Key, Tag> {}
+
+impl<'a, Key: Eq + Hash, Tag: Mergable> Hash for Set<'a, Key, Tag> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.raw.hash(state);
+    }
+}
 
 impl<'a, Key, Tag> Set<'a, Key, Tag>
 where
@@ -145,8 +884,306 @@ where
     }
 
     /// Iterates over elements in the set.
-    pub fn iter(&self) -> impl Iterator<Item = &Key> {
-        self.raw.tag().sets.iter()
+    pub fn iter(&self) -> ElementsIter<'a, Key> {
+        ElementsIter {
+            inner: self.raw.tag().sets.iter(),
+            remaining: self.len(),
+        }
+    }
+
+    /// Iterates over elements in the set in ascending order.
+    pub fn iter_sorted(&self) -> alloc::vec::IntoIter<&'a Key>
+    where
+        Key: Ord,
+    {
+        let mut xs: Vec<_> = self.iter().collect();
+        xs.sort_unstable();
+        xs.into_iter()
+    }
+
+    /// Picks an element of the set uniformly at random.
+    #[cfg(feature = "rand")]
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<&'a Key> {
+        let sets = &self.raw.tag().sets;
+        sets.get(rng.random_range(0..sets.len()))
+    }
+
+    /// Tests if an element, given by any borrowed form of its key, is in the set.
+    ///
+    /// It finds the representative of the element, rather than scanning the elements.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Key: Borrow<Q> + Clone,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.sets
+            .find(key)
+            .is_some_and(|other| core::ptr::eq(other.key(), self.key()))
+    }
+
+    /// Tests if two sets, possibly of different [UnionFindSets], have the same elements.
+    ///
+    /// Unlike `==`, which compares representatives within one [UnionFindSets],
+    /// it compares contents, rejecting sets of different sizes without looking at elements.
+    ///
+    /// ```
+    /// use tagged_ufs::{Count, UnionFindSets};
+    ///
+    /// let mut sets1 = UnionFindSets::new();
+    /// let mut sets2 = UnionFindSets::new();
+    /// for x in 0..3u32 {
+    ///     sets1.make_set(x, ()).unwrap();
+    ///     sets2.make_set(x, Count(1)).unwrap();
+    /// }
+    /// sets1.unite(&0, &1).unwrap();
+    /// sets2.unite(&1, &0).unwrap();
+    /// let xs = sets1.find(&0).unwrap();
+    /// assert!(xs.same_elements(&sets2.find(&1).unwrap()));
+    /// assert!(!xs.same_elements(&sets2.find(&2).unwrap()));
+    /// assert_eq!(xs.fingerprint(), sets2.find(&1).unwrap().fingerprint());
+    /// ```
+    pub fn same_elements<T>(&self, other: &Set<'_, Key, T>) -> bool
+    where
+        Key: Clone,
+        T: Mergable,
+    {
+        self.len() == other.len() && other.iter().all(|x| self.contains(x))
+    }
+
+    /// Hashes elements of the set regardless of their order,
+    /// so that sets with the same elements have the same fingerprint within a process.
+    ///
+    /// Fingerprints can be computed once and compared many times
+    /// before resorting to [Set::same_elements].
+    pub fn fingerprint(&self) -> u64 {
+        let hasher = ahash::RandomState::with_seeds(
+            0x243f_6a88_85a3_08d3,
+            0x1319_8a2e_0370_7344,
+            0xa409_3822_299f_31d0,
+            0x082e_fa98_ec4e_6c89,
+        );
+        self.iter()
+            .fold(0u64, |acc, x| acc.wrapping_add(hasher.hash_one(x)))
+    }
+
+    /// Gets the representative element
+    pub fn key(&self) -> &'a Key {
+        self.raw.key()
+    }
+
+    /// Gets the id of this set, which stays the same until the set is merged into another.
+    pub fn id(&self) -> SetId {
+        self.raw.id()
+    }
+
+    /// Gets the tag associated with this set.
+    pub fn tag(&self) -> &'a Tag {
+        &self.raw.tag().tag
+    }
+
+    /// Clones elements and the tag into a set detached from the sets.
+    pub fn to_owned(&self) -> OwnedSet<Key, Tag>
+    where
+        Key: Clone,
+        Tag: Clone,
+    {
+        OwnedSet {
+            key: self.key().clone(),
+            elements: self.iter().cloned().collect(),
+            tag: self.tag().clone(),
+        }
+    }
+}
+
+impl<'a, 'b, Key, Tag> IntoIterator for &'b Set<'a, Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    type Item = &'b Key;
+    type IntoIter = ElementsIter<'b, Key>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An individual set detached from its sets, see [Set::to_owned].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedSet<Key, Tag> {
+    key: Key,
+    elements: Vec<Key>,
+    tag: Tag,
+}
+
+impl<Key, Tag> OwnedSet<Key, Tag> {
+    /// Queries the number of elements in the set.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Tests if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Iterates over elements in the set.
+    pub fn iter(&self) -> core::slice::Iter<'_, Key> {
+        self.elements.iter()
+    }
+
+    /// Gets the representative element
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Gets the tag associated with this set.
+    pub fn tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    /// Takes the elements and the tag.
+    pub fn into_parts(self) -> (Vec<Key>, Tag) {
+        (self.elements, self.tag)
+    }
+}
+
+impl<Key, Tag> IntoIterator for OwnedSet<Key, Tag> {
+    type Item = Key;
+    type IntoIter = alloc::vec::IntoIter<Key>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'a, Key, Tag> IntoIterator for &'a OwnedSet<Key, Tag> {
+    type Item = &'a Key;
+    type IntoIter = core::slice::Iter<'a, Key>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over all individual sets, see [UnionFindSets::iter].
+pub struct SetsIter<'a, Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    raw: crate::raw::SetsIter<'a, Key, IterableTag<Key, Tag>>,
+    sets: &'a crate::raw::UnionFindSets<Key, IterableTag<Key, Tag>>,
+}
+
+impl<'a, Key, Tag> Clone for SetsIter<'a, Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    fn clone(&self) -> Self {
+        Self {
+            raw: self.raw.clone(),
+            sets: self.sets,
+        }
+    }
+}
+
+impl<'a, Key, Tag> Iterator for SetsIter<'a, Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    type Item = Set<'a, Key, Tag>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let raw = self.raw.next()?;
+        Some(Set {
+            raw,
+            sets: self.sets,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.raw.size_hint()
+    }
+}
+
+impl<'a, Key: Eq + Hash, Tag: Mergable> ExactSizeIterator for SetsIter<'a, Key, Tag> {}
+
+impl<'a, Key: Eq + Hash, Tag: Mergable> FusedIterator for SetsIter<'a, Key, Tag> {}
+
+/// An iterator over elements of a set, see [Set::iter].
+pub struct ElementsIter<'a, Key> {
+    inner: crate::chunked::Iter<'a, Key>,
+    remaining: usize,
+}
+
+impl<'a, Key> Clone for ElementsIter<'a, Key> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, Key> Iterator for ElementsIter<'a, Key> {
+    type Item = &'a Key;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.inner.next()?;
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, Key> DoubleEndedIterator for ElementsIter<'a, Key> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let x = self.inner.next_back()?;
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(x)
+    }
+}
+
+impl<'a, Key> ExactSizeIterator for ElementsIter<'a, Key> {}
+
+impl<'a, Key> FusedIterator for ElementsIter<'a, Key> {}
+
+/// An individual set whose tag can be updated in place.
+#[derive(Debug)]
+pub struct SetMut<'a, Key, Tag>
+where
+    Key: Eq,
+{
+    raw: crate::raw::SetMut<'a, Key, IterableTag<Key, Tag>>,
+}
+
+impl<'a, Key, Tag> SetMut<'a, Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    /// Queries the number of elements in the set.
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Tests if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over elements in the set.
+    pub fn iter(&self) -> ElementsIter<'_, Key> {
+        ElementsIter {
+            inner: self.raw.tag().sets.iter(),
+            remaining: self.len(),
+        }
     }
 
     /// Gets the representative element
@@ -154,8 +1191,66 @@ where
         self.raw.key()
     }
 
+    /// Gets the id of this set.
+    pub fn id(&self) -> SetId {
+        self.raw.id()
+    }
+
     /// Gets the tag associated with this set.
     pub fn tag(&self) -> &Tag {
         &self.raw.tag().tag
     }
+
+    /// Gets the tag associated with this set, mutably.
+    pub fn tag_mut(&mut self) -> &mut Tag {
+        &mut self.raw.tag_mut().tag
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<Key, Tag> borsh::BorshSerialize for UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Ord + borsh::BorshSerialize,
+    Tag: Mergable + borsh::BorshSerialize,
+{
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        self.raw.serialize(writer)
+    }
+}
+
+/// Besides the checks done by the raw sets,
+/// every set must list exactly as many elements as it counts.
+#[cfg(feature = "borsh")]
+impl<Key, Tag> borsh::BorshDeserialize for UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Ord + Clone + borsh::BorshDeserialize,
+    Tag: Mergable + borsh::BorshDeserialize,
+{
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let raw: crate::raw::UnionFindSets<Key, IterableTag<Key, Tag>> =
+            borsh::BorshDeserialize::deserialize_reader(reader)?;
+        let invalid = |msg| {
+            Err(borsh::io::Error::new(
+                borsh::io::ErrorKind::InvalidData,
+                msg,
+            ))
+        };
+        let mut listed = HashSet::with_hasher(ahash::RandomState::new());
+        for xs in raw.iter() {
+            if xs.len() != xs.tag().sets.len() {
+                return invalid("set size does not match its elements");
+            }
+            for x in xs.tag().sets.iter() {
+                if !listed.insert(x) {
+                    return invalid("element listed more than once");
+                }
+                // paths are not compressed, so that the forest is kept as it is written
+                if raw.peek_id(x) != Some(xs.id()) {
+                    return invalid("element listed in a set it is not in");
+                }
+            }
+        }
+        drop(listed);
+        Ok(Self { raw })
+    }
 }
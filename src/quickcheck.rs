@@ -0,0 +1,96 @@
+//! Random sets for property tests of downstream crates.
+
+use crate::{Mergable, UnionFindSets};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use quickcheck::{Arbitrary, Gen};
+
+/// Generates random elements with random tags, and unites random pairs of them.
+///
+/// Shrinking drops whole sets, splits a set in two and drops elements from a set,
+/// so failures shrink toward fewer elements and fewer unions.
+/// As tags of shrunk sets cannot be unmerged,
+/// every part of a split set keeps a clone of the merged tag.
+impl<Key, Tag> Arbitrary for UnionFindSets<Key, Tag>
+where
+    Key: Arbitrary + Eq + Hash,
+    Tag: Arbitrary + Mergable,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        let elements: Vec<(Key, Tag)> = Arbitrary::arbitrary(g);
+        let mut sets = Self::new();
+        let mut keys = Vec::with_capacity(elements.len());
+        for (key, tag) in elements {
+            if sets.make_set(key.clone(), tag).is_ok() {
+                keys.push(key);
+            }
+        }
+        if !keys.is_empty() {
+            let unions: Vec<(usize, usize)> = Arbitrary::arbitrary(g);
+            for (x, y) in unions {
+                let _ = sets.unite(&keys[x % keys.len()], &keys[y % keys.len()]);
+            }
+        }
+        sets
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let groups: Vec<(Vec<Key>, Tag)> =
+            self.iter().map(|xs| xs.to_owned().into_parts()).collect();
+        let n = groups.len();
+        let dropped = {
+            let groups = groups.clone();
+            (0..n).map(move |i| {
+                let mut groups = groups.clone();
+                groups.remove(i);
+                from_groups(groups)
+            })
+        };
+        let split = {
+            let groups = groups.clone();
+            (0..n).filter_map(move |i| {
+                if groups[i].0.len() < 2 {
+                    return None;
+                }
+                let mut groups = groups.clone();
+                let (xs, tag) = &mut groups[i];
+                let ys = xs.split_off(xs.len() / 2);
+                let tag = tag.clone();
+                groups.push((ys, tag));
+                Some(from_groups(groups))
+            })
+        };
+        let thinned = (0..n).filter_map(move |i| {
+            if groups[i].0.len() < 2 {
+                return None;
+            }
+            let mut groups = groups.clone();
+            groups[i].0.pop();
+            Some(from_groups(groups))
+        });
+        Box::new(dropped.chain(split).chain(thinned))
+    }
+}
+
+/// Rebuilds sets of the given elements, each of which is tagged exactly by the given tag.
+fn from_groups<Key, Tag>(groups: Vec<(Vec<Key>, Tag)>) -> UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable + Clone,
+{
+    let mut sets = UnionFindSets::new();
+    for (xs, tag) in groups {
+        let Some(first) = xs.first().cloned() else {
+            continue;
+        };
+        for x in xs {
+            let _ = sets.make_set(x.clone(), tag.clone());
+            let _ = sets.unite(&first, &x);
+        }
+        if let Some(mut set) = sets.find_mut(&first) {
+            *set.tag_mut() = tag;
+        }
+    }
+    sets
+}
@@ -0,0 +1,154 @@
+use super::{Allocator, Node, SetId, SizedTag, UnionFindSets, UnionStats};
+use crate::Mergable;
+use alloc::vec::Vec;
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::cell::RefCell;
+use core::hash::Hash;
+use hashbrown::HashMap;
+
+/// Ids of sets are not written, and are given anew by [UnionFindSets].
+impl<Tag: BorshSerialize> BorshSerialize for SizedTag<Tag> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        (self.size as u64).serialize(writer)?;
+        self.tag.serialize(writer)
+    }
+}
+
+impl<Tag: BorshDeserialize> BorshDeserialize for SizedTag<Tag> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let size = u64::deserialize_reader(reader)?;
+        let size = usize::try_from(size).map_err(|_| Error::from(ErrorKind::InvalidData))?;
+        let tag = Tag::deserialize_reader(reader)?;
+        Ok(Self {
+            size,
+            id: SetId(0),
+            tag,
+        })
+    }
+}
+
+impl<Key: BorshSerialize, Tag: BorshSerialize> BorshSerialize for Node<Key, Tag> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        match self {
+            Node::Parent(parent) => {
+                0u8.serialize(writer)?;
+                parent.borrow().serialize(writer)
+            }
+            Node::Root(tag) => {
+                1u8.serialize(writer)?;
+                tag.serialize(writer)
+            }
+        }
+    }
+}
+
+impl<Key: BorshDeserialize, Tag: BorshDeserialize> BorshDeserialize for Node<Key, Tag> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        match u8::deserialize_reader(reader)? {
+            0 => Ok(Node::Parent(RefCell::new(Key::deserialize_reader(reader)?))),
+            1 => Ok(Node::Root(SizedTag::deserialize_reader(reader)?)),
+            _ => Err(invalid_data("unknown kind of node")),
+        }
+    }
+}
+
+/// Nodes are written in key order, so equal sets of sets
+/// with equal internal forests always produce equal bytes.
+impl<Key, Tag, A> BorshSerialize for UnionFindSets<Key, Tag, A>
+where
+    Key: Eq + Hash + Ord + BorshSerialize,
+    Tag: Mergable + BorshSerialize,
+    A: Allocator,
+{
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let mut nodes: Vec<_> = self.nodes.iter().collect();
+        nodes.sort_by_key(|(key, _)| *key);
+        u32::try_from(nodes.len())
+            .map_err(|_| Error::from(ErrorKind::InvalidData))?
+            .serialize(writer)?;
+        for node in nodes {
+            node.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// The decoded forest is checked before it is handed out:
+/// every parent chain must end at a root,
+/// and every root must count exactly the keys beneath it.
+impl<Key, Tag> BorshDeserialize for UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Ord + Clone + BorshDeserialize,
+    Tag: Mergable + BorshDeserialize,
+{
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let entries: Vec<(Key, Node<Key, Tag>)> = BorshDeserialize::deserialize_reader(reader)?;
+        let mut nodes = HashMap::with_capacity_and_hasher(entries.len(), ahash::RandomState::new());
+        for (key, node) in entries {
+            if nodes.insert(key, node).is_some() {
+                return Err(invalid_data("duplicated key"));
+            }
+        }
+        let sets = validate(&nodes)?;
+        let mut next_id = 0;
+        for node in nodes.values_mut() {
+            if let Node::Root(tag) = node {
+                tag.id = SetId(next_id);
+                next_id += 1;
+            }
+        }
+        Ok(Self {
+            nodes,
+            sets,
+            next_id,
+            // counts of unions are not written, and start over
+            stats: UnionStats::default(),
+        })
+    }
+}
+
+/// Returns the number of sets in a valid forest.
+fn validate<Key, Tag>(nodes: &HashMap<Key, Node<Key, Tag>, ahash::RandomState>) -> Result<usize>
+where
+    Key: Eq + Hash,
+{
+    let mut sizes: HashMap<&Key, usize, ahash::RandomState> =
+        HashMap::with_hasher(ahash::RandomState::new());
+    for node in nodes.values() {
+        let Node::Parent(parent) = node else {
+            continue;
+        };
+        let mut top = parent.borrow();
+        let mut hops = 1;
+        let top = loop {
+            match nodes.get_key_value(&*top) {
+                Some((top, Node::Root(_))) => break top,
+                Some((_, Node::Parent(nxt))) => {
+                    hops += 1;
+                    if hops > nodes.len() {
+                        return Err(invalid_data("cyclic parent chain"));
+                    }
+                    top = nxt.borrow();
+                }
+                None => return Err(invalid_data("parent chain ends outside of the keys")),
+            }
+        };
+        *sizes.entry(top).or_insert(1) += 1;
+    }
+    let mut sets = 0;
+    for (key, node) in nodes.iter() {
+        let Node::Root(tag) = node else {
+            continue;
+        };
+        sets += 1;
+        if sizes.get(key).copied().unwrap_or(1) != tag.size {
+            return Err(invalid_data("set size does not match its elements"));
+        }
+    }
+    Ok(sets)
+}
+
+fn invalid_data(msg: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
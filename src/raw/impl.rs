@@ -1,25 +1,32 @@
-use std::borrow::Borrow;
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::hash::Hash;
-
-pub trait Mergable {
-    fn merge(&mut self, other: Self);
-}
-
-impl Mergable for () {
-    fn merge(&mut self, _other: Self) {}
-}
+use crate::error::found_both;
+use crate::instrumentation::{count_compressed_pointer, count_find, count_union};
+use crate::{Error, HeapSize, Mergable, Result};
+use alloc::vec::Vec;
+use allocator_api2::alloc::{Allocator, Global};
+use core::borrow::Borrow;
+use core::cell::RefCell;
+use core::cmp::Ordering;
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use hashbrown::hash_map::{Entry, HashMap, RawEntryMut};
+use hashbrown::HashSet;
 
 #[derive(Debug, Clone)]
 pub(crate) struct SizedTag<Tag> {
-    size: usize,
-    tag: Tag,
+    pub(crate) size: usize,
+    pub(crate) id: SetId,
+    pub(crate) tag: Tag,
 }
 
 impl<T> SizedTag<T> {
-    fn new(tag: T) -> Self {
-        Self { size: 1, tag }
+    pub(crate) fn new(id: SetId, tag: T) -> Self {
+        Self { size: 1, id, tag }
+    }
+}
+
+impl<T: HeapSize> HeapSize for SizedTag<T> {
+    fn heap_size(&self) -> usize {
+        self.tag.heap_size()
     }
 }
 
@@ -30,15 +37,88 @@ impl<T: Mergable> Mergable for SizedTag<T> {
     }
 }
 
+/// An id of an individual set, which stays the same until the set is merged into another.
+///
+/// Ids are only meaningful within the sets (of sets) they come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SetId(pub(crate) u64);
+
+/// Counts of outcomes of unions tried on sets, see [UnionFindSets::union_stats].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UnionStats {
+    /// Unions which really united two sets.
+    pub unions: u64,
+    /// Unions of two keys already in a same set, i.e., which returned `Ok(false)`.
+    pub redundant: u64,
+    /// Unions which failed as either key is not in the sets.
+    pub missing: u64,
+}
+
+impl UnionStats {
+    /// Gets the share of redundant unions among those whose keys are both found,
+    /// or 0 if there is none.
+    pub fn redundancy(&self) -> f64 {
+        let found = self.unions + self.redundant;
+        if found == 0 {
+            0.0
+        } else {
+            self.redundant as f64 / found as f64
+        }
+    }
+}
+
+/// What a union did, see [UnionFindSets::unite_with_outcome].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UniteOutcome<'a, Key> {
+    /// Both keys were already in a same set.
+    Unchanged,
+    /// Two sets were united, where `winner` stays the representative
+    /// and `loser` is no longer one.
+    Merged { winner: &'a Key, loser: &'a Key },
+}
+
+/// Roots of a union, told by their addresses and hashes,
+/// which stay valid as long as no key is inserted or removed.
+struct Merged<Key> {
+    parent: (*const Key, u64),
+    child: (*const Key, u64),
+}
+
+/// A key in the forest: either a child pointing at its parent,
+/// or a root carrying the tag of its whole set.
+///
+/// Parents sit in `RefCell`s, so that paths can be compressed through a shared reference.
+#[derive(Debug, Clone)]
+pub(crate) enum Node<Key, Tag> {
+    Parent(RefCell<Key>),
+    Root(SizedTag<Tag>),
+}
+
+impl<Key: HeapSize, Tag: HeapSize> HeapSize for Node<Key, Tag> {
+    fn heap_size(&self) -> usize {
+        match self {
+            Node::Parent(parent) => parent.borrow().heap_size(),
+            Node::Root(tag) => tag.heap_size(),
+        }
+    }
+}
+
 /// Raw implementation of union-find sets, with built-in balanced union and path compression.
+///
+/// The storage of the forest is allocated by `A`,
+/// e.g., an arena which is freed wholesale, see [new_in](Self::new_in).
 #[derive(Clone)]
-pub struct UnionFindSets<Key, Tag>
+pub struct UnionFindSets<Key, Tag, A = Global>
 where
     Key: Eq + Hash,
     Tag: Mergable,
+    A: Allocator,
 {
-    parents: RefCell<HashMap<Key, Key, ahash::RandomState>>,
-    tags: HashMap<Key, SizedTag<Tag>, ahash::RandomState>,
+    pub(crate) nodes: HashMap<Key, Node<Key, Tag>, ahash::RandomState, A>,
+    pub(crate) sets: usize,
+    /// Id of the next set to make.
+    pub(crate) next_id: u64,
+    pub(crate) stats: UnionStats,
 }
 
 /// An individual set (of elements) without the ability to iterate over elements.
@@ -48,17 +128,38 @@ where
     Key: Eq,
 {
     pub(crate) key: &'a Key,
-    pub(crate) tag: &'a SizedTag<Tag>,
+    pub(crate) id: SetId,
+    pub(crate) size: usize,
+    pub(crate) tag: &'a Tag,
 }
 
+/// Sets are compared by their ids,
+/// so sets from different sets (of sets) are not comparable.
 impl<'a, Key: Eq + Hash, Tag> PartialEq for Set<'a, Key, Tag> {
     fn eq(&self, other: &Self) -> bool {
-        self.key.eq(other.key)
+        self.id == other.id
     }
 }
 
 impl<'a, Key: Eq + Hash, Tag> Eq for Set<'a, Key, Tag> {}
 
+impl<'a, Key: Eq + Hash, Tag> Hash for Set<'a, Key, Tag> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<'a, Key: Eq, Tag> Set<'a, Key, Tag> {
+    fn new(key: &'a Key, tag: &'a SizedTag<Tag>) -> Self {
+        Self {
+            key,
+            id: tag.id,
+            size: tag.size,
+            tag: &tag.tag,
+        }
+    }
+}
+
 impl<'a, Key, Tag> Set<'a, Key, Tag>
 where
     Key: Eq + Hash,
@@ -66,7 +167,61 @@ where
 {
     /// Queries the number of elements in this set.
     pub fn len(&self) -> usize {
-        self.tag.size
+        self.size
+    }
+
+    /// Tests if this set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the representative element
+    pub fn key(&self) -> &'a Key {
+        self.key
+    }
+
+    /// Gets the id of this set.
+    pub fn id(&self) -> SetId {
+        self.id
+    }
+
+    /// Gets the customized tag associated with this set.
+    pub fn tag(&self) -> &'a Tag {
+        self.tag
+    }
+}
+
+/// An individual set whose tag can be updated in place.
+#[derive(Debug)]
+pub struct SetMut<'a, Key, Tag>
+where
+    Key: Eq,
+{
+    pub(crate) key: &'a Key,
+    pub(crate) id: SetId,
+    pub(crate) size: usize,
+    pub(crate) tag: &'a mut Tag,
+}
+
+impl<'a, Key: Eq, Tag> SetMut<'a, Key, Tag> {
+    fn new(key: &'a Key, tag: &'a mut SizedTag<Tag>) -> Self {
+        Self {
+            key,
+            id: tag.id,
+            size: tag.size,
+            tag: &mut tag.tag,
+        }
+    }
+}
+
+impl<'a, Key, Tag> SetMut<'a, Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    /// Queries the number of elements in this set.
+    pub fn len(&self) -> usize {
+        self.size
     }
 
     /// Tests if this set is empty.
@@ -79,12 +234,59 @@ where
         self.key
     }
 
+    /// Gets the id of this set.
+    pub fn id(&self) -> SetId {
+        self.id
+    }
+
     /// Gets the customized tag associated with this set.
     pub fn tag(&self) -> &Tag {
-        &self.tag.tag
+        self.tag
+    }
+
+    /// Gets the customized tag associated with this set, mutably.
+    pub fn tag_mut(&mut self) -> &mut Tag {
+        self.tag
+    }
+}
+
+/// An iterator over all individual sets, see [UnionFindSets::iter].
+pub struct SetsIter<'a, Key, Tag> {
+    nodes: hashbrown::hash_map::Iter<'a, Key, Node<Key, Tag>>,
+    remaining: usize,
+}
+
+impl<'a, Key, Tag> Clone for SetsIter<'a, Key, Tag> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, Key: Eq, Tag> Iterator for SetsIter<'a, Key, Tag> {
+    type Item = Set<'a, Key, Tag>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (key, node) in self.nodes.by_ref() {
+            if let Node::Root(tag) = node {
+                self.remaining = self.remaining.saturating_sub(1);
+                return Some(Set::new(key, tag));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
 
+impl<'a, Key: Eq, Tag> ExactSizeIterator for SetsIter<'a, Key, Tag> {}
+
+impl<'a, Key: Eq, Tag> FusedIterator for SetsIter<'a, Key, Tag> {}
+
 impl<Key, Tag> Default for UnionFindSets<Key, Tag>
 where
     Key: Eq + Hash + Clone,
@@ -102,131 +304,641 @@ where
 {
     /// Makes a new, empty set of sets.
     pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<Key, Tag, A> UnionFindSets<Key, Tag, A>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+    A: Allocator,
+{
+    /// Makes a new, empty set of sets, whose storage is allocated by `alloc`.
+    ///
+    /// For example, with `bumpalo` (and its `allocator-api2` feature),
+    /// `UnionFindSets::new_in(&bump)` puts the whole forest into the arena `bump`.
+    /// Keys and tags may still own allocations of their own.
+    pub fn new_in(alloc: A) -> Self {
         Self {
-            parents: RefCell::new(HashMap::with_hasher(ahash::RandomState::new())),
-            tags: HashMap::with_hasher(ahash::RandomState::new()),
+            nodes: HashMap::with_hasher_in(ahash::RandomState::new(), alloc),
+            sets: 0,
+            next_id: 0,
+            stats: UnionStats::default(),
         }
     }
 
     /// Makes an individual set with a singleton element and its associated tag.
     ///
     /// If the set to make is already there,
-    /// an error will be raised and nothing will happen to the sets.
-    pub fn make_set(&mut self, key: Key, tag: Tag) -> anyhow::Result<()> {
-        {
-            let parents = self.parents.borrow();
-            if parents.contains_key(&key) {
-                anyhow::bail!("Duplicated key!");
+    /// [Error::DuplicateKey](crate::Error::DuplicateKey) will be raised
+    /// and nothing will happen to the sets.
+    pub fn make_set(&mut self, key: Key, tag: Tag) -> Result<()> {
+        match self.nodes.entry(key) {
+            Entry::Occupied(_) => return Err(Error::DuplicateKey),
+            Entry::Vacant(entry) => {
+                entry.insert(Node::Root(SizedTag::new(SetId(self.next_id), tag)));
             }
         }
-        if self.tags.contains_key(&key) {
-            anyhow::bail!("Duplicated key!");
-        }
-        self.tags.insert(key, SizedTag::new(tag));
+        self.sets += 1;
+        self.next_id += 1;
         Ok(())
     }
 
-    /// Unites two sets.
+    /// Makes an individual set with a singleton element and the default tag,
+    /// like [make_set](Self::make_set).
+    pub fn make_set_default(&mut self, key: Key) -> Result<()>
+    where
+        Tag: Default,
+    {
+        self.make_set(key, Tag::default())
+    }
+
+    /// Makes individual sets of all the keys with default tags,
+    /// stopping at the first error, e.g., [Error::DuplicateKey](crate::Error::DuplicateKey),
+    /// where sets made before it are kept.
+    pub fn make_sets_default<I>(&mut self, keys: I) -> Result<()>
+    where
+        Tag: Default,
+        I: IntoIterator<Item = Key>,
+    {
+        keys.into_iter()
+            .try_for_each(|key| self.make_set_default(key))
+    }
+
+    /// Unites two sets, given by any borrowed forms of their keys.
     ///
-    /// If either of them is not in the sets, an error will be raised;
+    /// If either of them is not in the sets,
+    /// [Error::KeyNotFound](crate::Error::KeyNotFound) will be raised, telling which are missing;
     /// if they are of a same set, `Ok(false)` will be returns;
     /// otherwise, which means these two sets are really united into one in this case,
     /// `Ok(true)` will be returned.
-    pub fn unite<K1, K2>(&mut self, key1: &K1, key2: &K2) -> anyhow::Result<bool>
+    ///
+    /// If `Hash`, `Eq` or `Clone` of keys disagree with each other,
+    /// [Error::Inconsistent](crate::Error::Inconsistent) may be raised
+    /// and nothing will happen to the sets.
+    pub fn unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.unite_choosing(key1, key2, |(_, tag1), (_, tag2)| tag1.size > tag2.size)
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but tells which representative survives and which is merged away,
+    /// e.g., to update indexes keyed by representatives.
+    ///
+    /// ```
+    /// use tagged_ufs::raw::{UniteOutcome, UnionFindSets};
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..3u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &1).unwrap();
+    /// let top = *sets.find(&0).unwrap().key();
+    /// assert_eq!(
+    ///     sets.unite_with_outcome(&2, &1).unwrap(),
+    ///     UniteOutcome::Merged { winner: &top, loser: &2 },
+    /// );
+    /// assert_eq!(sets.unite_with_outcome(&0, &2).unwrap(), UniteOutcome::Unchanged);
+    /// ```
+    pub fn unite_with_outcome<Q1, Q2>(
+        &mut self,
+        key1: &Q1,
+        key2: &Q2,
+    ) -> Result<UniteOutcome<'_, Key>>
     where
-        K1: Hash + Eq + Borrow<Key> + std::fmt::Debug,
-        K2: Hash + Eq + Borrow<Key> + std::fmt::Debug,
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
     {
-        let Some(key1_top) = self.find_top_key(key1) else {
-            anyhow::bail!("Cannot find set: {:?}", key1);
+        let merged = self.unite_at(key1, key2, |(_, tag1), (_, tag2)| tag1.size > tag2.size)?;
+        let Some(Merged { parent, child }) = merged else {
+            return Ok(UniteOutcome::Unchanged);
         };
-        let Some(key2_top) = self.find_top_key(key2) else {
-            anyhow::bail!("Cannot find set: {:?}", key2);
+        let lookup = |(ptr, hash): (*const Key, u64)| {
+            self.nodes
+                .raw_entry()
+                .from_hash(hash, |key| core::ptr::eq(key, ptr))
+                .map(|(key, _)| key)
+                .ok_or(Error::Inconsistent)
         };
+        Ok(UniteOutcome::Merged {
+            winner: lookup(parent)?,
+            loser: lookup(child)?,
+        })
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but the representative of the set with the higher `priority` of tags survives,
+    /// whichever set is larger; on ties, the larger set wins as usual.
+    ///
+    /// Sets are no longer balanced by sizes, so long paths may be built,
+    /// though they are still compressed by finding.
+    ///
+    /// ```
+    /// use tagged_ufs::raw::UnionFindSets;
+    /// use tagged_ufs::Max;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// sets.make_set("golden", Max(9)).unwrap();
+    /// for x in ["a", "b", "c"] {
+    ///     sets.make_set(x, Max(1)).unwrap();
+    /// }
+    /// sets.unite(&"a", &"b").unwrap();
+    /// sets.unite(&"b", &"c").unwrap();
+    /// sets.unite_by_priority(&"c", &"golden", |tag| tag.0).unwrap();
+    /// assert_eq!(sets.find(&"a").unwrap().key(), &"golden");
+    /// ```
+    pub fn unite_by_priority<Q1, Q2, P, F>(
+        &mut self,
+        key1: &Q1,
+        key2: &Q2,
+        mut priority: F,
+    ) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+        P: Ord,
+        F: FnMut(&Tag) -> P,
+    {
+        self.unite_choosing(key1, key2, |(_, tag1), (_, tag2)| {
+            match priority(&tag1.tag).cmp(&priority(&tag2.tag)) {
+                Ordering::Greater => true,
+                Ordering::Less => false,
+                Ordering::Equal => tag1.size > tag2.size,
+            }
+        })
+    }
+
+    /// Unites two sets, where the top of the first survives iff `first_wins` on the two tops.
+    pub(crate) fn unite_choosing<Q1, Q2, F>(
+        &mut self,
+        key1: &Q1,
+        key2: &Q2,
+        first_wins: F,
+    ) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+        F: FnOnce((&Key, &SizedTag<Tag>), (&Key, &SizedTag<Tag>)) -> bool,
+    {
+        self.unite_at(key1, key2, first_wins)
+            .map(|merged| merged.is_some())
+    }
+
+    /// Unites two sets like [unite_choosing](Self::unite_choosing),
+    /// telling where the two roots are if they are really united.
+    fn unite_at<Q1, Q2, F>(
+        &mut self,
+        key1: &Q1,
+        key2: &Q2,
+        first_wins: F,
+    ) -> Result<Option<Merged<Key>>>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+        F: FnOnce((&Key, &SizedTag<Tag>), (&Key, &SizedTag<Tag>)) -> bool,
+    {
+        let ((key1_top, key1_tag), (key2_top, key2_tag)) =
+            match found_both(self.find_top_key(key1), self.find_top_key(key2)) {
+                Ok(tops) => tops,
+                Err(err) => {
+                    self.stats.missing += 1;
+                    return Err(err);
+                }
+            };
         if key1_top == key2_top {
-            return Ok(false);
-        }
-        let key1_top = key1_top.clone();
-        let key2_top = key2_top.clone();
-        let mut key1_tag = self.tags.remove(&key1_top).unwrap();
-        let mut key2_tag = self.tags.remove(&key2_top).unwrap();
-        let parent_key1 = key1_tag.size > key2_tag.size;
-        let mut parents = self.parents.borrow_mut();
-        if parent_key1 {
-            key1_tag.merge(key2_tag);
-            parents.insert(key2_top, key1_top.clone());
-            self.tags.insert(key1_top, key1_tag);
+            self.stats.redundant += 1;
+            count_union(false);
+            return Ok(None);
+        }
+        let (parent, child) = if first_wins((key1_top, key1_tag), (key2_top, key2_tag)) {
+            (key1_top, key2_top)
         } else {
-            key2_tag.merge(key1_tag);
-            parents.insert(key1_top, key2_top.clone());
-            self.tags.insert(key2_top, key2_tag);
+            (key2_top, key1_top)
+        };
+        // the only clone, which the losing root will point at
+        let link = parent.clone();
+        if link != *parent {
+            return Err(Error::Inconsistent);
         }
-        Ok(true)
+        let parent_hash = self.nodes.hasher().hash_one(parent);
+        let child_hash = self.nodes.hasher().hash_one(child);
+        let merged = Merged {
+            parent: (parent, parent_hash),
+            child: (child, child_hash),
+        };
+        self.link_roots(&merged, link)?;
+        Ok(Some(merged))
+    }
+
+    /// Links the root `merged.child` to the root `merged.parent`, whose key `link` is a clone of.
+    ///
+    /// Both roots are looked up by address, so that their keys are neither cloned nor compared.
+    /// If either is not a root, nothing will happen and [Error::Inconsistent] will be raised.
+    fn link_roots(&mut self, merged: &Merged<Key>, link: Key) -> Result<()> {
+        let (parent, parent_hash) = merged.parent;
+        let (child, child_hash) = merged.child;
+        let RawEntryMut::Occupied(mut child_entry) = self
+            .nodes
+            .raw_entry_mut()
+            .from_hash(child_hash, |key| core::ptr::eq(key, child))
+        else {
+            return Err(Error::Inconsistent);
+        };
+        let child_tag =
+            match core::mem::replace(child_entry.get_mut(), Node::Parent(RefCell::new(link))) {
+                Node::Root(tag) => tag,
+                node => {
+                    *child_entry.get_mut() = node;
+                    return Err(Error::Inconsistent);
+                }
+            };
+        let mut child_tag = Some(child_tag);
+        if let RawEntryMut::Occupied(mut parent_entry) = self
+            .nodes
+            .raw_entry_mut()
+            .from_hash(parent_hash, |key| core::ptr::eq(key, parent))
+        {
+            if let Node::Root(parent_tag) = parent_entry.get_mut() {
+                if let Some(tag) = child_tag.take() {
+                    parent_tag.merge(tag);
+                }
+            }
+        }
+        if let Some(tag) = child_tag {
+            // the parent is gone, so the child is restored as the root it was
+            if let RawEntryMut::Occupied(mut child_entry) = self
+                .nodes
+                .raw_entry_mut()
+                .from_hash(child_hash, |key| core::ptr::eq(key, child))
+            {
+                *child_entry.get_mut() = Node::Root(tag);
+            }
+            return Err(Error::Inconsistent);
+        }
+        self.sets -= 1;
+        self.stats.unions += 1;
+        count_union(true);
+        Ok(())
+    }
+
+    /// Unites sets of all the keys into one, returning how many unions really happened.
+    ///
+    /// Each key is found once, and the roots found are linked right to the largest one.
+    /// If any key is not in the sets, nothing will happen and
+    /// [Error::KeyNotFoundAt](crate::Error::KeyNotFoundAt) will be raised,
+    /// telling the index of the first missing key.
+    ///
+    /// ```
+    /// use tagged_ufs::raw::UnionFindSets;
+    /// use tagged_ufs::Error;
+    ///
+    /// let mut sets = UnionFindSets::new();
+    /// for x in 0..5u32 {
+    ///     sets.make_set(x, ()).unwrap();
+    /// }
+    /// sets.unite(&0, &1).unwrap();
+    /// assert_eq!(sets.unite_many(&[1, 2, 0, 3]).unwrap(), 2);
+    /// assert_eq!(sets.len(), 2);
+    /// assert_eq!(sets.unite_many(&[4, 7]), Err(Error::KeyNotFoundAt { index: 1 }));
+    /// ```
+    pub fn unite_many<Q>(&mut self, keys: &[Q]) -> Result<usize>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let mut seen = hashbrown::HashSet::with_hasher(ahash::RandomState::new());
+        let mut tops = Vec::new();
+        for (index, key) in keys.iter().enumerate() {
+            let Some((top, tag)) = self.find_top_key(key) else {
+                self.stats.missing += 1;
+                return Err(Error::KeyNotFoundAt { index });
+            };
+            if seen.insert(top as *const Key) {
+                tops.push((top, tag.size));
+            }
+        }
+        let Some(largest) = (0..tops.len()).max_by_key(|i| tops[*i].1) else {
+            return Ok(0);
+        };
+        let (winner, _) = tops.swap_remove(largest);
+        let winner_at = (winner as *const Key, self.nodes.hasher().hash_one(winner));
+        // a clone of the winner for every loser to point at, as with `unite`
+        let mut links = Vec::with_capacity(tops.len());
+        for (loser, _) in tops {
+            let link = winner.clone();
+            if link != *winner {
+                return Err(Error::Inconsistent);
+            }
+            let merged = Merged {
+                parent: winner_at,
+                child: (loser, self.nodes.hasher().hash_one(loser)),
+            };
+            links.push((merged, link));
+        }
+        let mut merges = 0;
+        for (merged, link) in links {
+            self.link_roots(&merged, link)?;
+            merges += 1;
+        }
+        Ok(merges)
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but simply returns `None` if either of them is not in the sets.
+    pub fn try_unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Option<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.unite(key1, key2).ok()
     }
 
     /// Finds an individual set.
     ///
+    /// As with `HashMap::get`, `key` may be any borrowed form of `Key`, e.g., `&str` for `String`.
     /// If the set is not inside, `None` will be returned.
-    pub fn find<K>(&self, key: &K) -> Option<Set<Key, Tag>>
+    pub fn find<Q>(&self, key: &Q) -> Option<Set<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, tag) = self.find_top_key(key)?;
+        Some(Set::new(key, tag))
+    }
+
+    /// Tests whether `key` has been made a set of, i.e., is an element of any set.
+    ///
+    /// Unlike [find](Self::find), it neither walks nor compresses paths.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.nodes.contains_key(key)
+    }
+
+    /// Tells whether `key` is the root of its tree, i.e., the representative of its set,
+    /// or `None` if it is not in the sets.
+    pub fn is_root<Q>(&self, key: &Q) -> Option<bool>
     where
-        K: Eq + Hash + Borrow<Key>,
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
     {
-        let key_top = self.find_top_key(key)?;
-        let tag = self.tags.get(key_top).unwrap();
-        Some(Set { key: key_top, tag })
+        Some(matches!(self.nodes.get(key)?, Node::Root(_)))
+    }
+
+    /// Counts links on the path from `key` up to its root, without compressing the path,
+    /// or returns `None` if it is not in the sets.
+    ///
+    /// It probes how deep trees grow, e.g., under different orders of unions.
+    pub fn depth<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut node = self.nodes.get(key)?;
+        let mut depth = 0;
+        while let Node::Parent(parent) = node {
+            node = self.nodes.get(&*parent.borrow())?;
+            depth += 1;
+        }
+        Some(depth)
+    }
+
+    /// Gets the tag of the set containing `key`, without making a view of the set.
+    ///
+    /// If the key is not in the sets, `None` will be returned.
+    pub fn tag_of<Q>(&self, key: &Q) -> Option<&Tag>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find_top_key(key).map(|(_, tag)| &tag.tag)
+    }
+
+    /// Finds an individual set, like [find](Self::find),
+    /// but its tag can be updated through [SetMut::tag_mut].
+    pub fn find_mut<Q>(&mut self, key: &Q) -> Option<SetMut<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // the root is looked up again by address rather than by a clone of its key
+        let top = self.find_top_key(key)?.0;
+        let hash = self.nodes.hasher().hash_one(top);
+        let top: *const Key = top;
+        let RawEntryMut::Occupied(entry) = self
+            .nodes
+            .raw_entry_mut()
+            .from_hash(hash, |key| core::ptr::eq(key, top))
+        else {
+            return None;
+        };
+        match entry.into_key_value() {
+            (key, Node::Root(tag)) => Some(SetMut::new(key, tag)),
+            (_, Node::Parent(_)) => None,
+        }
     }
 
     /// Iterates over all individual sets.
-    pub fn iter(&self) -> impl Iterator<Item = Set<Key, Tag>> {
-        self.tags.iter().map(|(key, tag)| Set { key, tag })
+    pub fn iter(&self) -> SetsIter<'_, Key, Tag> {
+        SetsIter {
+            nodes: self.nodes.iter(),
+            remaining: self.sets,
+        }
+    }
+
+    /// Iterates over every element along with the representative of its set,
+    /// compressing paths as elements are visited.
+    pub fn iter_pairs(&self) -> impl Iterator<Item = (&Key, &Key)> + '_ {
+        self.nodes
+            .iter()
+            .filter_map(|(key, _)| Some((key, self.find_top_key(key)?.0)))
+    }
+
+    /// Calls `f` on the tag of every set, along with its representative,
+    /// so that tags can be updated in place.
+    pub fn for_each_tag_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Key, &mut Tag),
+    {
+        for (key, node) in self.nodes.iter_mut() {
+            if let Node::Root(tag) = node {
+                f(key, &mut tag.tag);
+            }
+        }
+    }
+
+    /// Counts outcomes of all unions tried since the sets were made
+    /// or [reset_union_stats](Self::reset_union_stats) was called.
+    pub fn union_stats(&self) -> UnionStats {
+        self.stats
+    }
+
+    /// Resets counts of outcomes of unions to zero.
+    pub fn reset_union_stats(&mut self) {
+        self.stats = UnionStats::default();
     }
 
     /// Queries the number of individual sets in the set.
     pub fn len(&self) -> usize {
-        self.tags.len()
+        self.sets
     }
 
     /// Tests if this set (of sets) is empty.
     pub fn is_empty(&self) -> bool {
-        self.tags.is_empty()
+        self.sets == 0
     }
 
-    fn find_top_key<K>(&self, key: &K) -> Option<&Key>
+    /// Converts tags of all sets, given their representatives, keeping the sets as they are.
+    pub fn map_tags<U, F>(self, mut f: F) -> UnionFindSets<Key, U, A>
     where
-        K: Hash + Eq + Borrow<Key>,
+        U: Mergable,
+        F: FnMut(&Key, Tag) -> U,
+        A: Clone,
     {
-        self.find_top_key_(key.borrow())
+        let mut nodes = HashMap::with_capacity_and_hasher_in(
+            self.nodes.len(),
+            self.nodes.hasher().clone(),
+            self.nodes.allocator().clone(),
+        );
+        for (key, node) in self.nodes {
+            let node = match node {
+                Node::Parent(parent) => Node::Parent(parent),
+                Node::Root(SizedTag { size, id, tag }) => Node::Root(SizedTag {
+                    size,
+                    id,
+                    tag: f(&key, tag),
+                }),
+            };
+            nodes.insert(key, node);
+        }
+        UnionFindSets {
+            nodes,
+            sets: self.sets,
+            next_id: self.next_id,
+            stats: self.stats,
+        }
     }
 
-    fn find_top_key_(&self, key: &Key) -> Option<&Key> {
-        let mut keys = vec![];
-        let top = {
-            let parents = self.parents.borrow();
-            self.collect_keys(key, &mut keys, parents.borrow())?
+    /// Makes a whole set at once, whose first key becomes the representative.
+    ///
+    /// If any key is already there, or listed twice,
+    /// [Error::DuplicateKey](crate::Error::DuplicateKey) will be raised and the sets are left as they are.
+    pub(crate) fn insert_set(&mut self, keys: Vec<Key>, tag: Tag) -> Result<()> {
+        let mut listed = HashSet::with_capacity_and_hasher(keys.len(), ahash::RandomState::new());
+        for key in keys.iter() {
+            if self.nodes.contains_key(key) || !listed.insert(key) {
+                return Err(Error::DuplicateKey);
+            }
+        }
+        drop(listed);
+        let mut keys = keys.into_iter();
+        let Some(top) = keys.next() else {
+            return Ok(());
         };
-        keys.pop();
-        if !keys.is_empty() {
-            let mut parents = self.parents.borrow_mut();
-            while let Some(mid_key) = keys.pop() {
-                parents.insert(mid_key, top.clone());
+        let size = 1 + keys.len();
+        for key in keys {
+            self.nodes
+                .insert(key, Node::Parent(RefCell::new(top.clone())));
+        }
+        let id = SetId(self.next_id);
+        self.nodes
+            .insert(top, Node::Root(SizedTag { size, id, tag }));
+        self.sets += 1;
+        self.next_id += 1;
+        Ok(())
+    }
+
+    /// Consumes the sets, yielding the tag of every individual set.
+    pub(crate) fn into_tags(self) -> impl Iterator<Item = Tag> {
+        self.into_roots().map(|(_, tag)| tag)
+    }
+
+    /// Consumes the sets, yielding the representative and the tag of every individual set.
+    pub(crate) fn into_roots(self) -> impl Iterator<Item = (Key, Tag)> {
+        self.nodes.into_iter().filter_map(|(key, node)| match node {
+            Node::Root(tag) => Some((key, tag.tag)),
+            Node::Parent(_) => None,
+        })
+    }
+
+    /// Compresses the path from `key` in a second walk,
+    /// so that keys on the path are moved out of their nodes rather than cloned;
+    /// only the top key is cloned, once per rewritten parent.
+    /// Nor is the path collected into a buffer, so finding never allocates.
+    fn find_top_key<Q>(&self, key: &Q) -> Option<(&Key, &SizedTag<Tag>)>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let start = self.nodes.get_key_value(key)?;
+        let (top, tag) = self.top_key(start)?;
+        let mut path_len = 0;
+        let mut node = Some(start.1);
+        while let Some(Node::Parent(parent)) = node {
+            path_len += 1;
+            if *parent.borrow() == *top {
+                break;
             }
+            let mid_key = parent.replace(top.clone());
+            count_compressed_pointer();
+            node = self.nodes.get(&mid_key);
         }
-        Some(top)
+        count_find(path_len);
+        Some((top, tag))
     }
 
-    fn collect_keys(
-        &self,
-        key: &Key,
-        keys: &mut Vec<Key>,
-        parents: &HashMap<Key, Key, ahash::RandomState>,
-    ) -> Option<&Key> {
-        if let Some(nxt_key) = parents.get(key) {
-            keys.push(key.clone());
-            self.collect_keys(nxt_key, keys, parents)
-        } else if let Some((top, _)) = self.tags.get_key_value(key) {
-            Some(top)
-        } else {
-            None
+    /// Gets the id of the set containing `key`, without compressing the path.
+    #[cfg(feature = "borsh")]
+    pub(crate) fn peek_id<Q>(&self, key: &Q) -> Option<SetId>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let start = self.nodes.get_key_value(key)?;
+        self.top_key(start).map(|(_, tag)| tag.id)
+    }
+
+    fn top_key<'a>(
+        &'a self,
+        mut node: (&'a Key, &'a Node<Key, Tag>),
+    ) -> Option<(&'a Key, &'a SizedTag<Tag>)> {
+        loop {
+            match node {
+                (top, Node::Root(tag)) => return Some((top, tag)),
+                (_, Node::Parent(parent)) => {
+                    node = self.nodes.get_key_value(&*parent.borrow())?;
+                }
+            }
         }
     }
 }
+
+impl<Key, Tag, A> UnionFindSets<Key, Tag, A>
+where
+    Key: Eq + Hash + HeapSize,
+    Tag: Mergable + HeapSize,
+    A: Allocator,
+{
+    /// Reports the number of bytes on heap owned by these sets,
+    /// including the table of the forest and whatever keys and tags own.
+    pub fn heap_size(&self) -> usize {
+        self.nodes.allocation_size()
+            + self
+                .nodes
+                .iter()
+                .map(|(key, node)| key.heap_size() + node.heap_size())
+                .sum::<usize>()
+    }
+}
@@ -3,6 +3,10 @@
 
 mod r#impl;
 pub use self::r#impl::*;
+pub use crate::Mergable;
+pub use allocator_api2::alloc::{Allocator, Global};
+#[cfg(feature = "borsh")]
+mod borsh;
 
 #[cfg(test)]
 pub(crate) mod test;
@@ -42,6 +42,243 @@ fn add_connect_query(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<(u8, u
     }
 }
 
+#[test]
+fn unite_reports_missing_keys() {
+    let mut sets = UnionFindSets::new();
+    sets.make_set(0, ()).unwrap();
+    let missing = |which| Err(crate::Error::KeyNotFound { which });
+    assert_eq!(sets.unite(&1, &0), missing(crate::Which::Key1));
+    assert_eq!(sets.unite(&0, &2), missing(crate::Which::Key2));
+    assert_eq!(sets.unite(&1, &2), missing(crate::Which::Both));
+}
+
+#[test]
+fn try_unite_missing_keys() {
+    let mut sets = UnionFindSets::new();
+    sets.make_set(0, ()).unwrap();
+    sets.make_set(1, ()).unwrap();
+    assert_eq!(sets.try_unite(&0, &2), None);
+    assert_eq!(sets.try_unite(&0, &1), Some(true));
+    assert_eq!(sets.try_unite(&1, &0), Some(false));
+}
+
+#[test]
+fn inconsistent_keys_do_not_panic() {
+    #[derive(PartialEq, Eq, Hash)]
+    struct Forgetful(u8);
+
+    impl Clone for Forgetful {
+        fn clone(&self) -> Self {
+            Forgetful(self.0.wrapping_add(100))
+        }
+    }
+
+    let mut sets = UnionFindSets::new();
+    sets.make_set(Forgetful(0), ()).unwrap();
+    sets.make_set(Forgetful(1), ()).unwrap();
+    assert_eq!(
+        sets.unite(&Forgetful(0), &Forgetful(1)),
+        Err(crate::Error::Inconsistent)
+    );
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets.find(&Forgetful(0)).unwrap().len(), 1);
+}
+
+#[test]
+fn vanishing_parent_keeps_child() {
+    use std::cell::Cell;
+    use std::hash::{Hash, Hasher};
+
+    /// Hashes honestly only the first time, if moody.
+    struct Moody {
+        id: u8,
+        moody: bool,
+        hashed: Cell<bool>,
+    }
+
+    impl Moody {
+        fn new(id: u8, moody: bool) -> Self {
+            Self {
+                id,
+                moody,
+                hashed: Cell::new(false),
+            }
+        }
+    }
+
+    impl PartialEq for Moody {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Moody {}
+
+    impl Hash for Moody {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            let lying = self.moody && self.hashed.replace(true);
+            state.write_u8(if lying { !self.id } else { self.id });
+        }
+    }
+
+    impl Clone for Moody {
+        fn clone(&self) -> Self {
+            Self::new(self.id, self.moody)
+        }
+    }
+
+    let mut sets = UnionFindSets::new();
+    sets.make_set(Moody::new(0, false), ()).unwrap();
+    sets.make_set(Moody::new(1, true), ()).unwrap();
+    // of two sets as large, the second one wins, whose root cannot be found again by address
+    assert_eq!(
+        sets.unite(&Moody::new(0, false), &Moody::new(1, false)),
+        Err(crate::Error::Inconsistent)
+    );
+    assert_eq!(sets.len(), 2);
+    let set_0 = sets.find(&Moody::new(0, false)).unwrap();
+    assert_eq!(set_0.len(), 1);
+    assert_eq!(set_0.key().id, 0);
+    assert_eq!(sets.union_stats().unions, 0);
+}
+
+#[test]
+fn allocate_in_arena() {
+    let bump = bumpalo::Bump::new();
+    let mut sets = UnionFindSets::new_in(&bump);
+    for x in 0..100u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    for x in 1..100u32 {
+        sets.unite(&(x - 1), &x).unwrap();
+    }
+    assert_eq!(sets.len(), 1);
+    assert_eq!(sets.find(&42).unwrap().len(), 100);
+    assert!(bump.allocated_bytes() > 0);
+}
+
+#[test]
+fn insert_set_with_existing_keys() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..3u32 {
+        sets.make_set(x, crate::Count(1)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    // an existing key partway, as the representative, and listed twice
+    for keys in [vec![5, 6, 1, 7], vec![2, 8, 9], vec![8, 9, 8]] {
+        assert_eq!(
+            sets.insert_set(keys, crate::Count(3)),
+            Err(crate::Error::DuplicateKey)
+        );
+        assert_eq!(sets.len(), 2);
+        for x in 5..10u32 {
+            assert!(!sets.contains(&x));
+            assert!(sets.find(&x).is_none());
+        }
+        assert_eq!(sets.find(&1).unwrap().len(), 2);
+        assert_eq!(sets.find(&2).unwrap().len(), 1);
+    }
+    sets.insert_set(vec![8, 9], crate::Count(2)).unwrap();
+    assert_eq!(sets.len(), 3);
+    assert_eq!(sets.find(&9).unwrap().key(), &8);
+    assert_eq!(sets.find(&9).unwrap().len(), 2);
+}
+
+#[test]
+fn update_tags() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, crate::Count(0)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    let mut set = sets.find_mut(&1).unwrap();
+    assert_eq!(set.len(), 2);
+    set.tag_mut().0 += 5;
+    sets.find_mut(&2).unwrap().tag_mut().0 += 1;
+    assert!(sets.find_mut(&4).is_none());
+    sets.unite(&2, &0).unwrap();
+    assert_eq!(sets.find(&0).unwrap().tag(), &crate::Count(6));
+    assert_eq!(sets.find(&3).unwrap().tag(), &crate::Count(0));
+}
+
+#[test]
+fn map_tags() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, vec![x]).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &1).unwrap();
+    let sets = sets.map_tags(|_, xs| crate::Sum(xs.iter().sum::<u32>()));
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets.find(&0).unwrap().tag(), &crate::Sum(3));
+    assert_eq!(sets.find(&0).unwrap().len(), 3);
+    assert_eq!(sets.find(&3).unwrap().tag(), &crate::Sum(3));
+    let sets = sets.map_tags(|key, _| crate::Min(*key));
+    assert_eq!(
+        sets.find(&2).unwrap().tag().0,
+        *sets.find(&1).unwrap().key()
+    );
+}
+
+#[test]
+fn for_each_tag_mut() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, crate::Sum(x)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    let mut visited = 0;
+    sets.for_each_tag_mut(|_, tag| {
+        tag.0 *= 10;
+        visited += 1;
+    });
+    assert_eq!(visited, 3);
+    assert_eq!(sets.find(&1).unwrap().tag(), &crate::Sum(10));
+    assert_eq!(sets.find(&3).unwrap().tag(), &crate::Sum(30));
+}
+
+#[test]
+fn exact_size_iteration() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..10u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    for x in 1..5u32 {
+        sets.unite(&0, &x).unwrap();
+    }
+    let mut iter = sets.iter();
+    assert_eq!(iter.len(), 6);
+    let first = iter.next().unwrap();
+    let rest = iter.clone();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.by_ref().count(), 5);
+    assert!(iter.next().is_none());
+    assert_eq!(rest.map(|xs| xs.len()).sum::<usize>() + first.len(), 10);
+}
+
+#[test]
+fn stable_set_ids() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..6u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    let ids: Vec<_> = (0..6u32).map(|x| sets.find(&x).unwrap().id()).collect();
+    let distinct: std::collections::HashSet<_> = ids.iter().collect();
+    assert_eq!(distinct.len(), 6);
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &1).unwrap();
+    let id = sets.find(&0).unwrap().id();
+    assert!(ids[..3].contains(&id));
+    assert_eq!(sets.find(&2).unwrap().id(), id);
+    assert_eq!(sets.find(&5).unwrap().id(), ids[5]);
+    let found: std::collections::HashSet<_> = (0..6u32).map(|x| sets.find(&x).unwrap()).collect();
+    assert_eq!(found.len(), 4);
+    let iterated: std::collections::HashSet<_> = sets.iter().map(|xs| xs.id()).collect();
+    assert!(iterated.contains(&id));
+    assert_eq!(iterated.len(), 4);
+}
+
 pub(crate) struct Oracle {
     sets: Vec<Vec<u8>>,
 }
@@ -89,6 +326,10 @@ impl Oracle {
         None
     }
 
+    pub(crate) fn remove(&mut self, key: u8) -> Option<Vec<u8>> {
+        self.pop(key).ok()
+    }
+
     fn pop(&mut self, key: u8) -> anyhow::Result<Vec<u8>> {
         let key_index = (|| {
             for (i, xs) in self.sets.iter().enumerate() {
@@ -105,3 +346,120 @@ impl Oracle {
         }
     }
 }
+
+#[test]
+fn unite_clones_one_key() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Counted(u8, Rc<Cell<usize>>);
+
+    impl PartialEq for Counted {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Counted {}
+
+    impl core::hash::Hash for Counted {
+        fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+            self.0.hash(state);
+        }
+    }
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            self.1.set(self.1.get() + 1);
+            Counted(self.0, self.1.clone())
+        }
+    }
+
+    let clones = Rc::new(Cell::new(0));
+    let key = |x| Counted(x, clones.clone());
+    let mut sets = UnionFindSets::new();
+    for x in 0..4 {
+        sets.make_set(key(x), ()).unwrap();
+    }
+    sets.unite(&key(0), &key(1)).unwrap();
+    sets.unite(&key(2), &key(3)).unwrap();
+    sets.unite(&key(0), &key(2)).unwrap();
+    assert_eq!(clones.get(), 3);
+    assert!(!sets.unite(&key(1), &key(3)).unwrap());
+    assert_eq!(sets.find_mut(&key(3)).unwrap().len(), 4);
+    assert_eq!(clones.get(), 3);
+    assert_eq!(sets.find(&key(0)).unwrap().len(), 4);
+}
+
+#[quickcheck]
+fn pair_elements_with_representatives(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds.iter() {
+        let _ = sets.make_set(*x, ());
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    let pairs: Vec<_> = sets.iter_pairs().map(|(x, rep)| (*x, *rep)).collect();
+    let distinct: std::collections::HashSet<_> = adds.iter().collect();
+    assert_eq!(pairs.len(), distinct.len());
+    for (x, rep) in pairs {
+        assert_eq!(sets.find(&x).unwrap().key(), &rep);
+    }
+}
+
+#[quickcheck]
+fn unite_many_as_chained_unions(adds: Vec<u8>, connects: Vec<(u8, u8)>, batch: Vec<u8>) {
+    let mut trial = UnionFindSets::new();
+    let mut oracle = Oracle::new();
+    for x in adds {
+        let _ = trial.make_set(x, ());
+        let _ = oracle.make_set(x);
+    }
+    for (x, y) in connects {
+        let _ = trial.unite(&x, &y);
+        let _ = oracle.unite(x, y);
+    }
+    let missing = batch.iter().position(|x| oracle.find(x).is_none());
+    let sets = trial.len();
+    let merges = trial.unite_many(&batch);
+    if let Some(index) = missing {
+        assert_eq!(merges, Err(crate::Error::KeyNotFoundAt { index }));
+        assert_eq!(trial.len(), sets);
+        return;
+    }
+    let mut oracle_merges = 0;
+    for y in batch.iter().skip(1) {
+        if oracle.unite(batch[0], *y).unwrap() {
+            oracle_merges += 1;
+        }
+    }
+    assert_eq!(merges.unwrap(), oracle_merges);
+    assert_eq!(trial.len(), sets - oracle_merges);
+    for x in batch.iter() {
+        assert_eq!(trial.find(x).unwrap().len(), oracle.find(x).unwrap().len());
+    }
+}
+
+#[quickcheck]
+fn depth_without_compression(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<u8>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, ());
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    for x in queries {
+        let Some(depth) = sets.depth(&x) else {
+            assert!(sets.is_root(&x).is_none());
+            continue;
+        };
+        assert_eq!(sets.is_root(&x), Some(depth == 0));
+        // probing does not compress, which finding then does
+        assert_eq!(sets.depth(&x), Some(depth));
+        let top = *sets.find(&x).unwrap().key();
+        assert_eq!(sets.depth(&x), Some(usize::from(top != x)));
+        assert_eq!(sets.is_root(&top), Some(true));
+    }
+}
@@ -0,0 +1,60 @@
+//! Parallel ingestion of edges by [rayon](https://docs.rs/rayon).
+
+use crate::{Mergable, UnionFindSets};
+use ::rayon::iter::{IntoParallelIterator, ParallelExtend, ParallelIterator};
+use core::hash::Hash;
+
+type Shard<Key> = crate::raw::UnionFindSets<Key, ()>;
+
+/// Unites both ends of every edge, making sets with default tags for unseen ends.
+///
+/// Edges are first united into shards, one per rayon job, which are merged pairwise
+/// and finally merged into `self`, so `self` is touched by a single thread only.
+impl<Key, Tag> ParallelExtend<(Key, Key)> for UnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone + Send,
+    Tag: Mergable + Default,
+{
+    fn par_extend<I>(&mut self, edges: I)
+    where
+        I: IntoParallelIterator<Item = (Key, Key)>,
+    {
+        let shard = edges
+            .into_par_iter()
+            .fold(Shard::new, |mut shard, (x, y)| {
+                add_edge(&mut shard, x, y);
+                shard
+            })
+            .reduce(Shard::new, merge_shards);
+        for (x, rep) in shard.iter_pairs() {
+            for key in [x, rep] {
+                if self.find(key).is_none() {
+                    let _ = self.make_set(key.clone(), Tag::default());
+                }
+            }
+            let _ = self.unite(x, rep);
+        }
+    }
+}
+
+fn add_edge<Key: Eq + Hash + Clone>(shard: &mut Shard<Key>, x: Key, y: Key) {
+    if shard.find(&x).is_none() {
+        let _ = shard.make_set(x.clone(), ());
+    }
+    if shard.find(&y).is_none() {
+        let _ = shard.make_set(y.clone(), ());
+    }
+    let _ = shard.unite(&x, &y);
+}
+
+fn merge_shards<Key: Eq + Hash + Clone>(a: Shard<Key>, b: Shard<Key>) -> Shard<Key> {
+    let (mut large, small) = if a.nodes.len() >= b.nodes.len() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    for (x, rep) in small.iter_pairs() {
+        add_edge(&mut large, x.clone(), rep.clone());
+    }
+    large
+}
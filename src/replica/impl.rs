@@ -0,0 +1,169 @@
+use crate::ops::Op;
+use crate::{Result, Semilattice, UnionFindSets};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+/// Identifies a replica; every replica must be given a distinct one.
+pub type ReplicaId = u64;
+
+/// A version vector, telling how many mutations of every replica have been seen.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Version {
+    seen: BTreeMap<ReplicaId, u64>,
+}
+
+impl Version {
+    /// Queries how many mutations of a replica have been seen.
+    pub fn get(&self, replica: ReplicaId) -> u64 {
+        self.seen.get(&replica).copied().unwrap_or(0)
+    }
+
+    /// Iterates over replicas and their numbers of seen mutations.
+    pub fn iter(&self) -> impl Iterator<Item = (ReplicaId, u64)> + '_ {
+        self.seen.iter().map(|(replica, seen)| (*replica, *seen))
+    }
+}
+
+/// Mutations a replica has but another has not seen, in causal order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delta<Key, Tag> {
+    entries: Vec<Entry<Key, Tag>>,
+}
+
+/// A mutation, stamped by its origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry<Key, Tag> {
+    origin: ReplicaId,
+    seq: u64,
+    op: Op<Key, Tag>,
+}
+
+impl<Key, Tag> Delta<Key, Tag> {
+    /// Queries the number of mutations in the delta.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Tests if there is nothing new in the delta.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over mutations, along with their origins.
+    pub fn iter(&self) -> impl Iterator<Item = (ReplicaId, &Op<Key, Tag>)> + '_ {
+        self.entries.iter().map(|x| (x.origin, &x.op))
+    }
+}
+
+/// A replica of sets, whose tags are joined as a [Semilattice],
+/// so that replicas converge whatever orders deltas are applied in.
+#[derive(Clone)]
+pub struct Replica<Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Semilattice,
+{
+    id: ReplicaId,
+    sets: UnionFindSets<Key, Tag>,
+    log: Vec<Entry<Key, Tag>>,
+    version: Version,
+}
+
+impl<Key, Tag> Replica<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Semilattice,
+{
+    /// Makes a new, empty replica.
+    pub fn new(id: ReplicaId) -> Self {
+        Self {
+            id,
+            sets: UnionFindSets::new(),
+            log: Vec::new(),
+            version: Version::default(),
+        }
+    }
+
+    /// Gets the id of this replica.
+    pub fn id(&self) -> ReplicaId {
+        self.id
+    }
+
+    /// Gets the replicated sets.
+    pub fn sets(&self) -> &UnionFindSets<Key, Tag> {
+        &self.sets
+    }
+
+    /// Gets the version this replica is at.
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Makes a set like [UnionFindSets::make_set], logging it if it succeeds.
+    pub fn make_set(&mut self, key: Key, tag: Tag) -> Result<()> {
+        self.sets.make_set(key.clone(), tag.clone())?;
+        self.log_local(Op::MakeSet(key, tag));
+        Ok(())
+    }
+
+    /// Unites sets like [UnionFindSets::unite], logging it if sets are really united.
+    pub fn unite(&mut self, key1: &Key, key2: &Key) -> Result<bool> {
+        let united = self.sets.unite(key1, key2)?;
+        if united {
+            self.log_local(Op::Unite(key1.clone(), key2.clone()));
+        }
+        Ok(united)
+    }
+
+    /// Collects mutations not seen at `version`, e.g., the version of another replica.
+    pub fn delta_since(&self, version: &Version) -> Delta<Key, Tag> {
+        let entries = self
+            .log
+            .iter()
+            .filter(|x| x.seq > version.get(x.origin))
+            .cloned()
+            .collect();
+        Delta { entries }
+    }
+
+    /// Applies mutations of a delta which are not seen yet.
+    ///
+    /// Made sets whose keys are already there get their tags joined.
+    /// If a union refers to keys that are not there,
+    /// which means some delta from their origins is missed,
+    /// [Error::KeyNotFound](crate::Error::KeyNotFound) will be raised
+    /// and mutations after it are left unapplied.
+    pub fn apply_delta(&mut self, delta: Delta<Key, Tag>) -> Result<()> {
+        for entry in delta.entries {
+            let seen = self.version.get(entry.origin);
+            if entry.seq <= seen {
+                continue;
+            }
+            match &entry.op {
+                Op::MakeSet(key, tag) => match self.sets.find_mut(key) {
+                    Some(mut set) => set.tag_mut().merge(tag.clone()),
+                    None => self.sets.make_set(key.clone(), tag.clone())?,
+                },
+                Op::Unite(x, y) => {
+                    self.sets.unite(x, y)?;
+                }
+                // replicas never log removals, which could not be joined
+                Op::Find(_) | Op::Remove(_) => {}
+            }
+            self.version.seen.insert(entry.origin, entry.seq);
+            self.log.push(entry);
+        }
+        Ok(())
+    }
+
+    fn log_local(&mut self, op: Op<Key, Tag>) {
+        let seq = self.version.get(self.id) + 1;
+        self.version.seen.insert(self.id, seq);
+        self.log.push(Entry {
+            origin: self.id,
+            seq,
+            op,
+        });
+    }
+}
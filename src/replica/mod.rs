@@ -0,0 +1,12 @@
+//! Replicas of sets, synchronized by exchanging deltas instead of whole states.
+//!
+//! Every replica logs its own mutations and those it receives,
+//! each stamped by the replica it originates from and a sequence number there.
+//! A version vector tells how much of every replica's log has been seen,
+//! so only what is newer has to be sent.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
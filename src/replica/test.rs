@@ -0,0 +1,68 @@
+use super::*;
+use quickcheck_macros::*;
+use std::collections::BTreeSet;
+
+type State = BTreeSet<(BTreeSet<u8>, BTreeSet<u8>)>;
+
+fn state(replica: &Replica<u8, BTreeSet<u8>>) -> State {
+    replica
+        .sets()
+        .iter()
+        .map(|xs| (xs.iter().copied().collect(), xs.tag().clone()))
+        .collect()
+}
+
+#[quickcheck]
+fn replicas_converge(ops: Vec<(u8, bool, u8, u8)>, syncs: Vec<(u8, u8)>) {
+    let mut replicas: Vec<_> = (0..3).map(Replica::new).collect();
+    let mut syncs = syncs.into_iter().cycle();
+    for (i, (at, make, x, y)) in ops.into_iter().enumerate() {
+        let replica = &mut replicas[at as usize % 3];
+        if make {
+            let _ = replica.make_set(x % 16, BTreeSet::from([y]));
+        } else {
+            let _ = replica.unite(&(x % 16), &(y % 16));
+        }
+        if i % 4 == 0 {
+            if let Some((from, to)) = syncs.next() {
+                let (from, to) = (from as usize % 3, to as usize % 3);
+                let delta = replicas[from].delta_since(replicas[to].version());
+                replicas[to].apply_delta(delta).unwrap();
+            }
+        }
+    }
+    let mut expected = replicas[0].sets().clone();
+    expected.join(replicas[1].sets()).unwrap();
+    expected.join(replicas[2].sets()).unwrap();
+    for (from, to) in [(0, 1), (1, 2), (2, 0), (0, 1)] {
+        let delta = replicas[from].delta_since(replicas[to].version());
+        replicas[to].apply_delta(delta).unwrap();
+    }
+    for replica in replicas.iter() {
+        assert_eq!(state(replica), state(&replicas[0]));
+        assert!(replica.delta_since(replicas[0].version()).is_empty());
+    }
+    let expected: State = expected
+        .iter()
+        .map(|xs| (xs.iter().copied().collect(), xs.tag().clone()))
+        .collect();
+    assert_eq!(state(&replicas[0]), expected);
+}
+
+#[test]
+fn send_only_unseen() {
+    let mut a = Replica::new(1);
+    let mut b = Replica::new(2);
+    a.make_set(0, BTreeSet::from([0])).unwrap();
+    a.make_set(1, BTreeSet::from([1])).unwrap();
+    b.apply_delta(a.delta_since(b.version())).unwrap();
+    assert_eq!(b.version().get(1), 2);
+    a.unite(&0, &1).unwrap();
+    assert!(!a.unite(&1, &0).unwrap());
+    let delta = a.delta_since(b.version());
+    assert_eq!(delta.len(), 1);
+    b.apply_delta(delta.clone()).unwrap();
+    b.apply_delta(delta).unwrap();
+    assert_eq!(b.sets().find(&0).unwrap().tag(), &BTreeSet::from([0, 1]));
+    assert!(b.delta_since(a.version()).is_empty());
+}
@@ -0,0 +1,139 @@
+use crate::compact::UnionFindSets;
+use crate::raw::UnionStats;
+use crate::{HeapSize, Result};
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+/// Union-find sets of keys without tags, with built-in balanced union and path compression.
+#[derive(Clone)]
+pub struct SimpleUnionFind<Key>
+where
+    Key: Eq + Hash,
+{
+    sets: UnionFindSets<Key, ()>,
+}
+
+impl<Key> Default for SimpleUnionFind<Key>
+where
+    Key: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Key> SimpleUnionFind<Key>
+where
+    Key: Eq + Hash + Clone,
+{
+    /// Makes a new, empty set of sets.
+    pub fn new() -> Self {
+        Self {
+            sets: UnionFindSets::new(),
+        }
+    }
+
+    /// Makes an individual set with a singleton element.
+    ///
+    /// If the set to make is already there, or there are too many keys,
+    /// [Error::DuplicateKey](crate::Error::DuplicateKey) or [Error::TooManyKeys](crate::Error::TooManyKeys)
+    /// will be raised respectively, and nothing will happen to the sets.
+    pub fn make_set(&mut self, key: Key) -> Result<()> {
+        self.sets.make_set(key, ())
+    }
+
+    /// Unites two sets, given by any borrowed forms of their keys.
+    ///
+    /// If either of them is not in the sets,
+    /// [Error::KeyNotFound](crate::Error::KeyNotFound) will be raised, telling which are missing;
+    /// if they are of a same set, `Ok(false)` will be returns;
+    /// otherwise, which means these two sets are really united into one in this case,
+    /// `Ok(true)` will be returned.
+    pub fn unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.sets.unite(key1, key2)
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but simply returns `None` if either of them is not in the sets.
+    pub fn try_unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Option<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.sets.try_unite(key1, key2)
+    }
+
+    /// Finds the representative key of the set containing `key`.
+    ///
+    /// If the set is not inside, `None` will be returned.
+    pub fn find<Q>(&self, key: &Q) -> Option<&Key>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.sets.find(key).map(|xs| xs.key())
+    }
+
+    /// Tests if two keys are in a same set.
+    ///
+    /// Keys not in the sets are never in a same set.
+    pub fn same_set<Q1, Q2>(&self, key1: &Q1, key2: &Q2) -> bool
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        matches!(
+            (self.sets.find(key1), self.sets.find(key2)),
+            (Some(xs), Some(ys)) if xs.id() == ys.id()
+        )
+    }
+
+    /// Queries the number of elements in the set containing `key`.
+    ///
+    /// If the set is not inside, `None` will be returned.
+    pub fn set_len<Q>(&self, key: &Q) -> Option<usize>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.sets.find(key).map(|xs| xs.len())
+    }
+
+    /// Iterates over representative keys of all individual sets.
+    pub fn representatives(&self) -> impl Iterator<Item = &Key> + '_ {
+        self.sets.iter().map(|xs| xs.key())
+    }
+
+    /// Counts outcomes of all unions tried since the sets were made.
+    pub fn union_stats(&self) -> UnionStats {
+        self.sets.union_stats()
+    }
+
+    /// Queries the number of individual sets in the set.
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Tests if this set (of sets) is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+}
+
+impl<Key> SimpleUnionFind<Key>
+where
+    Key: Eq + Hash + HeapSize,
+{
+    /// Reports the number of bytes on heap owned by these sets,
+    /// like [compact::UnionFindSets::heap_size](crate::compact::UnionFindSets::heap_size).
+    pub fn heap_size(&self) -> usize {
+        self.sets.heap_size()
+    }
+}
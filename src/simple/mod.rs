@@ -0,0 +1,13 @@
+//! Union-find sets without tags, for when only connectivity matters.
+//!
+//! It is a thin layer over [compact](crate::compact) sets with unit tags,
+//! which take no bytes, as roots are told by their links alone,
+//! and whose merges are empty and inlined away,
+//! so it only spares callers from passing and getting `()` around.
+//! It holds at most `2^31 - 1` keys.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,59 @@
+use super::*;
+use crate::ops::{Op, OpResult};
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn apply_like_iterable_sets(ops: Vec<(u8, u8, u8)>) {
+    let mut trial = SimpleUnionFind::new();
+    let mut oracle = crate::UnionFindSets::new();
+    for (kind, x, y) in ops {
+        let (x, y) = (x % 16, y % 16);
+        match oracle.apply(op(kind, x, y)) {
+            OpResult::MakeSet(res) => assert_eq!(trial.make_set(x), res),
+            OpResult::Unite(res) => {
+                assert_eq!(trial.unite(&x, &y), res);
+                let found = (oracle.find(&x), oracle.find(&y));
+                let same = matches!(found, (Some(xs), Some(ys)) if xs == ys);
+                assert_eq!(trial.same_set(&x, &y), same);
+            }
+            OpResult::Find(res) => {
+                assert_eq!(trial.set_len(&x), res.map(|(_, len)| len));
+                if let Some(top) = trial.find(&x) {
+                    assert!(oracle.find(&x).unwrap().contains(top));
+                }
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+    assert_eq!(trial.len(), oracle.len());
+    assert_eq!(trial.representatives().count(), oracle.len());
+    assert_eq!(trial.union_stats(), oracle.union_stats());
+}
+
+fn op(kind: u8, x: u8, y: u8) -> Op<u8, ()> {
+    match kind % 3 {
+        0 => Op::MakeSet(x, ()),
+        1 => Op::Unite(x, y),
+        _ => Op::Find(x),
+    }
+}
+
+#[test]
+fn borrowed_keys() {
+    let mut sets = SimpleUnionFind::new();
+    for x in ["a", "b", "c"] {
+        sets.make_set(x.to_string()).unwrap();
+    }
+    assert_eq!(
+        sets.make_set("a".to_string()),
+        Err(crate::Error::DuplicateKey)
+    );
+    assert!(sets.unite("a", "c").unwrap());
+    assert!(!sets.unite("c", "a").unwrap());
+    assert!(sets.same_set("a", "c"));
+    assert!(!sets.same_set("a", "b"));
+    assert!(!sets.same_set("a", "d"));
+    assert_eq!(sets.set_len("c"), Some(2));
+    assert_eq!(sets.len(), 2);
+    assert!(sets.heap_size() > 0);
+}
@@ -0,0 +1,195 @@
+use crate::Mergable;
+use core::cell::Cell;
+
+/// Marks a link as a root, whose remaining bits are the size of its set.
+/// Any other link is the index of the parent.
+const ROOT_BIT: u32 = 1 << 31;
+
+/// Union-find sets over indices `0..N`, with built-in balanced union and path compression.
+///
+/// Initially, every index is a singleton set.
+/// `N` must be less than `2^31`.
+///
+/// Like other sets in this crate, sets can be associated with mergable tags.
+/// The tag of a child is taken out (leaving a default one behind) when its set is united,
+/// so tags must be [Default].
+/// With the default `()` tags, the tag layer vanishes entirely.
+#[derive(Debug, Clone)]
+pub struct UnionFind<const N: usize, Tag = ()> {
+    links: [Cell<u32>; N],
+    tags: [Tag; N],
+    /// Skips over indices already united with their successors by [unite_range](Self::unite_range):
+    /// 0 means `i` is not yet, otherwise `i + runs[i]` is the next one to look at.
+    runs: [Cell<u32>; N],
+    sets: usize,
+}
+
+impl<const N: usize> Default for UnionFind<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> UnionFind<N> {
+    /// Makes `N` singleton sets.
+    pub const fn new() -> Self {
+        Self::with_tags([(); N])
+    }
+}
+
+impl<const N: usize, Tag> UnionFind<N, Tag> {
+    /// Makes `N` singleton sets, where the set of index `i` is associated with `tags[i]`.
+    pub const fn with_tags(tags: [Tag; N]) -> Self {
+        const { assert!(N < ROOT_BIT as usize, "too many indices") };
+        Self {
+            links: [const { Cell::new(ROOT_BIT | 1) }; N],
+            tags,
+            runs: [const { Cell::new(0) }; N],
+            sets: N,
+        }
+    }
+
+    /// Queries the number of indices, i.e., `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Queries the number of individual sets.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sets
+    }
+
+    /// Tests if there is no set at all, i.e., `N` is 0.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sets == 0
+    }
+
+    /// Finds the representative index of the set containing `key`.
+    ///
+    /// If `key` is out of `0..N`, `None` will be returned.
+    #[inline]
+    pub fn find(&self, key: usize) -> Option<usize> {
+        if key >= N {
+            return None;
+        }
+        let mut top = key;
+        while !self.is_root(top) {
+            top = self.links[top].get() as usize;
+        }
+        let mut cur = key;
+        while cur != top {
+            cur = self.links[cur].replace(top as u32) as usize;
+        }
+        Some(top)
+    }
+
+    /// Tests if two indices are in a same set.
+    ///
+    /// Indices out of `0..N` are never in a same set.
+    #[inline]
+    pub fn same_set(&self, key1: usize, key2: usize) -> bool {
+        match (self.find(key1), self.find(key2)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    }
+
+    /// Queries the number of elements in the set containing `key`.
+    ///
+    /// If `key` is out of `0..N`, `None` will be returned.
+    #[inline]
+    pub fn set_len(&self, key: usize) -> Option<usize> {
+        let top = self.find(key)?;
+        Some(self.size_of(top) as usize)
+    }
+
+    /// Gets the tag associated with the set containing `key`.
+    ///
+    /// If `key` is out of `0..N`, `None` will be returned.
+    #[inline]
+    pub fn tag(&self, key: usize) -> Option<&Tag> {
+        let top = self.find(key)?;
+        Some(&self.tags[top])
+    }
+
+    #[inline]
+    fn is_root(&self, key: usize) -> bool {
+        self.links[key].get() & ROOT_BIT != 0
+    }
+
+    #[inline]
+    fn size_of(&self, top: usize) -> u32 {
+        self.links[top].get() & !ROOT_BIT
+    }
+
+    /// Finds the first index at or after `key`, which is not yet united with its successor by ranges.
+    fn next_run(&self, key: usize) -> usize {
+        let mut top = key;
+        while let Some(skip) = self.runs.get(top).map(Cell::get).filter(|x| *x != 0) {
+            top += skip as usize;
+        }
+        let mut cur = key;
+        while cur < top {
+            let skip = self.runs[cur].replace((top - cur) as u32);
+            cur += skip as usize;
+        }
+        top
+    }
+}
+
+impl<const N: usize, Tag> UnionFind<N, Tag>
+where
+    Tag: Mergable + Default,
+{
+    /// Unites the sets containing two indices.
+    ///
+    /// If either index is out of `0..N`, `None` will be returned;
+    /// if they are of a same set, `Some(false)` will be returned;
+    /// otherwise `Some(true)` will be returned.
+    #[inline]
+    pub fn unite(&mut self, key1: usize, key2: usize) -> Option<bool> {
+        let key1_top = self.find(key1)?;
+        let key2_top = self.find(key2)?;
+        if key1_top == key2_top {
+            return Some(false);
+        }
+        let key1_size = self.size_of(key1_top);
+        let key2_size = self.size_of(key2_top);
+        let (parent, child) = if key1_size > key2_size {
+            (key1_top, key2_top)
+        } else {
+            (key2_top, key1_top)
+        };
+        let child_tag = core::mem::take(&mut self.tags[child]);
+        self.tags[parent].merge(child_tag);
+        self.links[parent].set(ROOT_BIT | (key1_size + key2_size));
+        self.links[child].set(parent as u32);
+        self.sets -= 1;
+        Some(true)
+    }
+
+    /// Unites all indices in `lo..=hi` into one set.
+    ///
+    /// Indices already united with their successors by earlier ranges are skipped,
+    /// so the total time of all calls is near-linear in `N`, regardless of how ranges overlap.
+    /// It returns how many sets are merged away.
+    /// If `lo..=hi` is not a range in `0..N`, `None` will be returned.
+    pub fn unite_range(&mut self, lo: usize, hi: usize) -> Option<usize> {
+        if lo > hi || hi >= N {
+            return None;
+        }
+        let mut merged = 0;
+        let mut key = self.next_run(lo);
+        while key < hi {
+            if self.unite(key, key + 1)? {
+                merged += 1;
+            }
+            self.runs[key].set(1);
+            key = self.next_run(key + 1);
+        }
+        Some(merged)
+    }
+}
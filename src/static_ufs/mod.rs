@@ -0,0 +1,10 @@
+//! Fixed-capacity union-find sets over indices, without any heap allocation.
+//!
+//! The whole forest lives inline in arrays, so it is available without `alloc`
+//! and can be placed in a `static` or on the stack of an interrupt handler.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,79 @@
+use super::*;
+use crate::raw::UnionFindSets;
+use crate::Mergable;
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn connect_query(connects: Vec<(u8, u8)>, queries: Vec<(u8, u8)>) {
+    let mut trial = UnionFind::<200>::new();
+    let mut oracle = UnionFindSets::new();
+    for x in 0..200u8 {
+        oracle.make_set(x, ()).unwrap();
+    }
+
+    for (x, y) in connects.into_iter() {
+        let trial_res = trial.unite(x as usize, y as usize);
+        let oracle_res = oracle.unite(&x, &y);
+        assert_eq!(trial_res, oracle_res.ok());
+    }
+    assert_eq!(trial.len(), oracle.len());
+
+    for (x, y) in queries.into_iter() {
+        let oracle_x = oracle.find(&x);
+        let oracle_y = oracle.find(&y);
+        assert_eq!(trial.find(x as usize).is_some(), oracle_x.is_some());
+        assert_eq!(
+            trial.set_len(x as usize),
+            oracle_x.as_ref().map(|xs| xs.len())
+        );
+        let oracle_same = match (oracle_x, oracle_y) {
+            (Some(xs), Some(ys)) => xs == ys,
+            _ => false,
+        };
+        assert_eq!(trial.same_set(x as usize, y as usize), oracle_same);
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Sum(usize);
+
+impl Mergable for Sum {
+    fn merge(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+#[test]
+fn merge_tags() {
+    let mut sets = UnionFind::<4, Sum>::with_tags([Sum(1), Sum(2), Sum(3), Sum(4)]);
+    assert_eq!(sets.unite(0, 1), Some(true));
+    assert_eq!(sets.unite(3, 1), Some(true));
+    assert_eq!(sets.tag(0), Some(&Sum(7)));
+    assert_eq!(sets.tag(2), Some(&Sum(3)));
+    assert_eq!(sets.tag(4), None);
+    assert_eq!(sets.len(), 2);
+}
+
+#[quickcheck]
+fn unite_ranges(ranges: Vec<(u8, u8)>, pairs: Vec<(u8, u8)>) {
+    let mut trial = UnionFind::<64>::new();
+    let mut oracle = UnionFind::<64>::new();
+    for ((lo, hi), (x, y)) in ranges.into_iter().zip(pairs) {
+        let (lo, hi) = (lo as usize % 70, hi as usize % 70);
+        let expected = (lo <= hi && hi < 64).then(|| {
+            let before = oracle.len();
+            for key in lo..hi {
+                oracle.unite(key, key + 1);
+            }
+            before - oracle.len()
+        });
+        assert_eq!(trial.unite_range(lo, hi), expected);
+        let (x, y) = (x as usize % 64, y as usize % 64);
+        assert_eq!(trial.unite(x, y), oracle.unite(x, y));
+    }
+    for x in 0..64 {
+        for y in 0..64 {
+            assert_eq!(trial.same_set(x, y), oracle.same_set(x, y));
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::ops::Op;
+use crate::{Mergable, UnionFindSets};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use proptest::collection::{vec, SizeRange};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+/// Generates an operation, whose keys and tags are drawn from the given strategies.
+pub fn arb_op<K, T>(key: K, tag: T) -> impl Strategy<Value = Op<K::Value, T::Value>>
+where
+    K: Strategy + Clone,
+    T: Strategy,
+{
+    prop_oneof![
+        (key.clone(), tag).prop_map(|(key, tag)| Op::MakeSet(key, tag)),
+        (key.clone(), key.clone()).prop_map(|(x, y)| Op::Unite(x, y)),
+        key.clone().prop_map(Op::Find),
+        key.prop_map(Op::Remove),
+    ]
+}
+
+/// Generates sequences of operations, whose lengths are in `len`.
+///
+/// Keys are drawn independently, so sequences also exercise
+/// duplicate keys and missing keys, especially if keys are drawn from a small domain.
+pub fn arb_ops<K, T>(
+    key: K,
+    tag: T,
+    len: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<Op<K::Value, T::Value>>>
+where
+    K: Strategy + Clone,
+    T: Strategy,
+{
+    vec(arb_op(key, tag), len)
+}
+
+/// Generates sets of elements whose keys and tags are drawn from the given strategies.
+///
+/// Numbers of both elements and unions are in `ops`.
+/// Unions are only between elements that are made, so shrinking moves toward fewer of them.
+pub fn arb_union_find_sets<K, T>(
+    key: K,
+    tag: T,
+    ops: impl Into<SizeRange>,
+) -> impl Strategy<Value = UnionFindSets<K::Value, T::Value>>
+where
+    K: Strategy,
+    K::Value: Eq + Hash + Clone,
+    T: Strategy,
+    T::Value: Mergable,
+{
+    let ops = ops.into();
+    (
+        vec((key, tag), ops.clone()),
+        vec(any::<(Index, Index)>(), ops),
+    )
+        .prop_map(|(elements, unions)| {
+            let mut sets = UnionFindSets::new();
+            let mut keys = Vec::with_capacity(elements.len());
+            for (key, tag) in elements {
+                if sets.make_set(key.clone(), tag).is_ok() {
+                    keys.push(key);
+                }
+            }
+            if !keys.is_empty() {
+                for (x, y) in unions {
+                    let _ = sets.unite(x.get(&keys), y.get(&keys));
+                }
+            }
+            sets
+        })
+}
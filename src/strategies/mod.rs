@@ -0,0 +1,7 @@
+//! [proptest](https://docs.rs/proptest) strategies of operations and of whole sets.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,46 @@
+use super::*;
+use crate::ops::Op;
+use crate::raw::test::Oracle;
+use crate::{Count, UnionFindSets};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn ops_against_oracle(ops in arb_ops(0..16u8, Just(()), 0..64)) {
+        let mut trial = UnionFindSets::new();
+        let mut oracle = Oracle::new();
+        for op in ops {
+            match op {
+                Op::MakeSet(x, tag) => {
+                    prop_assert_eq!(trial.make_set(x, tag).is_ok(), oracle.make_set(x).is_ok());
+                }
+                Op::Unite(x, y) => {
+                    let trial_res = trial.unite(&x, &y).ok();
+                    let oracle_res = oracle.unite(x, y).ok();
+                    prop_assert_eq!(trial_res, oracle_res);
+                }
+                Op::Find(x) => {
+                    let trial_len = trial.find(&x).map(|xs| xs.len());
+                    let oracle_len = oracle.find(&x).map(|xs| xs.len());
+                    prop_assert_eq!(trial_len, oracle_len);
+                }
+                Op::Remove(x) => {
+                    let trial_len = trial.take_set(&x).map(|(xs, _)| xs.len());
+                    let oracle_len = oracle.remove(x).map(|xs| xs.len());
+                    prop_assert_eq!(trial_len, oracle_len);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sets_are_consistent(sets in arb_union_find_sets(any::<u16>(), Just(Count(1)), 0..32)) {
+        let mut elements = 0;
+        for xs in sets.iter() {
+            prop_assert_eq!(xs.tag(), &Count(xs.len()));
+            prop_assert!(xs.iter().all(|x| xs.contains(x)));
+            elements += xs.len();
+        }
+        prop_assert!(elements <= 32);
+    }
+}
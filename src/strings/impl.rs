@@ -0,0 +1,146 @@
+use crate::compact::UnionFindSets;
+use crate::raw::{Set, SetMut};
+use crate::{Error, Mergable, Result};
+use alloc::boxed::Box;
+
+/// A string interned in [StringUnionFindSets].
+///
+/// Symbols are only meaningful within the sets they come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Gets the index of the symbol, which counts strings in the order they were interned.
+    pub fn index(self) -> u32 {
+        self.0
+    }
+}
+
+/// Union-find sets of strings, where every string is interned to a [Symbol].
+///
+/// ```
+/// use tagged_ufs::strings::StringUnionFindSets;
+///
+/// let mut sets = StringUnionFindSets::<()>::new();
+/// let a = sets.intern("alice").unwrap();
+/// let b = sets.intern("bob").unwrap();
+/// sets.unite(a, b).unwrap();
+/// sets.unite_str("bob", "carol").unwrap();
+/// assert_eq!(sets.resolve(a), Some("alice"));
+/// assert_eq!(sets.find(a).unwrap().len(), 3);
+/// ```
+#[derive(Clone)]
+pub struct StringUnionFindSets<Tag: Mergable> {
+    sets: UnionFindSets<Box<str>, Tag>,
+}
+
+impl<Tag: Mergable> Default for StringUnionFindSets<Tag> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Tag: Mergable> StringUnionFindSets<Tag> {
+    /// Makes a new, empty set of sets.
+    pub fn new() -> Self {
+        Self {
+            sets: UnionFindSets::new(),
+        }
+    }
+
+    /// Makes an individual set of a string and its associated tag, returning its symbol.
+    ///
+    /// If the string is already there, or there are too many strings,
+    /// [Error::DuplicateKey](crate::Error::DuplicateKey) or [Error::TooManyKeys](crate::Error::TooManyKeys)
+    /// will be raised respectively, and nothing will happen to the sets.
+    pub fn make_set(&mut self, name: &str, tag: Tag) -> Result<Symbol> {
+        if self.sets.id_of(name).is_some() {
+            return Err(Error::DuplicateKey);
+        }
+        self.sets.make_set_id(name.into(), tag).map(Symbol)
+    }
+
+    /// Gets the symbol of a string, which is interned with a default tag on first sight.
+    ///
+    /// If there are too many strings, [Error::TooManyKeys](crate::Error::TooManyKeys) will be raised.
+    pub fn intern(&mut self, name: &str) -> Result<Symbol>
+    where
+        Tag: Default,
+    {
+        match self.symbol(name) {
+            Some(symbol) => Ok(symbol),
+            None => self.make_set(name, Tag::default()),
+        }
+    }
+
+    /// Looks up the symbol of a string, without interning it.
+    pub fn symbol(&self, name: &str) -> Option<Symbol> {
+        self.sets.id_of(name).map(Symbol)
+    }
+
+    /// Resolves a symbol back into its string.
+    ///
+    /// If the symbol is not from these sets, `None` will be returned.
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.sets.key_of(symbol.0).map(|name| &**name)
+    }
+
+    /// Unites two sets, given by symbols of their strings.
+    ///
+    /// If either of them is not from these sets,
+    /// [Error::KeyNotFound](crate::Error::KeyNotFound) will be raised, telling which are missing;
+    /// if they are of a same set, `Ok(false)` will be returns;
+    /// otherwise `Ok(true)` will be returned.
+    pub fn unite(&mut self, symbol1: Symbol, symbol2: Symbol) -> Result<bool> {
+        self.sets.unite_ids(symbol1.0, symbol2.0)
+    }
+
+    /// Unites two sets, given by their strings, which are interned on first sight.
+    pub fn unite_str(&mut self, name1: &str, name2: &str) -> Result<bool>
+    where
+        Tag: Default,
+    {
+        let symbol1 = self.intern(name1)?;
+        let symbol2 = self.intern(name2)?;
+        self.unite(symbol1, symbol2)
+    }
+
+    /// Finds an individual set, given the symbol of an element.
+    ///
+    /// If the symbol is not from these sets, `None` will be returned.
+    pub fn find(&self, symbol: Symbol) -> Option<Set<'_, Box<str>, Tag>> {
+        self.sets.find_by_id(symbol.0)
+    }
+
+    /// Finds an individual set, like [find](Self::find),
+    /// but its tag can be updated through [SetMut::tag_mut].
+    pub fn find_mut(&mut self, symbol: Symbol) -> Option<SetMut<'_, Box<str>, Tag>> {
+        self.sets.find_mut_by_id(symbol.0)
+    }
+
+    /// Finds the symbol of the representative of the set containing `symbol`.
+    pub fn representative(&self, symbol: Symbol) -> Option<Symbol> {
+        let set = self.find(symbol)?;
+        Some(Symbol(set.id().0 as u32))
+    }
+
+    /// Queries the number of interned strings.
+    pub fn symbols(&self) -> usize {
+        self.sets.keys_len()
+    }
+
+    /// Iterates over all individual sets.
+    pub fn iter(&self) -> crate::compact::SetsIter<'_, Box<str>, Tag> {
+        self.sets.iter()
+    }
+
+    /// Queries the number of individual sets in the set.
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Tests if this set (of sets) is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+}
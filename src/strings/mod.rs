@@ -0,0 +1,11 @@
+//! Union-find sets of strings, which are interned to `u32` symbols on first sight.
+//!
+//! Strings are hashed only when they are interned or looked up by name;
+//! every other operation goes through symbols, which index the flat vectors of
+//! [compact](crate::compact) sets directly.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,65 @@
+use super::*;
+use crate::raw::test::Oracle;
+use crate::Error;
+use quickcheck_macros::*;
+
+#[quickcheck]
+fn add_connect_query(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<u8>) {
+    let mut trial = StringUnionFindSets::<()>::new();
+    let mut oracle = Oracle::new();
+
+    for x in adds.into_iter() {
+        let trial_res = trial.make_set(&x.to_string(), ());
+        let oracle_res = oracle.make_set(x);
+        assert_eq!(trial_res.is_ok(), oracle_res.is_ok());
+    }
+
+    for (x, y) in connects.into_iter() {
+        let symbols = (trial.symbol(&x.to_string()), trial.symbol(&y.to_string()));
+        match (symbols, oracle.unite(x, y)) {
+            ((Some(x), Some(y)), Ok(united)) => assert_eq!(trial.unite(x, y).unwrap(), united),
+            ((None, _) | (_, None), Err(_)) => (),
+            (symbols, oracle_res) => panic!("{:?} vs {:?}", symbols, oracle_res),
+        }
+    }
+
+    for x in queries.into_iter() {
+        let oracle_set = oracle.find(&x);
+        let trial_set = trial.symbol(&x.to_string()).and_then(|x| trial.find(x));
+        assert_eq!(trial_set.map(|xs| xs.len()), oracle_set.map(|xs| xs.len()));
+    }
+    assert_eq!(trial.len(), trial.iter().count());
+}
+
+#[test]
+fn intern_on_first_sight() {
+    let mut sets = StringUnionFindSets::<crate::Count>::new();
+    let a = sets.make_set("a", crate::Count(1)).unwrap();
+    assert_eq!(
+        sets.make_set("a", crate::Count(1)),
+        Err(Error::DuplicateKey)
+    );
+    assert_eq!(sets.intern("a"), Ok(a));
+    assert!(sets.unite_str("b", "c").unwrap());
+    assert_eq!(sets.symbols(), 3);
+    let c = sets.symbol("c").unwrap();
+    assert_eq!(sets.resolve(c), Some("c"));
+    assert!(sets.unite(a, c).unwrap());
+    assert!(!sets.unite_str("a", "b").unwrap());
+    assert_eq!(sets.find(c).unwrap().tag(), &crate::Count(1));
+    sets.find_mut(a).unwrap().tag_mut().0 += 1;
+    let rep = sets.representative(a).unwrap();
+    assert_eq!(sets.representative(c), Some(rep));
+    assert_eq!(sets.find(rep).unwrap().tag(), &crate::Count(2));
+    assert_eq!(sets.len(), 1);
+
+    let mut other = StringUnionFindSets::<()>::new();
+    let x = other.intern("x").unwrap();
+    let foreign = sets.symbol("c").unwrap();
+    assert!(other.resolve(foreign).is_none());
+    assert!(other.find(foreign).is_none());
+    assert!(matches!(
+        other.unite(x, foreign),
+        Err(Error::KeyNotFound { .. })
+    ));
+}
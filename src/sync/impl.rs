@@ -0,0 +1,167 @@
+use crate::compact::UnionFindSets;
+use crate::raw::Set;
+use crate::{Mergable, Result};
+use core::borrow::Borrow;
+use core::hash::Hash;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Sets which are `Send + Sync` as long as keys and tags are,
+/// so that they can be shared by `&` among threads.
+///
+/// Poisoning of the lock is ignored, so that a panic of one thread does not cascade.
+/// The sets are then as they would be after the panic is caught on a single thread.
+///
+/// ```
+/// use tagged_ufs::sync::SyncUnionFindSets;
+///
+/// let sets = SyncUnionFindSets::new();
+/// for x in 0..8u32 {
+///     sets.make_set(x, ()).unwrap();
+/// }
+/// std::thread::scope(|s| {
+///     for x in 0..4u32 {
+///         let sets = &sets;
+///         s.spawn(move || sets.unite(&x, &(x + 4)).unwrap());
+///     }
+/// });
+/// assert_eq!(sets.len(), 4);
+/// assert!(sets.same_set(&1, &5));
+/// ```
+pub struct SyncUnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    sets: RwLock<UnionFindSets<Key, Tag>>,
+}
+
+impl<Key, Tag> Default for SyncUnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Key, Tag> From<UnionFindSets<Key, Tag>> for SyncUnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    fn from(sets: UnionFindSets<Key, Tag>) -> Self {
+        Self {
+            sets: RwLock::new(sets),
+        }
+    }
+}
+
+impl<Key, Tag> SyncUnionFindSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Makes new, empty sets.
+    pub fn new() -> Self {
+        Self::from(UnionFindSets::new())
+    }
+
+    /// Takes the sets back out of the lock.
+    pub fn into_inner(self) -> UnionFindSets<Key, Tag> {
+        self.sets
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Makes an individual set with a singleton element and its associated tag,
+    /// like [UnionFindSets::make_set], under the write lock.
+    pub fn make_set(&self, key: Key, tag: Tag) -> Result<()> {
+        self.write().make_set(key, tag)
+    }
+
+    /// Unites two sets, like [UnionFindSets::unite], under the write lock.
+    pub fn unite<Q1, Q2>(&self, key1: &Q1, key2: &Q2) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Eq + Hash + ?Sized,
+        Q2: Eq + Hash + ?Sized,
+    {
+        self.write().unite(key1, key2)
+    }
+
+    /// Unites two sets, like [UnionFindSets::try_unite], under the write lock.
+    pub fn try_unite<Q1, Q2>(&self, key1: &Q1, key2: &Q2) -> Option<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Eq + Hash + ?Sized,
+        Q2: Eq + Hash + ?Sized,
+    {
+        self.write().try_unite(key1, key2)
+    }
+
+    /// Calls `f` on the set containing `key`, if any, under the read lock.
+    pub fn with_set<Q, F, R>(&self, key: &Q, f: F) -> Option<R>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        F: FnOnce(&Set<'_, Key, Tag>) -> R,
+    {
+        self.read().find(key).map(|xs| f(&xs))
+    }
+
+    /// Returns a copy of the representative of the set containing `key`, under the read lock.
+    pub fn representative<Q>(&self, key: &Q) -> Option<Key>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.with_set(key, |xs| xs.key().clone())
+    }
+
+    /// Tests whether two elements are in a same set, under the read lock.
+    /// Missing elements are in no set.
+    pub fn same_set<Q1, Q2>(&self, key1: &Q1, key2: &Q2) -> bool
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Eq + Hash + ?Sized,
+        Q2: Eq + Hash + ?Sized,
+    {
+        let sets = self.read();
+        match (sets.find(key1), sets.find(key2)) {
+            (Some(xs), Some(ys)) => xs == ys,
+            _ => false,
+        }
+    }
+
+    /// Tests whether `key` is an element of any set, under the read lock.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.read().contains(key)
+    }
+
+    /// Returns the number of sets.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Tests whether there is no set.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Locks the sets for reading, e.g., for a batch of finds,
+    /// which other threads may run meanwhile, too.
+    pub fn read(&self) -> RwLockReadGuard<'_, UnionFindSets<Key, Tag>> {
+        self.sets.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the sets for writing, e.g., for a batch of unions,
+    /// blocking other threads meanwhile.
+    pub fn write(&self) -> RwLockWriteGuard<'_, UnionFindSets<Key, Tag>> {
+        self.sets.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
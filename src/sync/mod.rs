@@ -0,0 +1,13 @@
+//! Sets shared among threads behind a read-write lock.
+//!
+//! Finds only take the lock for reading, so they run in parallel,
+//! and unions take it exclusively.
+//! The sets inside are [compact](crate::compact) ones, whose links are atomic,
+//! so that finds still compress paths under the read lock:
+//! no union runs meanwhile, and a link is only ever redirected to an ancestor.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,70 @@
+use super::*;
+use crate::raw::test::Oracle;
+use quickcheck_macros::*;
+
+#[test]
+fn send_and_sync() {
+    fn check<T: Send + Sync>() {}
+    check::<SyncUnionFindSets<String, Vec<u8>>>();
+}
+
+#[quickcheck]
+fn unite_from_threads(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let sets = SyncUnionFindSets::new();
+    let mut oracle = Oracle::new();
+    for x in adds {
+        let _ = sets.make_set(x, ());
+        let _ = oracle.make_set(x);
+    }
+    std::thread::scope(|s| {
+        for chunk in connects.chunks(4) {
+            let sets = &sets;
+            s.spawn(move || {
+                for (x, y) in chunk {
+                    let _ = sets.unite(x, y);
+                    let _ = sets.same_set(x, y);
+                }
+            });
+        }
+    });
+    for (x, y) in connects {
+        let _ = oracle.unite(x, y);
+    }
+    for x in 0..=u8::MAX {
+        let size = sets.with_set(&x, |xs| xs.len());
+        assert_eq!(size, oracle.find(&x).map(|xs| xs.len()));
+    }
+}
+
+#[quickcheck]
+fn find_from_threads(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let mut compact = crate::compact::UnionFindSets::new();
+    let mut oracle = Oracle::new();
+    for x in adds {
+        let _ = compact.make_set(x, ());
+        let _ = oracle.make_set(x);
+    }
+    for (x, y) in connects {
+        let _ = compact.unite(&x, &y);
+        let _ = oracle.unite(x, y);
+    }
+    let sets = SyncUnionFindSets::from(compact);
+    // finds under the read lock compress paths in parallel
+    let reader = sets.read();
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            let reader = &reader;
+            let oracle = &oracle;
+            s.spawn(move || {
+                for x in 0..=u8::MAX {
+                    let size = reader.find(&x).map(|xs| xs.len());
+                    assert_eq!(size, oracle.find(&x).map(|xs| xs.len()));
+                }
+            });
+        }
+    });
+    drop(reader);
+    for x in 0..=u8::MAX {
+        assert!(sets.read().depth(&x).is_none_or(|depth| depth <= 1));
+    }
+}
@@ -40,3 +40,1191 @@ fn add_connect_query(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<u8>) {
         }
     }
 }
+
+#[cfg(feature = "borsh")]
+#[quickcheck]
+fn borsh_round_trip(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    use std::collections::BTreeSet;
+
+    let mut origin = UnionFindSets::new();
+    for x in adds.into_iter() {
+        let _ = origin.make_set(x, ());
+    }
+    for (x, y) in connects.into_iter() {
+        let _ = origin.unite(&x, &y);
+    }
+    let bytes = borsh::to_vec(&origin).unwrap();
+    let decoded: UnionFindSets<u8, ()> = borsh::from_slice(&bytes).unwrap();
+
+    let partition = |sets: &UnionFindSets<u8, ()>| -> BTreeSet<BTreeSet<u8>> {
+        sets.iter().map(|xs| xs.iter().copied().collect()).collect()
+    };
+    assert_eq!(partition(&decoded), partition(&origin));
+    assert_eq!(borsh::to_vec(&decoded).unwrap(), bytes);
+}
+
+#[cfg(feature = "borsh")]
+#[test]
+fn borsh_rejects_misplaced_elements() {
+    let decode = |raw: &crate::raw::UnionFindSets<u8, IterableTag<u8, ()>>| {
+        let bytes = borsh::to_vec(raw).unwrap();
+        borsh::from_slice::<UnionFindSets<u8, ()>>(&bytes)
+    };
+
+    // each set lists the element of the other one
+    let mut raw = crate::raw::UnionFindSets::new();
+    raw.make_set(0, IterableTag::new(1, ())).unwrap();
+    raw.make_set(1, IterableTag::new(0, ())).unwrap();
+    assert!(decode(&raw).is_err());
+
+    // a set of two lists one of them twice
+    let mut raw = crate::raw::UnionFindSets::new();
+    raw.make_set(0, IterableTag::new(0, ())).unwrap();
+    raw.make_set(1, IterableTag::new(0, ())).unwrap();
+    raw.unite(&0, &1).unwrap();
+    assert!(decode(&raw).is_err());
+
+    let mut raw = crate::raw::UnionFindSets::new();
+    raw.make_set(0, IterableTag::new(0, ())).unwrap();
+    raw.make_set(1, IterableTag::new(1, ())).unwrap();
+    raw.unite(&0, &1).unwrap();
+    assert_eq!(decode(&raw).unwrap().find(&0).unwrap().len(), 2);
+}
+
+#[test]
+fn heap_size_counts_keys() {
+    let mut small = UnionFindSets::new();
+    let mut large = UnionFindSets::new();
+    for x in 0..100u32 {
+        small.make_set(x.to_string(), ()).unwrap();
+        large
+            .make_set("x".repeat(100) + &x.to_string(), ())
+            .unwrap();
+    }
+    assert!(small.heap_size() > 0);
+    // keys are held by both the forest and the lists of elements
+    assert!(large.heap_size() >= small.heap_size() + 2 * 100 * 100);
+}
+
+#[test]
+fn errors_convert_into_anyhow() {
+    fn unite_unknown() -> anyhow::Result<bool> {
+        let mut sets = UnionFindSets::new();
+        sets.make_set(0, ())?;
+        Ok(sets.unite(&0, &1)?)
+    }
+    let err = unite_unknown().unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<crate::Error>(),
+        Some(&crate::Error::KeyNotFound {
+            which: crate::Which::Key2
+        })
+    );
+}
+
+#[test]
+fn unite_keys_without_debug() {
+    #[derive(PartialEq, Eq, Hash, Clone)]
+    struct Opaque(u8);
+
+    let mut sets = UnionFindSets::new();
+    sets.make_set(Opaque(0), ()).unwrap();
+    sets.make_set(Opaque(1), ()).unwrap();
+    assert_eq!(sets.unite(&Opaque(0), &Opaque(1)), Ok(true));
+}
+
+#[test]
+fn borrowed_lookups() {
+    let mut sets = UnionFindSets::new();
+    sets.make_set("a".to_string(), ()).unwrap();
+    sets.make_set("b".to_string(), ()).unwrap();
+    assert_eq!(sets.unite("a", "b"), Ok(true));
+    assert_eq!(sets.find("a").unwrap().len(), 2);
+    assert!(sets.find("c").is_none());
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derive_mergable() {
+    #[derive(Debug, PartialEq, crate::Mergable)]
+    struct Stats {
+        #[mergable(sum)]
+        weight: u64,
+        #[mergable(min)]
+        lowest: u8,
+        #[mergable(max)]
+        highest: u8,
+        #[mergable(extend)]
+        labels: Vec<&'static str>,
+        #[mergable(skip)]
+        name: &'static str,
+        nothing: (),
+    }
+
+    let mut sets = UnionFindSets::new();
+    for (x, label) in [(3u8, "a"), (5, "b"), (4, "c")] {
+        let tag = Stats {
+            weight: x as u64,
+            lowest: x,
+            highest: x,
+            labels: vec![label],
+            name: label,
+            nothing: (),
+        };
+        sets.make_set(x, tag).unwrap();
+    }
+    sets.unite(&3, &5).unwrap();
+    sets.unite(&4, &5).unwrap();
+    let set = sets.find(&3).unwrap();
+    let tag = set.tag();
+    assert_eq!(tag.weight, 12);
+    assert_eq!(tag.lowest, 3);
+    assert_eq!(tag.highest, 5);
+    let mut labels = tag.labels.clone();
+    labels.sort();
+    assert_eq!(labels, vec!["a", "b", "c"]);
+    // the representative keeps its own name
+    let name = match set.key() {
+        3 => "a",
+        5 => "b",
+        _ => "c",
+    };
+    assert_eq!(tag.name, name);
+}
+
+#[test]
+fn collection_tags() {
+    fn merge_all<Tag: crate::Mergable + Clone>(tags: impl Fn(u8) -> Tag) -> Tag {
+        let mut sets = crate::raw::UnionFindSets::new();
+        for x in 0..4u8 {
+            sets.make_set(x, tags(x)).unwrap();
+        }
+        sets.unite(&0, &1).unwrap();
+        sets.unite(&2, &3).unwrap();
+        sets.unite(&1, &3).unwrap();
+        sets.find(&0).unwrap().tag().clone()
+    }
+
+    let mut xs = merge_all(|x| vec![x]);
+    xs.sort();
+    assert_eq!(xs, vec![0, 1, 2, 3]);
+    let parities = merge_all(|x| std::collections::HashSet::from([x % 2]));
+    assert_eq!(parities, std::collections::HashSet::from([0, 1]));
+    let parities = merge_all(|x| std::collections::BTreeSet::from([x % 2]));
+    assert_eq!(parities, std::collections::BTreeSet::from([0, 1]));
+    let mut digits = merge_all(|x| x.to_string()).into_bytes();
+    digits.sort();
+    assert_eq!(digits, b"0123");
+}
+
+#[test]
+fn aggregate_tags() {
+    let mut sets = crate::raw::UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, Sum(x)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&3, &1).unwrap();
+    assert_eq!(sets.find(&0).unwrap().tag(), &Sum(4));
+    assert_eq!(sets.find(&2).unwrap().tag(), &Sum(2));
+
+    let mut sets = crate::raw::UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, (Min(x), Max(x))).unwrap();
+    }
+    sets.unite(&1, &2).unwrap();
+    assert_eq!(sets.find(&2).unwrap().tag(), &(Min(1), Max(2)));
+
+    let mut sets = crate::raw::UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, Count(usize::from(x % 2 == 0))).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &1).unwrap();
+    assert_eq!(sets.find(&1).unwrap().tag(), &Count(2));
+}
+
+#[test]
+fn tuple_tags() {
+    let mut sets = crate::raw::UnionFindSets::new();
+    for x in 0..4u64 {
+        let tag = (
+            Sum(x),
+            vec![x],
+            (),
+            Count(1),
+            Min(x),
+            Max(x),
+            Sum(1u8),
+            Count(0),
+        );
+        sets.make_set(x, tag).unwrap();
+    }
+    sets.unite(&0, &3).unwrap();
+    sets.unite(&1, &3).unwrap();
+    let (sum, mut xs, (), count, min, max, ones, zeros) = sets.find(&0).unwrap().tag().clone();
+    xs.sort();
+    assert_eq!(sum, Sum(4));
+    assert_eq!(xs, vec![0, 1, 3]);
+    assert_eq!(count, Count(3));
+    assert_eq!((min, max), (Min(0), Max(3)));
+    assert_eq!(ones, Sum(3));
+    assert_eq!(zeros, Count(0));
+}
+
+#[test]
+fn optional_and_keyed_tags() {
+    let mut sets = crate::raw::UnionFindSets::new();
+    for x in 0..4u32 {
+        let tag = (x % 2 == 1).then_some(Sum(x));
+        sets.make_set(x, tag).unwrap();
+    }
+    sets.unite(&0, &2).unwrap();
+    assert_eq!(sets.find(&0).unwrap().tag(), &None);
+    sets.unite(&0, &1).unwrap();
+    assert_eq!(sets.find(&0).unwrap().tag(), &Some(Sum(1)));
+    sets.unite(&3, &2).unwrap();
+    assert_eq!(sets.find(&0).unwrap().tag(), &Some(Sum(4)));
+
+    let mut sets = crate::raw::UnionFindSets::new();
+    for x in 0..4u32 {
+        let tag: MergeMap<_, _> = [(x % 2, Count(1)), (x, Count(1))].into_iter().collect();
+        sets.make_set(x, tag).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &1).unwrap();
+    let set = sets.find(&0).unwrap();
+    let tag = set.tag();
+    assert_eq!(tag.len(), 3);
+    // `0` and `1` are their own parities
+    assert_eq!(tag.get(&0), Some(&Count(2)));
+    assert_eq!(tag.get(&1), Some(&Count(1)));
+    assert_eq!(tag.get(&2), Some(&Count(1)));
+}
+
+#[test]
+fn tags_by_add() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Span {
+        lo: u32,
+        hi: u32,
+    }
+
+    impl core::ops::Add for Span {
+        type Output = Span;
+
+        fn add(self, other: Span) -> Span {
+            Span {
+                lo: self.lo.min(other.lo),
+                hi: self.hi.max(other.hi),
+            }
+        }
+    }
+
+    let mut sets = crate::raw::UnionFindSets::new();
+    for x in 1..4u32 {
+        sets.make_set(x, ByAdd(Span { lo: x, hi: x })).unwrap();
+    }
+    sets.unite(&1, &3).unwrap();
+    assert_eq!(sets.find(&3).unwrap().tag(), &ByAdd(Span { lo: 1, hi: 3 }));
+}
+
+#[test]
+fn update_tags() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, Sum(x)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    let mut set = sets.find_mut(&0).unwrap();
+    let mut xs: Vec<_> = set.iter().copied().collect();
+    xs.sort();
+    assert_eq!(xs, vec![0, 1]);
+    set.tag_mut().0 *= 10;
+    sets.unite(&3, &1).unwrap();
+    assert_eq!(sets.find(&0).unwrap().tag(), &Sum(13));
+}
+
+#[test]
+fn map_tags() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, vec![x]).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    let sets = sets.map_tags(|_, xs| Count(xs.len()));
+    assert_eq!(sets.find(&1).unwrap().tag(), &Count(2));
+    let mut xs: Vec<_> = sets.find(&1).unwrap().iter().copied().collect();
+    xs.sort();
+    assert_eq!(xs, vec![0, 1]);
+}
+
+#[test]
+fn for_each_tag_mut() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, Sum(x)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    let mut visited = 0;
+    sets.for_each_tag_mut(|_, tag| {
+        tag.0 *= 10;
+        visited += 1;
+    });
+    assert_eq!(visited, 3);
+    assert_eq!(sets.find(&1).unwrap().tag(), &Sum(10));
+    assert_eq!(sets.find(&3).unwrap().tag(), &Sum(30));
+}
+
+#[test]
+fn set_contains() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&1, &2).unwrap();
+    let set = sets.find(&2).unwrap();
+    assert!(set.contains(&0));
+    assert!(set.contains(&1));
+    assert!(set.contains(&2));
+    assert!(!set.contains(&3));
+    assert!(!set.contains(&4));
+}
+
+#[test]
+fn iterate_sets_by_ref() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    sets.unite(&0, &3).unwrap();
+    let set = sets.find(&0).unwrap();
+    let mut xs = vec![];
+    for x in &set {
+        xs.push(*x);
+    }
+    xs.sort();
+    assert_eq!(xs, vec![0, 3]);
+    assert_eq!((&set).into_iter().max(), Some(&3));
+}
+
+#[test]
+fn exact_size_iteration() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..100u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    for x in 1..90u32 {
+        sets.unite(&0, &x).unwrap();
+    }
+    let iter: SetsIter<'_, u32, ()> = sets.iter();
+    assert_eq!(iter.len(), 11);
+    let set = sets.find(&0).unwrap();
+    let mut xs: ElementsIter<'_, u32> = set.iter();
+    assert_eq!(xs.len(), 90);
+    xs.next();
+    let ys = xs.clone();
+    assert_eq!(xs.len(), 89);
+    assert_eq!(xs.by_ref().count(), 89);
+    assert_eq!(xs.len(), 0);
+    assert!(xs.next().is_none());
+    assert_eq!(ys.count(), 89);
+}
+
+#[test]
+fn iterate_elements_from_both_ends() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..200u32 {
+        sets.make_set(x, ()).unwrap();
+        if x > 0 {
+            sets.unite(&0, &x).unwrap();
+        }
+    }
+    let set = sets.find(&0).unwrap();
+    let forward: Vec<_> = set.iter().collect();
+    let mut backward: Vec<_> = set.iter().rev().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    let mut xs = set.iter();
+    let mut taken = vec![];
+    while let (Some(x), Some(y)) = (xs.next(), xs.next_back()) {
+        taken.push(*x);
+        taken.push(*y);
+    }
+    assert_eq!(xs.len(), 0);
+    taken.sort();
+    assert_eq!(taken, (0..200).collect::<Vec<_>>());
+}
+
+#[test]
+fn owned_sets() {
+    fn largest(xs: &[(String, String)]) -> OwnedSet<String, Count> {
+        let mut sets = UnionFindSets::new();
+        for (x, y) in xs {
+            let _ = sets.make_set(x.clone(), Count(1));
+            let _ = sets.make_set(y.clone(), Count(1));
+            sets.unite(x, y).unwrap();
+        }
+        let largest = sets.iter().max_by_key(|xs| xs.len()).unwrap();
+        largest.to_owned()
+    }
+
+    let edges = [("a", "b"), ("c", "d"), ("d", "e")].map(|(x, y)| (x.to_string(), y.to_string()));
+    let set = largest(&edges);
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.tag(), &Count(3));
+    assert!(["c", "d", "e"].contains(&set.key().as_str()));
+    assert_eq!((&set).into_iter().count(), 3);
+    let mut xs: Vec<String> = set.into_iter().collect();
+    xs.sort();
+    assert_eq!(xs, vec!["c", "d", "e"]);
+}
+
+#[test]
+fn stable_set_ids() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..6u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    let ids: Vec<_> = (0..6u32).map(|x| sets.find(&x).unwrap().id()).collect();
+    let distinct: std::collections::HashSet<_> = ids.iter().collect();
+    assert_eq!(distinct.len(), 6);
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &1).unwrap();
+    let id = sets.find(&0).unwrap().id();
+    assert!(ids[..3].contains(&id));
+    assert_eq!(sets.find(&2).unwrap().id(), id);
+    assert_eq!(sets.find(&5).unwrap().id(), ids[5]);
+    let found: std::collections::HashSet<_> = (0..6u32).map(|x| sets.find(&x).unwrap()).collect();
+    assert_eq!(found.len(), 4);
+    let iterated: std::collections::HashSet<_> = sets.iter().map(|xs| xs.id()).collect();
+    assert!(iterated.contains(&id));
+    assert_eq!(iterated.len(), 4);
+}
+
+#[test]
+fn group_map() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..5u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    sets.unite(&0, &2).unwrap();
+    sets.unite(&4, &2).unwrap();
+    let groups = sets.group_map();
+    assert_eq!(groups.len(), 3);
+    for (key, xs) in groups.iter() {
+        assert!(xs.contains(key));
+    }
+    let mut xs = groups[sets.find(&4).unwrap().key()].clone();
+    xs.sort();
+    assert_eq!(xs, vec![&0, &2, &4]);
+    assert_eq!(groups[&1], vec![&1]);
+}
+
+#[test]
+fn sorted_iteration() {
+    let mut sets = UnionFindSets::new();
+    for x in (0..200u32).rev() {
+        sets.make_set(x, ()).unwrap();
+    }
+    for x in (0..200u32).rev() {
+        sets.unite(&x, &(x % 3)).unwrap();
+    }
+    let keys: Vec<_> = sets.iter_sets_sorted_by_key().map(|xs| *xs.key()).collect();
+    assert_eq!(keys.len(), 3);
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    for set in sets.iter() {
+        let xs: Vec<_> = set.iter_sorted().copied().collect();
+        let r = xs[0] % 3;
+        let expected: Vec<_> = (0..200).filter(|x| x % 3 == r).collect();
+        assert_eq!(xs, expected);
+    }
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_sampling() {
+    use rand::SeedableRng;
+
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(42);
+    let mut sets = UnionFindSets::new();
+    assert!(sets.sample_set_weighted(&mut rng).is_none());
+    for x in 0..400u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    for x in 1..300u32 {
+        sets.unite(&0, &x).unwrap();
+    }
+    let big = sets.find(&0).unwrap();
+    let mut seen = std::collections::HashSet::new();
+    for _ in 0..3000 {
+        let x = big.sample(&mut rng).unwrap();
+        assert!(*x < 300);
+        seen.insert(*x);
+    }
+    assert_eq!(seen.len(), 300);
+    let hits = (0..1000)
+        .filter(|_| sets.sample_set_weighted(&mut rng).unwrap() == big)
+        .count();
+    // 3/4 expected
+    assert!((650..850).contains(&hits), "{hits}");
+}
+
+#[cfg(feature = "quickcheck")]
+#[quickcheck]
+fn arbitrary_sets(sets: UnionFindSets<u8, Vec<u8>>) {
+    use ::quickcheck::Arbitrary;
+
+    let elements =
+        |sets: &UnionFindSets<u8, Vec<u8>>| sets.iter().map(|xs| xs.len()).sum::<usize>();
+    for xs in sets.iter() {
+        assert!(xs.iter().all(|x| xs.contains(x)));
+    }
+    for smaller in sets.shrink().take(20) {
+        assert!(
+            elements(&smaller) < elements(&sets) || smaller.len() > sets.len(),
+            "{:?} does not shrink {:?}",
+            smaller,
+            sets
+        );
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_from_bytes() {
+    use crate::ops::Op;
+    use ::arbitrary::{Arbitrary, Unstructured};
+
+    let bytes: Vec<u8> = (0..=255u8).cycle().take(4096).collect();
+    let mut unions = 0;
+    for start in 0..64 {
+        let mut u = Unstructured::new(&bytes[start..]);
+        let sets = UnionFindSets::<u8, Vec<u8>>::arbitrary(&mut u).unwrap();
+        for xs in sets.iter() {
+            assert!(xs.iter().all(|x| sets.find(x).unwrap() == xs));
+        }
+        let mut u = Unstructured::new(&bytes[start..]);
+        let ops = Vec::<Op<u8, ()>>::arbitrary(&mut u).unwrap();
+        unions += ops.iter().filter(|op| matches!(op, Op::Unite(..))).count();
+    }
+    assert!(unions > 0);
+}
+
+#[quickcheck]
+fn join_replicas(adds: Vec<Vec<(u8, u8)>>, connects: Vec<Vec<(u8, u8)>>) {
+    use std::collections::BTreeSet;
+
+    let replicas: Vec<UnionFindSets<u8, BTreeSet<u8>>> = adds
+        .into_iter()
+        .zip(connects)
+        .take(3)
+        .map(|(adds, connects)| {
+            let mut sets = UnionFindSets::new();
+            for (x, tag) in adds {
+                let _ = sets.make_set(x % 32, BTreeSet::from([tag]));
+            }
+            for (x, y) in connects {
+                let _ = sets.unite(&(x % 32), &(y % 32));
+            }
+            sets
+        })
+        .collect();
+    let state = |sets: &UnionFindSets<u8, BTreeSet<u8>>| -> BTreeSet<(BTreeSet<u8>, BTreeSet<u8>)> {
+        sets.iter()
+            .map(|xs| (xs.iter().copied().collect(), xs.tag().clone()))
+            .collect()
+    };
+    let joined = |xs: &[&UnionFindSets<u8, BTreeSet<u8>>]| {
+        let mut sets = xs[0].clone();
+        for other in &xs[1..] {
+            sets.join(other).unwrap();
+        }
+        state(&sets)
+    };
+    for x in replicas.iter() {
+        assert_eq!(joined(&[x, x]), state(x));
+        for y in replicas.iter() {
+            assert_eq!(joined(&[x, y]), joined(&[y, x]));
+            for z in replicas.iter() {
+                let mut xy = x.clone();
+                xy.join(y).unwrap();
+                let mut yz = y.clone();
+                yz.join(z).unwrap();
+                assert_eq!(joined(&[&xy, z]), joined(&[x, &yz]));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_groups() {
+    let mut sets = UnionFindSets::new();
+    for x in ["b", "a", "c"] {
+        sets.make_set(x.to_string(), Count(1)).unwrap();
+    }
+    sets.unite("a", "b").unwrap();
+    let json = sets.to_json_groups().unwrap();
+    let groups: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let rep = sets.find("a").unwrap().key().clone();
+    assert_eq!(
+        groups,
+        serde_json::json!([
+            {"representative": rep, "tag": 2, "members": ["a", "b"]},
+            {"representative": "c", "tag": 1, "members": ["c"]},
+        ])
+    );
+    let read = UnionFindSets::<String, Count>::from_json_groups(&json).unwrap();
+    assert_eq!(read.len(), 2);
+    assert_eq!(
+        read.find("b").unwrap().iter_sorted().collect::<Vec<_>>(),
+        ["a", "b"]
+    );
+    assert_eq!(read.find("b").unwrap().tag(), &Count(2));
+
+    let edited = r#"[{"representative": "x", "tag": 5, "members": ["y", "z"]}]"#;
+    let read = UnionFindSets::<String, Count>::from_json_groups(edited).unwrap();
+    assert_eq!(read.find("z").unwrap().len(), 3);
+    assert_eq!(read.find("x").unwrap().tag(), &Count(5));
+    let duplicated = r#"[
+        {"representative": "x", "tag": 1, "members": []},
+        {"representative": "y", "tag": 1, "members": ["x"]}
+    ]"#;
+    assert!(UnionFindSets::<String, Count>::from_json_groups(duplicated).is_err());
+}
+
+#[cfg(feature = "instrumentation")]
+#[test]
+fn count_operations() {
+    // counters are shared with tests running in parallel, so only lower bounds hold
+    let before = crate::metrics_snapshot();
+    let mut sets = crate::raw::UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &3).unwrap();
+    sets.unite(&0, &2).unwrap();
+    assert!(!sets.unite(&1, &3).unwrap());
+    // one of 0 and 2 hangs two levels below the root
+    assert!(sets.find(&0).is_some());
+    assert!(sets.find(&2).is_some());
+    let after = crate::metrics_snapshot();
+    assert!(after.unions >= before.unions + 3);
+    assert!(after.redundant_unions > before.redundant_unions);
+    assert!(after.finds >= before.finds + 8);
+    assert!(after.compressed_pointers > before.compressed_pointers);
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn report_metrics() {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        let mut sets = crate::compact::UnionFindSets::new();
+        for x in 0..3u32 {
+            sets.make_set(x, ()).unwrap();
+        }
+        sets.unite(&0, &1).unwrap();
+        sets.unite(&1, &0).unwrap();
+        crate::record_set_count("left", sets.len());
+        crate::record_set_count("right", 5);
+    });
+    let snapshot = snapshotter.snapshot().into_vec();
+    let gauges: Vec<_> = snapshot
+        .iter()
+        .filter(|(key, ..)| key.key().name() == "tagged_ufs_sets")
+        .map(|(key, _, _, value)| (key.key().labels().next().unwrap().value(), value))
+        .collect();
+    assert_eq!(gauges.len(), 2);
+    for (name, value) in gauges {
+        let expected = if name == "left" { 2.0 } else { 5.0 };
+        assert!(matches!(value, DebugValue::Gauge(x) if x.0 == expected));
+    }
+    let values: std::collections::HashMap<_, _> = snapshot
+        .into_iter()
+        .map(|(key, _, _, value)| (key.key().name().to_string(), value))
+        .collect();
+    assert_eq!(values["tagged_ufs_unions_total"], DebugValue::Counter(1));
+    assert_eq!(
+        values["tagged_ufs_redundant_unions_total"],
+        DebugValue::Counter(1)
+    );
+    assert!(
+        matches!(&values["tagged_ufs_find_path_length"], DebugValue::Histogram(xs) if xs.len() == 4)
+    );
+}
+
+#[test]
+fn shared_string_keys() {
+    use std::rc::Rc;
+    let mut sets = UnionFindSets::<Rc<str>, Count>::new();
+    for x in ["a", "b", "c"] {
+        sets.make_set_str(x, Count(1)).unwrap();
+    }
+    assert_eq!(sets.make_set_str("b", Count(1)), Err(Error::DuplicateKey));
+    assert_eq!(
+        sets.make_set("c".into(), Count(1)),
+        Err(Error::DuplicateKey)
+    );
+    sets.unite("a", "c").unwrap();
+    assert_eq!(sets.find("c").unwrap().tag(), &Count(2));
+    assert_eq!(sets.shared_key("b").map(|x| &**x), Some("b"));
+    assert!(sets.shared_key("d").is_none());
+    // the forest and the list of elements share one allocation of every key
+    let a = sets.shared_key("a").unwrap().clone();
+    assert_eq!(Rc::strong_count(&a), 3);
+}
+
+#[quickcheck]
+fn map_keys_keeps_sets(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, Count(1));
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    let mapped = sets.clone().map_keys(|x| x as u32 + 1000).unwrap();
+    assert_eq!(mapped.len(), sets.len());
+    for xs in sets.iter() {
+        let ys = mapped.find(&(*xs.key() as u32 + 1000)).unwrap();
+        assert_eq!(ys.tag(), xs.tag());
+        let mut xs: Vec<_> = xs.iter().map(|x| *x as u32 + 1000).collect();
+        let mut ys: Vec<_> = ys.iter().copied().collect();
+        xs.sort();
+        ys.sort();
+        assert_eq!(xs, ys);
+    }
+}
+
+#[test]
+fn map_keys_collisions() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    assert_eq!(
+        sets.clone().map_keys(|x| x / 2).map(|_| ()),
+        Err(Error::DuplicateKey)
+    );
+    sets.unite(&0, &1).unwrap();
+    assert_eq!(
+        sets.clone().map_keys(|x| x / 2).map(|_| ()),
+        Err(Error::DuplicateKey)
+    );
+    sets.unite(&2, &3).unwrap();
+    assert_eq!(
+        sets.clone().map_keys(|x| x / 2).map(|_| ()),
+        Err(Error::DuplicateKey)
+    );
+    assert_eq!(sets.map_keys(|x| x + 1).unwrap().len(), 2);
+}
+
+#[quickcheck]
+fn restrict_induces_sets(adds: Vec<u8>, connects: Vec<(u8, u8)>, keys: Vec<u8>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, Count(1));
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    let part = sets.restrict(&keys);
+    for x in keys.iter() {
+        assert_eq!(part.find(x).is_some(), sets.find(x).is_some());
+        for y in keys.iter() {
+            if let (Some(xs), Some(ys)) = (part.find(x), part.find(y)) {
+                assert_eq!(xs.id() == ys.id(), sets.find(x) == sets.find(y));
+                assert_eq!(xs.tag(), sets.find(x).unwrap().tag());
+            }
+        }
+    }
+    let counted = part.restrict_with(&keys, |xs| Count(xs.len()));
+    for xs in counted.iter() {
+        assert_eq!(xs.tag(), &Count(xs.len()));
+    }
+}
+
+#[quickcheck]
+fn split_off_moves_whole_sets(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, Count(1));
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    let before = sets.clone();
+    let moved = sets.split_off(|xs| xs.key() % 2 == 0);
+    assert_eq!(sets.len() + moved.len(), before.len());
+    for xs in before.iter() {
+        let (here, there) = (sets.find(xs.key()), moved.find(xs.key()));
+        let ys = if xs.key() % 2 == 0 {
+            assert!(here.is_none());
+            there.unwrap()
+        } else {
+            assert!(there.is_none());
+            here.unwrap()
+        };
+        assert_eq!(ys.len(), xs.len());
+        assert_eq!(ys.tag(), xs.tag());
+        assert!(xs.iter().all(|x| ys.contains(x)));
+    }
+    assert_eq!(sets.iter().count(), sets.len());
+}
+
+#[test]
+fn golden_record_wins() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..5u32 {
+        sets.make_set(x, Max(x == 4)).unwrap();
+    }
+    for x in 1..4u32 {
+        sets.unite_by_priority(&0, &x, |tag| tag.0).unwrap();
+    }
+    assert!(sets.unite_by_priority(&4, &2, |tag| tag.0).unwrap());
+    assert_eq!(sets.find(&1).unwrap().key(), &4);
+    assert_eq!(sets.find(&1).unwrap().len(), 5);
+    assert_eq!(sets.find(&1).unwrap().tag(), &Max(true));
+    assert!(!sets.unite_by_priority(&0, &4, |tag| tag.0).unwrap());
+    assert!(sets.unite_by_priority(&0, &5, |tag| tag.0).is_err());
+}
+
+#[quickcheck]
+fn extract_sets_by_size(adds: Vec<u8>, connects: Vec<(u8, u8)>, size: u8) {
+    let size = size as usize % 4;
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, Count(1));
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    let before = sets.clone();
+    let extracted = sets.extract_sets_where(|xs| xs.len() > size);
+    for (tag, keys) in extracted.iter() {
+        let xs = before.find(&keys[0]).unwrap();
+        assert_eq!(xs.key(), &keys[0]);
+        assert_eq!(xs.tag(), tag);
+        assert_eq!(xs.len(), keys.len());
+        assert!(keys
+            .iter()
+            .all(|x| xs.contains(x) && sets.find(x).is_none()));
+    }
+    assert!(sets.iter().all(|xs| xs.len() <= size));
+    assert_eq!(sets.len() + extracted.len(), before.len());
+}
+
+#[quickcheck]
+fn into_sets_like_iter(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, Count(1));
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    let mut expected: Vec<_> = sets
+        .iter()
+        .map(|xs| {
+            let mut keys: Vec<_> = xs.iter().copied().collect();
+            keys.sort();
+            (*xs.tag(), keys)
+        })
+        .collect();
+    let mut trial: Vec<_> = sets
+        .into_sets()
+        .map(|(tag, mut keys)| {
+            keys.sort();
+            (tag, keys)
+        })
+        .collect();
+    expected.sort_by(|x, y| x.1.cmp(&y.1));
+    trial.sort_by(|x, y| x.1.cmp(&y.1));
+    assert_eq!(trial, expected);
+}
+
+#[quickcheck]
+fn connectivity_matrix(adds: Vec<u8>, connects: Vec<(u8, u8)>, order: Vec<u8>) {
+    let mut trial = UnionFindSets::new();
+    let mut oracle = Oracle::new();
+    for x in adds {
+        let _ = trial.make_set(x, ());
+        let _ = oracle.make_set(x);
+    }
+    for (x, y) in connects {
+        let _ = trial.unite(&x, &y);
+        let _ = oracle.unite(x, y);
+    }
+    let matrix = trial.to_connectivity_matrix(&order);
+    assert_eq!(matrix.len(), order.len());
+    for (x, row) in order.iter().zip(matrix.iter()) {
+        assert_eq!(row.len(), order.len());
+        for (y, cell) in order.iter().zip(row.iter()) {
+            let same = match (oracle.find(x), oracle.find(y)) {
+                (Some(xs), Some(ys)) => xs == ys,
+                _ => false,
+            };
+            assert_eq!(*cell, same);
+        }
+    }
+}
+
+#[quickcheck]
+fn same_set_pairs(adds: Vec<u8>, connects: Vec<(u8, u8)>, cap: u8) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, Count(1));
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    let pairs: std::collections::HashSet<_> = sets
+        .iter_same_set_pairs()
+        .map(|(x, y)| (*x.min(y), *x.max(y)))
+        .collect();
+    let mut oracle = std::collections::HashSet::new();
+    for xs in sets.iter() {
+        for x in xs.iter() {
+            for y in xs.iter() {
+                if x < y {
+                    oracle.insert((*x, *y));
+                }
+            }
+        }
+    }
+    assert_eq!(pairs, oracle);
+    assert_eq!(sets.iter_same_set_pairs().count(), oracle.len());
+
+    let cap = cap as usize;
+    let capped = sets.iter_same_set_pairs_capped(cap).count();
+    let expected: usize = sets
+        .iter()
+        .map(|xs| (xs.len() * (xs.len() - 1) / 2).min(cap))
+        .sum();
+    assert_eq!(capped, expected);
+}
+
+#[cfg(feature = "rayon")]
+#[quickcheck]
+fn par_extend_edges(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    use ::rayon::iter::{IntoParallelIterator, ParallelExtend};
+
+    let mut trial = UnionFindSets::new();
+    let mut oracle = Oracle::new();
+    let added: std::collections::HashSet<_> = adds.iter().copied().collect();
+    for x in adds {
+        let _ = trial.make_set(x, Count(1));
+    }
+    trial.par_extend(connects.clone().into_par_iter());
+    for x in trial
+        .iter()
+        .flat_map(|xs| xs.iter().copied().collect::<Vec<_>>())
+    {
+        let _ = oracle.make_set(x);
+    }
+    for (x, y) in connects {
+        let _ = oracle.unite(x, y);
+    }
+    for xs in trial.iter() {
+        let mut elements: Vec<_> = xs.iter().copied().collect();
+        elements.sort();
+        let mut expected = oracle.find(xs.key()).unwrap();
+        expected.sort();
+        assert_eq!(elements, expected);
+        // ends made by edges start from the default tag
+        let counted = elements.iter().filter(|x| added.contains(x)).count();
+        assert_eq!(xs.tag().0, counted);
+    }
+}
+
+#[quickcheck]
+fn same_elements_across_instances(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let mut sets1 = UnionFindSets::new();
+    let mut sets2 = UnionFindSets::new();
+    for x in adds.iter() {
+        let _ = sets1.make_set(*x, ());
+    }
+    // the same sets, built in the reverse order
+    for x in adds.iter().rev() {
+        let _ = sets2.make_set(*x, Count(1));
+    }
+    for (x, y) in connects.iter() {
+        let _ = sets1.unite(x, y);
+    }
+    for (x, y) in connects.iter().rev() {
+        let _ = sets2.unite(y, x);
+    }
+    for xs in sets1.iter() {
+        for ys in sets2.iter() {
+            let same = xs.iter().all(|x| ys.contains(x)) && ys.iter().all(|y| xs.contains(y));
+            assert_eq!(xs.same_elements(&ys), same);
+            if same {
+                assert_eq!(xs.fingerprint(), ys.fingerprint());
+            }
+        }
+    }
+}
+
+#[quickcheck]
+fn unite_outcome_tells_survivor(adds: Vec<u8>, connects: Vec<(u8, u8)>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, ());
+    }
+    for (x, y) in connects {
+        let tops = sets
+            .find(&x)
+            .zip(sets.find(&y))
+            .map(|(xs, ys)| (*xs.key(), *ys.key()));
+        match sets.unite_with_outcome(&x, &y) {
+            Ok(UniteOutcome::Unchanged) => assert_eq!(tops.map(|(a, b)| a == b), Some(true)),
+            Ok(UniteOutcome::Merged { winner, loser }) => {
+                let (winner, loser) = (*winner, *loser);
+                let (a, b) = tops.unwrap();
+                assert!((winner, loser) == (a, b) || (winner, loser) == (b, a));
+                assert_eq!(sets.find(&x).unwrap().key(), &winner);
+                assert_eq!(sets.find(&loser).unwrap().key(), &winner);
+            }
+            Err(_) => assert!(tops.is_none()),
+        }
+    }
+}
+
+#[quickcheck]
+fn make_sets_with_default_tags(adds: Vec<u8>) {
+    let mut trial: UnionFindSets<u8, Count> = UnionFindSets::new();
+    let mut oracle = UnionFindSets::new();
+    let trial_res = trial.make_sets_default(adds.iter().copied());
+    let mut oracle_res = Ok(());
+    for x in adds {
+        oracle_res = oracle.make_set(x, Count(0));
+        if oracle_res.is_err() {
+            break;
+        }
+    }
+    assert_eq!(trial_res, oracle_res);
+    assert_eq!(trial.len(), oracle.len());
+    assert!(trial.iter().all(|xs| *xs.tag() == Count(0)));
+}
+
+#[quickcheck]
+fn tag_of_like_find(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<u8>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, Count(1));
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    for x in queries {
+        assert_eq!(sets.tag_of(&x), sets.find(&x).map(|xs| xs.tag()));
+    }
+}
+
+#[quickcheck]
+fn contains_like_find(adds: Vec<u8>, connects: Vec<(u8, u8)>, queries: Vec<u8>) {
+    let mut sets = UnionFindSets::new();
+    let mut compact = crate::compact::UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, ());
+        let _ = compact.make_set(x, ());
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+        let _ = compact.unite(&x, &y);
+    }
+    for x in queries {
+        assert_eq!(sets.contains(&x), sets.find(&x).is_some());
+        assert_eq!(compact.contains(&x), sets.contains(&x));
+    }
+}
+
+#[cfg(feature = "roaring")]
+#[quickcheck]
+fn bitmap_tags_like_elements(adds: Vec<u8>, connects: Vec<(u8, u8)>, external: Vec<u8>) {
+    let mut sets = UnionFindSets::new();
+    for x in adds {
+        let _ = sets.make_set(x, BitmapTag::new(x.into()));
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    let bitmap: ::roaring::RoaringBitmap = external.iter().map(|x| u32::from(*x)).collect();
+    let external: std::collections::HashSet<_> = external.into_iter().collect();
+    for xs in sets.iter() {
+        assert_eq!(xs.tag().len(), xs.len() as u64);
+        assert!(xs.iter().all(|x| xs.tag().contains((*x).into())));
+        let overlap = xs.iter().filter(|x| external.contains(x)).count();
+        assert_eq!(xs.tag().intersection_size(&bitmap), overlap as u64);
+    }
+}
+
+#[test]
+fn join_sets_whose_representatives_are_iterated_last() {
+    // a golden record wins over a set longer than a chunk,
+    // so its elements are appended after those of the set
+    let mut sets = UnionFindSets::new();
+    for x in 0..65u32 {
+        sets.make_set(x, Max(false)).unwrap();
+        sets.unite(&0, &x).unwrap();
+    }
+    sets.make_set(1000, Max(true)).unwrap();
+    sets.unite_by_priority(&0, &1000, |tag| tag.0).unwrap();
+    assert_eq!(sets.find(&0).unwrap().key(), &1000);
+
+    let mut joined = UnionFindSets::new();
+    joined.join(&sets).unwrap();
+    assert_eq!(joined.len(), 1);
+    assert_eq!(joined.find(&0).unwrap().len(), 66);
+    assert_eq!(joined.find(&0).unwrap().key(), &1000);
+    assert_eq!(joined.find(&0).unwrap().tag(), &Max(true));
+
+    let mut canonical = crate::canonical::CanonicalSets::new();
+    for x in (0..70u32).rev() {
+        canonical.make_set(x, Max(false)).unwrap();
+        canonical.unite(&69, &x).unwrap();
+    }
+    let mut joined = UnionFindSets::new();
+    joined.join(canonical.sets()).unwrap();
+    assert_eq!(joined.find(&69).unwrap().len(), 70);
+}
+
+#[test]
+fn join_counts_no_redundant_unions() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &3).unwrap();
+
+    let mut joined = UnionFindSets::new();
+    joined.join(&sets).unwrap();
+    assert_eq!(joined.union_stats().redundant, 0);
+    assert_eq!(joined.union_stats().unions, 0);
+    joined.join(&sets).unwrap();
+    assert_eq!(joined.union_stats().redundant, 2);
+    assert_eq!(joined.len(), 2);
+}
+
+#[test]
+fn map_keys_keeps_representatives() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..65u32 {
+        sets.make_set(x, Max(false)).unwrap();
+        sets.unite(&0, &x).unwrap();
+    }
+    sets.make_set(1000, Max(true)).unwrap();
+    sets.unite_by_priority(&0, &1000, |tag| tag.0).unwrap();
+    let sets = sets.map_keys(|x| x).unwrap();
+    assert_eq!(sets.find(&0).unwrap().key(), &1000);
+    let sets = sets.map_keys(|x| x + 1).unwrap();
+    assert_eq!(sets.find(&1).unwrap().key(), &1001);
+    assert_eq!(sets.find(&1).unwrap().len(), 66);
+}
@@ -0,0 +1,189 @@
+use crate::{Error, HeapSize, Mergable, Result, Set, SetMut, SetsIter};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::Hash;
+use hashbrown::HashMap;
+
+/// A set of union-find sets, each element of which carries a value,
+/// and each set of which carries a mergable tag.
+#[derive(Clone)]
+pub struct UnionFindSets<Key, Value, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    sets: crate::UnionFindSets<Key, Tag>,
+    values: HashMap<Key, Value, ahash::RandomState>,
+}
+
+impl<Key, Value, Tag> Default for UnionFindSets<Key, Value, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Key, Value, Tag> UnionFindSets<Key, Value, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Makes a new, empty set of sets.
+    pub fn new() -> Self {
+        Self {
+            sets: crate::UnionFindSets::new(),
+            values: HashMap::with_hasher(ahash::RandomState::new()),
+        }
+    }
+
+    /// Makes an individual set with a singleton element, its value and the associated tag.
+    ///
+    /// If the set to make is already there,
+    /// [Error::DuplicateKey](crate::Error::DuplicateKey) will be raised
+    /// and nothing will happen to the sets.
+    pub fn make_set(&mut self, key: Key, value: Value, tag: Tag) -> Result<()> {
+        if self.values.contains_key(&key) {
+            return Err(Error::DuplicateKey);
+        }
+        self.sets.make_set(key.clone(), tag)?;
+        self.values.insert(key, value);
+        Ok(())
+    }
+
+    /// Unites two sets, given by any borrowed forms of their keys,
+    /// see [UnionFindSets::unite](crate::UnionFindSets::unite).
+    pub fn unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.sets.unite(key1, key2)
+    }
+
+    /// Unites two sets, like [unite](Self::unite),
+    /// but simply returns `None` if either of them is not in the sets.
+    pub fn try_unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Option<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        self.sets.try_unite(key1, key2)
+    }
+
+    /// Finds an individual set.
+    ///
+    /// If the set is not inside, `None` will be returned.
+    pub fn find<Q>(&self, key: &Q) -> Option<Set<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.sets.find(key)
+    }
+
+    /// Finds an individual set, whose tag can be updated.
+    ///
+    /// If the set is not inside, `None` will be returned.
+    pub fn find_mut<Q>(&mut self, key: &Q) -> Option<SetMut<'_, Key, Tag>>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.sets.find_mut(key)
+    }
+
+    /// Gets the value of an element.
+    ///
+    /// If the element is not inside, `None` will be returned.
+    pub fn element_value<Q>(&self, key: &Q) -> Option<&Value>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.values.get(key)
+    }
+
+    /// Gets the value of an element mutably.
+    ///
+    /// If the element is not inside, `None` will be returned.
+    pub fn element_value_mut<Q>(&mut self, key: &Q) -> Option<&mut Value>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.values.get_mut(key)
+    }
+
+    /// Removes the whole set containing `key`, returning its elements with their values and its tag,
+    /// where the representative comes first,
+    /// see [UnionFindSets::take_set](crate::UnionFindSets::take_set).
+    ///
+    /// If `key` is not in the sets, `None` will be returned.
+    pub fn take_set<Q>(&mut self, key: &Q) -> Option<(Vec<(Key, Value)>, Tag)>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (keys, tag) = self.sets.take_set(key)?;
+        let elements = keys
+            .iter()
+            .filter_map(|x| self.values.remove_entry(x))
+            .collect();
+        Some((elements, tag))
+    }
+
+    /// Moves whole sets on which `pred` holds out into new sets, along with values of their elements,
+    /// see [UnionFindSets::split_off](crate::UnionFindSets::split_off).
+    pub fn split_off<F>(&mut self, pred: F) -> Self
+    where
+        F: FnMut(&Set<'_, Key, Tag>) -> bool,
+    {
+        let sets = self.sets.split_off(pred);
+        let mut values = HashMap::with_hasher(ahash::RandomState::new());
+        values.extend(
+            sets.iter()
+                .flat_map(|xs| xs.iter())
+                .filter_map(|x| self.values.remove_entry(x)),
+        );
+        Self { sets, values }
+    }
+
+    /// Iterates over all individual sets.
+    pub fn iter(&self) -> SetsIter<'_, Key, Tag> {
+        self.sets.iter()
+    }
+
+    /// Queries the number of individual sets in the set.
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Tests if this set (of sets) is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+}
+
+impl<Key, Value, Tag> UnionFindSets<Key, Value, Tag>
+where
+    Key: Eq + Hash + Clone + HeapSize,
+    Value: HeapSize,
+    Tag: Mergable + HeapSize,
+{
+    /// Reports the number of bytes on heap owned by these sets,
+    /// including the sets, the table of values and whatever keys and values own.
+    pub fn heap_size(&self) -> usize {
+        self.sets.heap_size()
+            + self.values.allocation_size()
+            + self
+                .values
+                .iter()
+                .map(|(key, value)| key.heap_size() + value.heap_size())
+                .sum::<usize>()
+    }
+}
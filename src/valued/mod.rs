@@ -0,0 +1,11 @@
+//! Union-find sets with a value per element, besides a tag per set.
+//!
+//! Values are kept in a table beside the forest, which grows along with it,
+//! and shrinks along with it when whole sets are taken out,
+//! so they never go out of sync with the elements.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,47 @@
+use super::*;
+use crate::Sum;
+
+#[test]
+fn element_values() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..4u32 {
+        sets.make_set(x, x * 100, Sum(1)).unwrap();
+    }
+    assert_eq!(sets.make_set(0, 0, Sum(1)), Err(crate::Error::DuplicateKey));
+    assert_eq!(sets.element_value(&0), Some(&0));
+    sets.unite(&0, &1).unwrap();
+    *sets.element_value_mut(&1).unwrap() += 1;
+    assert_eq!(sets.element_value(&1), Some(&101));
+    assert_eq!(sets.element_value(&4), None);
+    let set = sets.find(&0).unwrap();
+    assert_eq!(set.tag(), &Sum(2));
+    let total: u32 = set.iter().filter_map(|x| sets.element_value(x)).sum();
+    assert_eq!(total, 101);
+    assert_eq!(sets.len(), 3);
+}
+
+#[test]
+fn values_leave_with_sets() {
+    let mut sets = UnionFindSets::new();
+    for x in 0..5u32 {
+        sets.make_set(x, x * 100, Sum(1)).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &3).unwrap();
+    let (mut elements, tag) = sets.take_set(&1).unwrap();
+    elements.sort();
+    assert_eq!(elements, vec![(0, 0), (1, 100)]);
+    assert_eq!(tag, Sum(2));
+    assert_eq!(sets.element_value(&0), None);
+    assert!(sets.take_set(&0).is_none());
+    sets.make_set(0, 7, Sum(1)).unwrap();
+    assert_eq!(sets.element_value(&0), Some(&7));
+
+    let pairs = sets.split_off(|xs| xs.len() == 2);
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs.element_value(&3), Some(&300));
+    assert_eq!(pairs.element_value(&0), None);
+    assert_eq!(sets.element_value(&3), None);
+    assert_eq!(sets.element_value(&4), Some(&400));
+    assert_eq!(sets.len(), 2);
+}
@@ -0,0 +1,170 @@
+use crate::error::found_both;
+use crate::ops::Op;
+use crate::{Error, Mergable, UnionFindSets};
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::hash::Hash;
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Sets whose mutations are logged to a writer.
+///
+/// Every mutation is checked, logged and only then applied,
+/// so the sets never run ahead of the log.
+/// Errors of sets, e.g., [Error::DuplicateKey](crate::Error::DuplicateKey),
+/// are reported as [ErrorKind::InvalidInput], wrapping the [Error](crate::Error),
+/// and nothing is logged for them.
+/// If a flush of a full group fails, the mutation is applied and written,
+/// but not known to be durable.
+pub struct Wal<Key, Tag, W>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    sets: UnionFindSets<Key, Tag>,
+    writer: W,
+    group: usize,
+    pending: usize,
+}
+
+impl<Key, Tag, W> Wal<Key, Tag, W>
+where
+    Key: Eq + Hash + Clone + BorshSerialize,
+    Tag: Mergable + BorshSerialize,
+    W: Write,
+{
+    /// Makes new, empty sets, logging to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self::resume(UnionFindSets::new(), writer)
+    }
+
+    /// Continues logging sets, e.g., those recovered by [recover] from the same log.
+    pub fn resume(sets: UnionFindSets<Key, Tag>, writer: W) -> Self {
+        Self {
+            sets,
+            writer,
+            group: 1,
+            pending: 0,
+        }
+    }
+
+    /// Flushes the writer once every `group` mutations instead of after each of them,
+    /// trading the durability of the latest mutations for throughput.
+    ///
+    /// A `group` of 0 leaves flushing entirely to [commit](Self::commit).
+    pub fn set_group_commit(&mut self, group: usize) {
+        self.group = group;
+    }
+
+    /// Gets the logged sets.
+    pub fn sets(&self) -> &UnionFindSets<Key, Tag> {
+        &self.sets
+    }
+
+    /// Logs making a set, and then makes it like [UnionFindSets::make_set].
+    ///
+    /// If the record cannot be written, the set is not made.
+    pub fn make_set(&mut self, key: Key, tag: Tag) -> io::Result<()> {
+        if self.sets.contains(&key) {
+            return Err(invalid_input(Error::DuplicateKey));
+        }
+        self.append(&Op::MakeSet(&key, &tag))?;
+        self.sets.make_set(key, tag).map_err(invalid_input)?;
+        self.count_pending()
+    }
+
+    /// Logs uniting sets if they are apart, and then unites them like [UnionFindSets::unite].
+    ///
+    /// If the record cannot be written, the sets are not united.
+    pub fn unite(&mut self, key1: &Key, key2: &Key) -> io::Result<bool> {
+        let (xs, ys) =
+            found_both(self.sets.find(key1), self.sets.find(key2)).map_err(invalid_input)?;
+        if xs.id() == ys.id() {
+            return self.sets.unite(key1, key2).map_err(invalid_input);
+        }
+        self.append(&Op::<&Key, ()>::Unite(key1, key2))?;
+        let united = self.sets.unite(key1, key2).map_err(invalid_input)?;
+        self.count_pending()?;
+        Ok(united)
+    }
+
+    /// Flushes all logged mutations.
+    pub fn commit(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        self.pending = 0;
+        Ok(())
+    }
+
+    /// Commits, and gives back the sets and the writer.
+    pub fn into_parts(mut self) -> io::Result<(UnionFindSets<Key, Tag>, W)> {
+        self.commit()?;
+        Ok((self.sets, self.writer))
+    }
+
+    fn append<K: BorshSerialize, T: BorshSerialize>(&mut self, op: &Op<K, T>) -> io::Result<()> {
+        let record = borsh::to_vec(op)?;
+        let len = u32::try_from(record.len())
+            .map_err(|_| io::Error::new(ErrorKind::InvalidData, "too large a record"))?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(&record)
+    }
+
+    /// Counts a mutation which is logged and then applied, committing a full group.
+    fn count_pending(&mut self) -> io::Result<()> {
+        self.pending += 1;
+        if self.group > 0 && self.pending >= self.group {
+            self.commit()?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid_input(err: Error) -> io::Error {
+    io::Error::new(ErrorKind::InvalidInput, err)
+}
+
+/// Rebuilds sets by replaying a log written by [Wal].
+///
+/// The log ends at its end or at a torn record.
+/// If a complete record cannot be decoded or replayed, [ErrorKind::InvalidData] will be raised.
+pub fn recover<Key, Tag, R>(mut reader: R) -> io::Result<UnionFindSets<Key, Tag>>
+where
+    Key: Eq + Hash + Clone + BorshDeserialize,
+    Tag: Mergable + BorshDeserialize,
+    R: Read,
+{
+    let mut sets = UnionFindSets::new();
+    while let Some(record) = read_record(&mut reader)? {
+        let op: Op<Key, Tag> = borsh::from_slice(&record)?;
+        let res = match op {
+            Op::MakeSet(key, tag) => sets.make_set(key, tag),
+            Op::Unite(x, y) => sets.unite(&x, &y).map(|_| ()),
+            Op::Find(_) => Ok(()),
+            Op::Remove(key) => {
+                sets.take_set(&key);
+                Ok(())
+            }
+        };
+        res.map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+    }
+    Ok(sets)
+}
+
+fn read_record<R: Read>(reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 4];
+    if !read_all(reader, &mut len)? {
+        return Ok(None);
+    }
+    // a torn length may be anything, so the record is not allocated up front
+    let len = u64::from(u32::from_le_bytes(len));
+    let mut record = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut record)?;
+    Ok((record.len() as u64 == len).then_some(record))
+}
+
+/// Fills up `buf`, or tells the reader ends before that.
+fn read_all<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    match reader.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => Ok(false),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,12 @@
+//! Write-ahead logging of sets, for durability without periodic snapshots.
+//!
+//! Every successful mutation is appended to a writer as a length-prefixed
+//! [borsh](https://borsh.io) record before it is applied to the sets,
+//! and sets are recovered by replaying records.
+//! A record torn by a crash in the middle of writing it ends the log.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,120 @@
+use super::*;
+use crate::UnionFindSets;
+use std::collections::BTreeSet;
+
+fn state(sets: &UnionFindSets<u32, BTreeSet<u32>>) -> BTreeSet<(BTreeSet<u32>, BTreeSet<u32>)> {
+    sets.iter()
+        .map(|xs| (xs.iter().copied().collect(), xs.tag().clone()))
+        .collect()
+}
+
+#[test]
+fn recover_from_log() {
+    let mut wal = Wal::new(Vec::new());
+    for x in 0..10u32 {
+        wal.make_set(x, BTreeSet::from([x])).unwrap();
+    }
+    assert!(wal.make_set(3, BTreeSet::new()).is_err());
+    for x in 1..5u32 {
+        assert!(wal.unite(&(x - 1), &x).unwrap());
+    }
+    assert!(!wal.unite(&4, &0).unwrap());
+    assert_eq!(
+        wal.unite(&0, &10).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+    let (sets, log) = wal.into_parts().unwrap();
+    let recovered = recover(log.as_slice()).unwrap();
+    assert_eq!(state(&recovered), state(&sets));
+
+    let mut wal = Wal::resume(recovered, log);
+    wal.unite(&8, &9).unwrap();
+    let (sets, log) = wal.into_parts().unwrap();
+    assert_eq!(state(&recover(log.as_slice()).unwrap()), state(&sets));
+}
+
+#[test]
+fn torn_records_end_log() {
+    let mut wal = Wal::new(Vec::new());
+    wal.make_set(0u32, BTreeSet::from([0u32])).unwrap();
+    wal.make_set(1, BTreeSet::from([1])).unwrap();
+    let (_, complete) = wal.into_parts().unwrap();
+    let sets: UnionFindSets<u32, BTreeSet<u32>> = recover(complete.as_slice()).unwrap();
+    let mut wal = Wal::resume(sets, complete.clone());
+    wal.unite(&0, &1).unwrap();
+    let (_, log) = wal.into_parts().unwrap();
+    for end in complete.len()..log.len() {
+        let sets: UnionFindSets<u32, BTreeSet<u32>> = recover(&log[..end]).unwrap();
+        assert_eq!(sets.len(), 2);
+    }
+    let sets: UnionFindSets<u32, BTreeSet<u32>> = recover(log.as_slice()).unwrap();
+    assert_eq!(sets.len(), 1);
+}
+
+#[test]
+fn group_commit() {
+    struct Flushes(usize);
+
+    impl std::io::Write for Flushes {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.0 += 1;
+            Ok(())
+        }
+    }
+
+    let mut wal = Wal::new(Flushes(0));
+    wal.set_group_commit(4);
+    for x in 0..10u32 {
+        wal.make_set(x, ()).unwrap();
+    }
+    let (_, flushes) = wal.into_parts().unwrap();
+    assert_eq!(flushes.0, 3);
+}
+
+#[test]
+fn failed_writes_leave_sets() {
+    struct Failing {
+        log: Vec<u8>,
+        fail: std::rc::Rc<std::cell::Cell<bool>>,
+    }
+
+    impl std::io::Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.fail.get() {
+                return Err(std::io::Error::other("disk full"));
+            }
+            self.log.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let fail = std::rc::Rc::new(std::cell::Cell::new(false));
+    let mut wal = Wal::new(Failing {
+        log: Vec::new(),
+        fail: fail.clone(),
+    });
+    for x in 0..3u32 {
+        wal.make_set(x, BTreeSet::from([x])).unwrap();
+    }
+    fail.set(true);
+    assert_eq!(
+        wal.make_set(3, BTreeSet::from([3])).unwrap_err().kind(),
+        std::io::ErrorKind::Other
+    );
+    assert!(!wal.sets().contains(&3));
+    assert!(wal.unite(&0, &1).is_err());
+    assert_eq!(wal.sets().len(), 3);
+    fail.set(false);
+    assert!(wal.unite(&1, &2).unwrap());
+    let (sets, writer) = wal.into_parts().unwrap();
+    assert_eq!(sets.len(), 2);
+    let recovered = recover(writer.log.as_slice()).unwrap();
+    assert_eq!(state(&recovered), state(&sets));
+}
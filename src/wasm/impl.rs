@@ -0,0 +1,144 @@
+use crate::{Error, Mergable, UnionFindSets, Which};
+use alloc::string::{String, ToString};
+use js_sys::{Array, Function, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+/// A string or a number, by the bits of the number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum JsKey {
+    Str(String),
+    Num(u64),
+}
+
+impl JsKey {
+    fn new(key: &JsValue) -> Result<Self, JsError> {
+        if let Some(x) = key.as_string() {
+            return Ok(JsKey::Str(x));
+        }
+        match key.as_f64() {
+            Some(x) => JsKey::from_f64(x).map_err(JsError::new),
+            None => Err(JsError::new("keys must be strings or numbers")),
+        }
+    }
+
+    pub(crate) fn from_f64(x: f64) -> Result<Self, &'static str> {
+        match x {
+            x if x.is_nan() => Err("NaN cannot be a key"),
+            // +0 and -0 are the same key, as they are in a JS `Map`
+            0.0 => Ok(JsKey::Num(0)),
+            x => Ok(JsKey::Num(x.to_bits())),
+        }
+    }
+
+    fn to_js(&self) -> JsValue {
+        match self {
+            JsKey::Str(x) => JsValue::from_str(x),
+            JsKey::Num(x) => JsValue::from_f64(f64::from_bits(*x)),
+        }
+    }
+}
+
+/// A JSON-like JS value as a tag.
+///
+/// Merging keeps the absorbing tag as it is.
+/// The merged tag is computed by the JS function before sets are united,
+/// so that exceptions it throws can be propagated, and then replaces the kept one.
+struct JsTag(JsValue);
+
+impl Mergable for JsTag {
+    fn merge(&mut self, _other: Self) {}
+}
+
+/// Union-find sets for JS, whose tags are merged by `merge(tag1, tag2)`.
+#[wasm_bindgen]
+pub struct JsUnionFind {
+    sets: UnionFindSets<JsKey, JsTag>,
+    merge: Function,
+}
+
+#[wasm_bindgen]
+impl JsUnionFind {
+    /// Makes new, empty sets, whose tags are merged by `merge(tag1, tag2)`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(merge: Function) -> JsUnionFind {
+        JsUnionFind {
+            sets: UnionFindSets::new(),
+            merge,
+        }
+    }
+
+    /// Makes a singleton set of `key`, tagged by `tag`.
+    #[wasm_bindgen(js_name = makeSet)]
+    pub fn make_set(&mut self, key: JsValue, tag: JsValue) -> Result<(), JsError> {
+        self.sets
+            .make_set(JsKey::new(&key)?, JsTag(tag))
+            .map_err(to_js_error)
+    }
+
+    /// Unites sets of two keys, and tells whether they were apart.
+    ///
+    /// What `merge` throws is thrown, leaving the sets as they are.
+    pub fn unite(&mut self, key1: JsValue, key2: JsValue) -> Result<bool, JsValue> {
+        let (key1, key2) = (JsKey::new(&key1)?, JsKey::new(&key2)?);
+        let merged = match (self.sets.find(&key1), self.sets.find(&key2)) {
+            (Some(x), Some(y)) if x == y => return Ok(false),
+            (Some(x), Some(y)) => self.merge.call2(&JsValue::NULL, &x.tag().0, &y.tag().0)?,
+            (x, y) => return Err(to_js_error(not_found(x.is_some(), y.is_some())).into()),
+        };
+        self.sets.unite(&key1, &key2).map_err(to_js_error)?;
+        if let Some(mut set) = self.sets.find_mut(&key1) {
+            *set.tag_mut() = JsTag(merged);
+        }
+        Ok(true)
+    }
+
+    /// Finds the set of `key`, as `{ key, elements, tag }` where `key` is its representative,
+    /// or `undefined` if `key` is not there.
+    pub fn find(&self, key: JsValue) -> Result<JsValue, JsValue> {
+        let Some(set) = self.sets.find(&JsKey::new(&key)?) else {
+            return Ok(JsValue::UNDEFINED);
+        };
+        let found = Object::new();
+        Reflect::set(&found, &"key".into(), &set.key().to_js())?;
+        let elements: Array = set.iter().map(JsKey::to_js).collect();
+        Reflect::set(&found, &"elements".into(), &elements)?;
+        Reflect::set(&found, &"tag".into(), &set.tag().0)?;
+        Ok(found.into())
+    }
+
+    /// Lists all sets, each as `[elements, tag]`.
+    pub fn sets(&self) -> Array {
+        self.sets
+            .iter()
+            .map(|set| {
+                let elements: Array = set.iter().map(JsKey::to_js).collect();
+                Array::of2(&elements, &set.tag().0)
+            })
+            .collect()
+    }
+
+    /// Tests if `key` is in the sets.
+    pub fn has(&self, key: JsValue) -> Result<bool, JsError> {
+        Ok(self.sets.find(&JsKey::new(&key)?).is_some())
+    }
+
+    /// The number of sets.
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.sets.len()
+    }
+}
+
+/// Tells which of two keys are missing, when they cannot be united.
+pub(crate) fn not_found(found1: bool, found2: bool) -> Error {
+    let which = match (found1, found2) {
+        (false, false) => Which::Both,
+        (false, _) => Which::Key1,
+        _ => Which::Key2,
+    };
+    Error::KeyNotFound { which }
+}
+
+fn to_js_error(err: Error) -> JsError {
+    JsError::new(&err.to_string())
+}
@@ -0,0 +1,8 @@
+//! JS bindings by [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/),
+//! where keys are strings or numbers and tags are merged by a JS function.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,74 @@
+use super::r#impl::{not_found, JsKey};
+use crate::{Error, Which};
+
+#[test]
+fn zeros_are_one_key() {
+    assert_eq!(JsKey::from_f64(0.0), JsKey::from_f64(-0.0));
+    assert_eq!(JsKey::from_f64(0.0), Ok(JsKey::Num(0)));
+    assert_ne!(JsKey::from_f64(1.0), JsKey::from_f64(-1.0));
+}
+
+#[test]
+fn nan_is_no_key() {
+    assert!(JsKey::from_f64(f64::NAN).is_err());
+    assert!(JsKey::from_f64(-f64::NAN).is_err());
+    assert!(JsKey::from_f64(f64::INFINITY).is_ok());
+}
+
+#[test]
+fn numbers_keep_their_bits() {
+    for x in [1.0, -1.5, f64::MAX, f64::MIN_POSITIVE, f64::NEG_INFINITY] {
+        assert_eq!(JsKey::from_f64(x), Ok(JsKey::Num(x.to_bits())));
+    }
+    assert_ne!(JsKey::from_f64(1.0), Ok(JsKey::Str("1".into())));
+}
+
+#[test]
+fn missing_keys() {
+    let which = |found1, found2| match not_found(found1, found2) {
+        Error::KeyNotFound { which } => which,
+        err => panic!("unexpected {err}"),
+    };
+    assert_eq!(which(false, false), Which::Both);
+    assert_eq!(which(false, true), Which::Key1);
+    assert_eq!(which(true, false), Which::Key2);
+}
+
+#[cfg(target_arch = "wasm32")]
+mod js {
+    use super::super::JsUnionFind;
+    use js_sys::{Function, Reflect};
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::*;
+
+    fn tag_of(sets: &JsUnionFind, key: impl Into<JsValue>) -> JsValue {
+        let found = sets.find(key.into()).unwrap();
+        Reflect::get(&found, &"tag".into()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn unite_merges_tags() {
+        let mut sets = JsUnionFind::new(Function::new_with_args("x, y", "return x + y"));
+        for x in [1.0, 2.0, 3.0] {
+            sets.make_set(x.into(), x.into()).unwrap();
+        }
+        assert!(sets.unite(1.0.into(), 2.0.into()).unwrap());
+        assert!(!sets.unite(2.0.into(), 1.0.into()).unwrap());
+        // the merged tag is computed from tags before the union, and then replaces the kept one
+        assert_eq!(tag_of(&sets, 1.0), JsValue::from_f64(3.0));
+        assert_eq!(tag_of(&sets, 2.0), JsValue::from_f64(3.0));
+        assert!(sets.unite(3.0.into(), (-0.0).into()).is_err());
+        assert_eq!(sets.size(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn throwing_merge_leaves_sets_apart() {
+        let mut sets = JsUnionFind::new(Function::new_with_args("x, y", "throw x"));
+        sets.make_set("a".into(), 1.0.into()).unwrap();
+        sets.make_set("b".into(), 2.0.into()).unwrap();
+        assert!(sets.unite("a".into(), "b".into()).is_err());
+        assert_eq!(sets.size(), 2);
+        assert_eq!(tag_of(&sets, "a"), JsValue::from_f64(1.0));
+        assert_eq!(tag_of(&sets, "b"), JsValue::from_f64(2.0));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{Mergable, Result, Set, UnionFindSets};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+/// A callback of a watcher, called with the set which has just reached its threshold.
+type Callback<Key, Tag> = Box<dyn FnMut(&Set<'_, Key, Tag>)>;
+
+/// Sets with watchers on their sizes, see [on_set_reaches](Self::on_set_reaches).
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use tagged_ufs::watch::WatchedSets;
+///
+/// let alerts = Rc::new(RefCell::new(vec![]));
+/// let mut sets = WatchedSets::new();
+/// let sink = alerts.clone();
+/// sets.on_set_reaches(3, move |xs| sink.borrow_mut().push(xs.len()));
+/// for x in 0..4u32 {
+///     sets.make_set(x, ()).unwrap();
+/// }
+/// sets.unite(&0, &1).unwrap();
+/// sets.unite(&1, &2).unwrap();
+/// sets.unite(&2, &3).unwrap();
+/// assert_eq!(*alerts.borrow(), vec![3]);
+/// ```
+pub struct WatchedSets<Key, Tag>
+where
+    Key: Eq + Hash,
+    Tag: Mergable,
+{
+    sets: UnionFindSets<Key, Tag>,
+    watchers: Vec<(usize, Callback<Key, Tag>)>,
+}
+
+impl<Key, Tag> Default for WatchedSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Key, Tag> WatchedSets<Key, Tag>
+where
+    Key: Eq + Hash + Clone,
+    Tag: Mergable,
+{
+    /// Makes new, empty sets without any watcher.
+    pub fn new() -> Self {
+        Self::from_sets(UnionFindSets::new())
+    }
+
+    /// Watches existing sets, where sets already as large as a threshold never fire its watcher.
+    pub fn from_sets(sets: UnionFindSets<Key, Tag>) -> Self {
+        Self {
+            sets,
+            watchers: Vec::new(),
+        }
+    }
+
+    /// Registers a watcher, whose `callback` is called during [unite](Self::unite)
+    /// (or [make_set](Self::make_set) if `threshold` is at most 1)
+    /// as soon as a set has at least `threshold` elements.
+    ///
+    /// A threshold of 0 never fires, as every set has reached it before it is made.
+    pub fn on_set_reaches<F>(&mut self, threshold: usize, callback: F)
+    where
+        F: FnMut(&Set<'_, Key, Tag>) + 'static,
+    {
+        self.watchers.push((threshold, Box::new(callback)));
+    }
+
+    /// Makes an individual set with a singleton element and its associated tag,
+    /// like [UnionFindSets::make_set].
+    pub fn make_set(&mut self, key: Key, tag: Tag) -> Result<()> {
+        let watched = !self.watchers.is_empty();
+        let probe = if watched { Some(key.clone()) } else { None };
+        self.sets.make_set(key, tag)?;
+        if let Some(key) = probe {
+            self.fire(&key, 0);
+        }
+        Ok(())
+    }
+
+    /// Unites two sets, like [UnionFindSets::unite],
+    /// calling back watchers whose thresholds the united set has just reached.
+    pub fn unite<Q1, Q2>(&mut self, key1: &Q1, key2: &Q2) -> Result<bool>
+    where
+        Key: Borrow<Q1> + Borrow<Q2>,
+        Q1: Hash + Eq + ?Sized,
+        Q2: Hash + Eq + ?Sized,
+    {
+        if self.watchers.is_empty() {
+            return self.sets.unite(key1, key2);
+        }
+        let before = match (self.sets.find(key1), self.sets.find(key2)) {
+            (Some(xs), Some(ys)) => xs.len().max(ys.len()),
+            _ => 0,
+        };
+        let united = self.sets.unite(key1, key2)?;
+        if united {
+            self.fire(key1, before);
+        }
+        Ok(united)
+    }
+
+    /// Gets the sets being watched.
+    pub fn sets(&self) -> &UnionFindSets<Key, Tag> {
+        &self.sets
+    }
+
+    /// Stops watching, giving the sets back.
+    pub fn into_sets(self) -> UnionFindSets<Key, Tag> {
+        self.sets
+    }
+
+    /// Calls back watchers whose thresholds are above `before` but reached by the set of `key`.
+    fn fire<Q>(&mut self, key: &Q, before: usize)
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(xs) = self.sets.find(key) else {
+            return;
+        };
+        let after = xs.len();
+        for (threshold, callback) in self.watchers.iter_mut() {
+            if before < *threshold && *threshold <= after {
+                callback(&xs);
+            }
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Sets which call back watchers as soon as a set grows to a size of interest.
+//!
+//! Sizes of sets only grow, so a set reaches a threshold at most once:
+//! either when it is made, or when it is united from two sets both smaller than that.
+
+mod r#impl;
+pub use self::r#impl::*;
+
+#[cfg(test)]
+mod test;
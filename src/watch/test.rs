@@ -0,0 +1,58 @@
+use super::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[quickcheck_macros::quickcheck]
+fn fire_once_per_crossing(adds: Vec<u8>, connects: Vec<(u8, u8)>, threshold: u8) {
+    let threshold = 1 + threshold as usize % 8;
+    let fired = Rc::new(RefCell::new(Vec::new()));
+    let mut sets = WatchedSets::new();
+    let sink = fired.clone();
+    sets.on_set_reaches(threshold, move |xs| {
+        sink.borrow_mut()
+            .push(xs.iter().copied().collect::<Vec<u8>>());
+    });
+    for x in adds {
+        let _ = sets.make_set(x, ());
+    }
+    for (x, y) in connects {
+        let _ = sets.unite(&x, &y);
+    }
+    // once a set has fired, no set containing it fires again,
+    // so sets fired with are disjoint, and every set as large contains one of them
+    let fired = fired.borrow();
+    let mut elements: Vec<u8> = fired.iter().flatten().copied().collect();
+    let total = elements.len();
+    elements.sort();
+    elements.dedup();
+    assert_eq!(elements.len(), total);
+    for xs in fired.iter() {
+        assert!(xs.len() >= threshold);
+    }
+    for xs in sets.sets().iter().filter(|xs| xs.len() >= threshold) {
+        assert!(fired.iter().any(|ys| xs.contains(&ys[0])));
+    }
+}
+
+#[test]
+fn several_thresholds() {
+    let fired = Rc::new(RefCell::new(Vec::new()));
+    let mut sets = WatchedSets::new();
+    for threshold in [2, 3, 5] {
+        let sink = fired.clone();
+        sets.on_set_reaches(threshold, move |xs| {
+            sink.borrow_mut().push((threshold, xs.len()))
+        });
+    }
+    for x in 0..6u32 {
+        sets.make_set(x, ()).unwrap();
+    }
+    sets.unite(&0, &1).unwrap();
+    sets.unite(&2, &3).unwrap();
+    assert!(!sets.unite(&1, &0).unwrap());
+    sets.unite(&0, &2).unwrap();
+    assert!(sets.unite(&0, &9).is_err());
+    sets.unite(&4, &0).unwrap();
+    assert_eq!(*fired.borrow(), vec![(2, 2), (2, 2), (3, 4), (5, 5)]);
+    assert_eq!(sets.into_sets().len(), 2);
+}